@@ -17,3 +17,54 @@ pub fn random_nonce() -> [u8; 16] {
     nonce
 }
 
+/// Decode a hex string into exactly `N` bytes
+///
+/// Distinguishes empty (or whitespace-only) input, odd length, the
+/// first non-hex character (including whitespace) and a wrong decoded
+/// length, instead of a bare
+/// [`Error::InvalidHex`](crate::error::Error::InvalidHex).
+pub fn decode_hex<const N: usize>(hex_str: &str) -> crate::error::Result<[u8; N]> {
+    use crate::error::Error;
+
+    if hex_str.trim().is_empty() {
+        return Err(Error::EmptyHex);
+    }
+    let bytes = hex::decode(hex_str).map_err(|e| match e {
+        hex::FromHexError::InvalidHexCharacter { c, index } => {
+            Error::InvalidHexCharacter { character: c, position: index }
+        }
+        hex::FromHexError::OddLength => Error::OddHexLength(hex_str.len()),
+        hex::FromHexError::InvalidStringLength => Error::InvalidHex,
+    })?;
+
+    bytes.as_slice().try_into().map_err(|_| Error::WrongDecodedLength {
+        expected: N,
+        got: bytes.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_decode_hex_malformed() {
+        assert_eq!(decode_hex::<2>("0aff").unwrap(), [0x0a, 0xff]);
+        assert!(matches!(decode_hex::<2>(""), Err(Error::EmptyHex)));
+        assert!(matches!(decode_hex::<2>(" \t\n"), Err(Error::EmptyHex)));
+        assert!(matches!(decode_hex::<2>("0af"), Err(Error::OddHexLength(3))));
+        assert!(matches!(
+            decode_hex::<2>("0a f"),
+            Err(Error::InvalidHexCharacter { character: ' ', position: 2 })
+        ));
+        assert!(matches!(
+            decode_hex::<2>("0azf"),
+            Err(Error::InvalidHexCharacter { character: 'z', position: 2 })
+        ));
+        assert!(matches!(
+            decode_hex::<2>("0aff00"),
+            Err(Error::WrongDecodedLength { expected: 2, got: 3 })
+        ));
+    }
+}
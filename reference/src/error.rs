@@ -9,6 +9,11 @@ pub enum Error {
     #[error("invalid key length")]
     InvalidKeyLength,
 
+    /// Key bytes that aren't a usable Ed25519 point: off the curve,
+    /// non-canonically encoded, or of small order
+    #[error("invalid public key point")]
+    InvalidPublicKey,
+
     /// Invalid HIT length
     #[error("invalid HIT length (expected 16 bytes)")]
     InvalidHitLength,
@@ -17,6 +22,33 @@ pub enum Error {
     #[error("invalid hex encoding")]
     InvalidHex,
 
+    /// Hex string that is empty or only whitespace, e.g. an unset
+    /// environment variable
+    #[error("empty hex input")]
+    EmptyHex,
+
+    /// Hex string with an odd number of characters
+    #[error("odd hex length: {0} characters")]
+    OddHexLength(usize),
+
+    /// Character outside `[0-9a-fA-F]` in a hex string
+    #[error("non-hex character {character:?} at position {position}")]
+    InvalidHexCharacter {
+        /// Offending character
+        character: char,
+        /// Character index in the hex string
+        position: usize,
+    },
+
+    /// Hex decoded to the wrong number of bytes
+    #[error("wrong decoded length: expected {expected} bytes, got {got}")]
+    WrongDecodedLength {
+        /// Bytes the type requires
+        expected: usize,
+        /// Bytes actually decoded
+        got: usize,
+    },
+
     /// Invalid handle format
     #[error("invalid handle format: {0}")]
     InvalidHandle(String),
@@ -37,6 +69,15 @@ pub enum Error {
     #[error("unknown message type: {0}")]
     UnknownMessageType(u8),
 
+    /// Frame carries a protocol version this implementation does not speak
+    #[error("unsupported protocol version {got} (supported: {supported})")]
+    UnsupportedVersion {
+        /// Version the peer sent
+        got: u8,
+        /// Version this implementation speaks
+        supported: u8,
+    },
+
     /// Invalid state transition
     #[error("invalid state transition")]
     InvalidStateTransition,
@@ -136,9 +177,16 @@ pub enum ErrorCode {
 impl From<&Error> for ErrorCode {
     fn from(err: &Error) -> Self {
         match err {
-            Error::InvalidKeyLength | Error::InvalidHitLength | Error::InvalidMessageFormat => {
-                ErrorCode::InvalidFormat
-            }
+            Error::InvalidKeyLength
+            | Error::InvalidPublicKey
+            | Error::InvalidHitLength
+            | Error::InvalidMessageFormat
+            | Error::UnsupportedVersion { .. }
+            | Error::InvalidHex
+            | Error::EmptyHex
+            | Error::OddHexLength(_)
+            | Error::InvalidHexCharacter { .. }
+            | Error::WrongDecodedLength { .. } => ErrorCode::InvalidFormat,
             Error::InvalidSignature | Error::SignatureVerificationFailed => {
                 ErrorCode::InvalidSignature
             }
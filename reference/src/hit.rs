@@ -3,25 +3,57 @@
 //! The HIT is derived from the Human Identity (public key) using SHA-256:
 //!
 //! ```text
-//! HIT = SHA-256(HI)[0:16]
+//! HIT = SHA-256(domain || HI)[0:16]
 //! ```
 //!
+//! `domain` is the key's [`SignatureScheme::hit_domain`](crate::identity::SignatureScheme::hit_domain),
+//! empty for Ed25519, so HITs of different schemes never collide.
+//!
 //! HITs are used for:
 //! - Compact routing identifiers (16 bytes vs 32)
 //! - Protocol message headers
 //! - Peer lookup tables
 //! - HIP (RFC 7401) interoperability
+//!
+//! ## Salted HITs
+//!
+//! The plain HIT is a public function of the key, so anyone holding a
+//! list of candidate keys can precompute their HITs and recognise them
+//! wherever they appear. When a HIT is shown in a context that should
+//! not be linkable to the node's routing identity (a per-service
+//! pseudonym, an anonymized export), derive it with
+//! [`Hit::from_public_key_salted`] and a salt chosen for that context:
+//!
+//! ```text
+//! HIT = SHA-256("TRIP-HIT-SALTED-v1" || len(salt) || salt || domain || HI)[0:16]
+//! ```
+//!
+//! `len(salt)` is 8 bytes big-endian. A salted HIT only verifies
+//! against the same salt ([`Hit::matches_salted`]), and HITs under
+//! different salts cannot be linked without it. Routing, protocol
+//! headers and HIP always use the unsalted HIT; peers cannot find a
+//! node by a salted one.
+//!
+//! HITs are compared in constant time. A peer lookup that returns as
+//! soon as the first byte differs leaks, through its response time,
+//! how long a prefix a probe shares with each stored HIT; an attacker
+//! on the network can use that to enumerate the peers a node knows.
+//! `==` and [`Hit::ct_eq`] take the same time whatever the contents.
 
 use crate::identity::PublicKey;
 use crate::error::{Error, Result};
 use sha2::{Sha256, Digest};
 use std::fmt;
+use subtle::ConstantTimeEq;
 
 /// Size of HIT in bytes
 pub const HIT_SIZE: usize = 16;
 
+/// Domain separator for salted HITs, so no salt reproduces a plain HIT
+const SALTED_HIT_DOMAIN: &[u8] = b"TRIP-HIT-SALTED-v1";
+
 /// Human Identity Tag - 128-bit identifier derived from public key
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Eq, Hash)]
 pub struct Hit([u8; HIT_SIZE]);
 
 impl Hit {
@@ -42,9 +74,28 @@ impl Hit {
 
     /// Derive HIT from a public key
     ///
-    /// HIT = SHA-256(PublicKey)[0:16]
+    /// HIT = SHA-256(domain || PublicKey)[0:16]
     pub fn from_public_key(public_key: &PublicKey) -> Self {
-        let hash = Sha256::digest(public_key.as_bytes());
+        let mut hasher = Sha256::new();
+        hasher.update(public_key.scheme().hit_domain());
+        hasher.update(public_key.as_bytes());
+        let hash = hasher.finalize();
+        let mut bytes = [0u8; HIT_SIZE];
+        bytes.copy_from_slice(&hash[..HIT_SIZE]);
+        Self(bytes)
+    }
+
+    /// Derive a context-specific HIT from a public key and a salt
+    ///
+    /// Not routable; see the module docs for when to use it.
+    pub fn from_public_key_salted(public_key: &PublicKey, salt: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(SALTED_HIT_DOMAIN);
+        hasher.update((salt.len() as u64).to_be_bytes());
+        hasher.update(salt);
+        hasher.update(public_key.scheme().hit_domain());
+        hasher.update(public_key.as_bytes());
+        let hash = hasher.finalize();
         let mut bytes = [0u8; HIT_SIZE];
         bytes.copy_from_slice(&hash[..HIT_SIZE]);
         Self(bytes)
@@ -60,10 +111,9 @@ impl Hit {
         hex::encode(self.0)
     }
 
-    /// Parse from hex string
+    /// Parse from hex string (32 characters)
     pub fn from_hex(hex_str: &str) -> Result<Self> {
-        let bytes = hex::decode(hex_str).map_err(|_| Error::InvalidHex)?;
-        Self::from_slice(&bytes)
+        Ok(Self(crate::crypto::decode_hex(hex_str)?))
     }
 
     /// Get short display (first 8 hex chars)
@@ -73,8 +123,49 @@ impl Hit {
 
     /// Check if this HIT was derived from the given public key
     pub fn matches(&self, public_key: &PublicKey) -> bool {
-        let derived = Self::from_public_key(public_key);
-        self.0 == derived.0
+        *self == Self::from_public_key(public_key)
+    }
+
+    /// Check if this HIT was derived from the given public key and salt
+    pub fn matches_salted(&self, public_key: &PublicKey, salt: &[u8]) -> bool {
+        *self == Self::from_public_key_salted(public_key, salt)
+    }
+
+    /// Constant-time comparison, as a [`subtle::Choice`]
+    ///
+    /// For match logic over many stored HITs: combine the choices
+    /// with `&`, `|` and [`subtle::ConditionallySelectable`] rather
+    /// than branching on each, so no early exit reintroduces the
+    /// timing leak.
+    pub fn ct_eq(&self, other: &Hit) -> subtle::Choice {
+        self.0.ct_eq(&other.0)
+    }
+
+    /// XOR distance to another HIT (Kademlia metric)
+    pub fn xor_distance(&self, other: &Hit) -> [u8; HIT_SIZE] {
+        let mut distance = [0u8; HIT_SIZE];
+        for (d, (a, b)) in distance.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            *d = a ^ b;
+        }
+        distance
+    }
+
+    /// Number of leading bits shared with another HIT (0-128)
+    pub fn shared_prefix_bits(&self, other: &Hit) -> u32 {
+        let mut bits = 0;
+        for byte in self.xor_distance(other) {
+            if byte != 0 {
+                return bits + byte.leading_zeros();
+            }
+            bits += 8;
+        }
+        bits
+    }
+}
+
+impl PartialEq for Hit {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
     }
 }
 
@@ -168,6 +259,26 @@ mod tests {
         assert_eq!(id1.hit(), id2.hit());
     }
 
+    #[test]
+    fn test_salted_hit() {
+        let id = Identity::from_seed(&[1u8; 32]);
+        let key = id.public_key();
+
+        let a = Hit::from_public_key_salted(key, b"service-a");
+        let b = Hit::from_public_key_salted(key, b"service-b");
+        assert_ne!(a, b);
+        assert_ne!(a, id.hit());
+        assert_ne!(Hit::from_public_key_salted(key, b""), id.hit());
+        assert_eq!(a, Hit::from_public_key_salted(key, b"service-a"));
+
+        assert!(a.matches_salted(key, b"service-a"));
+        assert!(!a.matches_salted(key, b"service-b"));
+        assert!(!a.matches(key));
+        assert!(!id.hit().matches_salted(key, b""));
+        let other = Identity::from_seed(&[2u8; 32]);
+        assert!(!a.matches_salted(other.public_key(), b"service-a"));
+    }
+
     #[test]
     fn test_hit_from_slice_wrong_size() {
         assert!(Hit::from_slice(&[0u8; 8]).is_err());
@@ -175,6 +286,60 @@ mod tests {
         assert!(Hit::from_slice(&[0u8; 16]).is_ok());
     }
 
+    #[test]
+    fn test_hit_from_hex_errors() {
+        assert!(matches!(Hit::from_hex(""), Err(Error::EmptyHex)));
+        assert!(matches!(Hit::from_hex("\n"), Err(Error::EmptyHex)));
+        assert!(matches!(Hit::from_hex("abc"), Err(Error::OddHexLength(3))));
+        assert!(matches!(
+            Hit::from_hex(&format!("{}g", "0".repeat(31))),
+            Err(Error::InvalidHexCharacter { character: 'g', position: 31 })
+        ));
+        assert!(matches!(
+            Hit::from_hex(&"00".repeat(32)),
+            Err(Error::WrongDecodedLength { expected: 16, got: 32 })
+        ));
+    }
+
+    #[test]
+    fn test_xor_distance_and_shared_prefix() {
+        let a = Hit::from_bytes([0u8; HIT_SIZE]);
+        assert_eq!(a.xor_distance(&a), [0u8; HIT_SIZE]);
+        assert_eq!(a.shared_prefix_bits(&a), 128);
+
+        // Flip bit 19 (byte 2, third bit from the top)
+        let mut bytes = [0u8; HIT_SIZE];
+        bytes[2] = 0b0001_0000;
+        let b = Hit::from_bytes(bytes);
+        assert_eq!(a.xor_distance(&b), bytes);
+        assert_eq!(a.shared_prefix_bits(&b), 19);
+        assert_eq!(b.shared_prefix_bits(&a), 19);
+
+        // Differ in the very first bit
+        let mut bytes = [0u8; HIT_SIZE];
+        bytes[0] = 0x80;
+        assert_eq!(a.shared_prefix_bits(&Hit::from_bytes(bytes)), 0);
+    }
+
+    #[test]
+    fn test_ct_eq_agrees_with_byte_equality() {
+        let a = Identity::from_seed(&[1u8; 32]).hit();
+        let b = Identity::from_seed(&[2u8; 32]).hit();
+
+        let mut last_byte = *a.as_bytes();
+        last_byte[HIT_SIZE - 1] ^= 1;
+        let near = Hit::from_bytes(last_byte);
+
+        for (x, y) in [(a, a), (a, Hit::from_bytes(*a.as_bytes())), (a, b), (a, near)] {
+            // `==` is built on `ct_eq`, so check both against the bytes
+            let same_bytes = x.as_bytes()[..] == y.as_bytes()[..];
+            assert_eq!(bool::from(x.ct_eq(&y)), same_bytes);
+            assert_eq!(x == y, same_bytes);
+        }
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&near)));
+    }
+
     #[test]
     fn test_known_vector() {
         // Test vector from spec
@@ -6,6 +6,19 @@
 //! - **HIT**: SHA-256(HI)[0:16]
 //! - **Stellar Address**: StrKey encoding
 //! - **Facets**: HKDF-derived child keys
+//!
+//! The Stellar address is the same Ed25519 key under another encoding,
+//! so whoever controls the identity controls the account. With the
+//! `stellar` feature, [`Identity::stellar_binding_proof`] turns that
+//! into a [`StellarBinding`] a payment counterparty can check before
+//! sending funds: [`StellarBinding::verify`] decodes the address and
+//! requires it to be exactly the identity's key, then checks the HIT
+//! and the signature. A binding naming someone else's address fails
+//! the first check however it was signed.
+//!
+//! Keys carry a [`SignatureScheme`] tag so the identity layer can move to
+//! other algorithms (Ed448, post-quantum) later. Ed25519 is the only
+//! scheme implemented today.
 
 use crate::hit::Hit;
 use crate::error::{Error, Result};
@@ -13,14 +26,74 @@ use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
 use sha2::{Sha256, Digest};
 
-/// Ed25519 public key (Human Identity)
+/// Domain separator for Stellar binding statements, so a binding
+/// signature can't be replayed as any other signed message
+#[cfg(feature = "stellar")]
+const STELLAR_BINDING_DOMAIN: &[u8] = b"TRIP-STELLAR-BINDING-v1";
+
+/// Signature algorithm of a key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SignatureScheme {
+    /// Ed25519 (RFC 8032)
+    #[default]
+    Ed25519,
+}
+
+impl SignatureScheme {
+    /// Human-readable scheme name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignatureScheme::Ed25519 => "ed25519",
+        }
+    }
+
+    /// Prefix hashed before the key bytes when deriving a HIT
+    ///
+    /// Ed25519 keeps the original, unprefixed derivation so existing
+    /// HITs stay valid. Later schemes use `"trip-hit:<name>:"`, which
+    /// can never produce the same 32-byte hash input as an Ed25519 key.
+    pub fn hit_domain(&self) -> &'static [u8] {
+        match self {
+            SignatureScheme::Ed25519 => b"",
+        }
+    }
+}
+
+/// Public key (Human Identity), tagged with its signature scheme
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct PublicKey([u8; 32]);
+pub struct PublicKey {
+    scheme: SignatureScheme,
+    bytes: [u8; 32],
+}
 
 impl PublicKey {
-    /// Create from raw bytes
+    /// Create from raw Ed25519 bytes.
+    ///
+    /// The bytes are not checked; an invalid point only fails later,
+    /// at verification. Use [`PublicKey::from_bytes_validated`] for
+    /// keys from untrusted input.
     pub fn from_bytes(bytes: [u8; 32]) -> Self {
-        Self(bytes)
+        Self::from_bytes_with_scheme(SignatureScheme::Ed25519, bytes)
+    }
+
+    /// Create from raw Ed25519 bytes, rejecting anything that isn't a
+    /// canonical encoding of a point outside the small-order subgroup
+    pub fn from_bytes_validated(bytes: [u8; 32]) -> Result<Self> {
+        let key = VerifyingKey::from_bytes(&bytes).map_err(|_| Error::InvalidPublicKey)?;
+        if key.is_weak() || key.to_edwards().compress().to_bytes() != bytes {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// Create from raw bytes for the given scheme
+    pub fn from_bytes_with_scheme(scheme: SignatureScheme, bytes: [u8; 32]) -> Self {
+        Self { scheme, bytes }
+    }
+
+    /// Signature scheme of this key
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
     }
 
     /// Create from byte slice
@@ -30,12 +103,12 @@ impl PublicKey {
         }
         let mut bytes = [0u8; 32];
         bytes.copy_from_slice(slice);
-        Ok(Self(bytes))
+        Ok(Self::from_bytes(bytes))
     }
 
     /// Get raw bytes
     pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
+        &self.bytes
     }
 
     /// Derive HIT from this public key
@@ -45,13 +118,12 @@ impl PublicKey {
 
     /// Convert to hex string
     pub fn to_hex(&self) -> String {
-        hex::encode(self.0)
+        hex::encode(self.bytes)
     }
 
-    /// Parse from hex string
+    /// Parse from hex string (64 characters)
     pub fn from_hex(hex_str: &str) -> Result<Self> {
-        let bytes = hex::decode(hex_str).map_err(|_| Error::InvalidHex)?;
-        Self::from_slice(&bytes)
+        Ok(Self::from_bytes(crate::crypto::decode_hex(hex_str)?))
     }
 
     /// Get short display (first 8 hex chars)
@@ -63,7 +135,7 @@ impl PublicKey {
     #[cfg(feature = "stellar")]
     #[cfg_attr(docsrs, doc(cfg(feature = "stellar")))]
     pub fn stellar_address(&self) -> String {
-        stellar_strkey::ed25519::PublicKey(self.0).to_string()
+        stellar_strkey::ed25519::PublicKey(self.bytes).to_string()
     }
 }
 
@@ -81,12 +153,13 @@ impl std::fmt::Display for PublicKey {
 
 impl AsRef<[u8]> for PublicKey {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        &self.bytes
     }
 }
 
-/// Ed25519 private key (kept secure)
+/// Private key (kept secure), tagged with its signature scheme
 pub struct PrivateKey {
+    scheme: SignatureScheme,
     signing_key: SigningKey,
 }
 
@@ -94,18 +167,23 @@ impl PrivateKey {
     /// Generate a new random private key
     pub fn generate() -> Self {
         let signing_key = SigningKey::generate(&mut OsRng);
-        Self { signing_key }
+        Self { scheme: SignatureScheme::Ed25519, signing_key }
     }
 
     /// Create from seed bytes (32 bytes)
     pub fn from_seed(seed: &[u8; 32]) -> Self {
         let signing_key = SigningKey::from_bytes(seed);
-        Self { signing_key }
+        Self { scheme: SignatureScheme::Ed25519, signing_key }
+    }
+
+    /// Signature scheme of this key
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
     }
 
     /// Get the corresponding public key
     pub fn public_key(&self) -> PublicKey {
-        PublicKey(self.signing_key.verifying_key().to_bytes())
+        PublicKey::from_bytes_with_scheme(self.scheme, self.signing_key.verifying_key().to_bytes())
     }
 
     /// Sign a message
@@ -161,9 +239,15 @@ impl Identity {
         self.private_key.sign(message)
     }
 
-    /// Verify a signature (static method)
+    /// Verify a signature (static method), using the key's scheme
     pub fn verify(public_key: &PublicKey, message: &[u8], signature: &[u8; 64]) -> bool {
-        let verifying_key = match VerifyingKey::from_bytes(&public_key.0) {
+        match public_key.scheme() {
+            SignatureScheme::Ed25519 => Self::verify_ed25519(public_key, message, signature),
+        }
+    }
+
+    fn verify_ed25519(public_key: &PublicKey, message: &[u8], signature: &[u8; 64]) -> bool {
+        let verifying_key = match VerifyingKey::from_bytes(public_key.as_bytes()) {
             Ok(k) => k,
             Err(_) => return false,
         };
@@ -181,6 +265,22 @@ impl Identity {
         self.public_key.stellar_address()
     }
 
+    /// Signed statement binding this identity's HIT and key to its
+    /// Stellar address
+    #[cfg(feature = "stellar")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stellar")))]
+    pub fn stellar_binding_proof(&self) -> StellarBinding {
+        let hit = self.hit();
+        let stellar_address = self.stellar_address();
+        let signature = self.sign(&StellarBinding::signed_bytes(&self.public_key, &hit, &stellar_address));
+        StellarBinding {
+            public_key: self.public_key,
+            hit,
+            stellar_address,
+            signature,
+        }
+    }
+
     /// Derive a facet identity
     pub fn derive_facet(&self, facet_name: &str) -> Identity {
         use hkdf::Hkdf;
@@ -204,6 +304,58 @@ impl Clone for Identity {
     }
 }
 
+/// Proof that a TRIP identity and a Stellar account are one key
+#[cfg(feature = "stellar")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stellar")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StellarBinding {
+    /// Identity key
+    pub public_key: PublicKey,
+    /// HIT of `public_key`
+    pub hit: Hit,
+    /// StrKey account address (`G...`)
+    pub stellar_address: String,
+    /// Signature by `public_key` over the statement
+    pub signature: [u8; 64],
+}
+
+#[cfg(feature = "stellar")]
+impl StellarBinding {
+    /// Bytes signed:
+    /// `domain || HI || HIT || len(address) || address`, with
+    /// `len(address)` 8 bytes big-endian
+    fn signed_bytes(public_key: &PublicKey, hit: &Hit, stellar_address: &str) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(STELLAR_BINDING_DOMAIN.len() + 56 + stellar_address.len());
+        bytes.extend_from_slice(STELLAR_BINDING_DOMAIN);
+        bytes.extend_from_slice(public_key.as_bytes());
+        bytes.extend_from_slice(hit.as_bytes());
+        bytes.extend_from_slice(&(stellar_address.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(stellar_address.as_bytes());
+        bytes
+    }
+
+    /// Check that the address is the StrKey encoding of the identity
+    /// key, that the HIT belongs to that key, and that the key signed
+    /// the statement
+    pub fn verify(&self) -> Result<()> {
+        let account = stellar_strkey::ed25519::PublicKey::from_string(&self.stellar_address)
+            .map_err(|_| Error::ProofVerificationFailed("not a Stellar account address".into()))?;
+        if account.0 != *self.public_key.as_bytes() {
+            return Err(Error::ProofVerificationFailed(
+                "Stellar address is not the identity key".into(),
+            ));
+        }
+        if !self.hit.matches(&self.public_key) {
+            return Err(Error::ProofVerificationFailed("HIT does not match identity key".into()));
+        }
+        let message = Self::signed_bytes(&self.public_key, &self.hit, &self.stellar_address);
+        if !Identity::verify(&self.public_key, &message, &self.signature) {
+            return Err(Error::SignatureVerificationFailed);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,4 +408,116 @@ mod tests {
         
         assert_eq!(id1.public_key().as_bytes(), id2.public_key().as_bytes());
     }
+
+    #[test]
+    fn test_from_hex_errors() {
+        let hex = Identity::from_seed(&[7u8; 32]).public_key().to_hex();
+        assert!(matches!(PublicKey::from_hex(""), Err(Error::EmptyHex)));
+        assert!(matches!(PublicKey::from_hex("  "), Err(Error::EmptyHex)));
+        assert!(matches!(PublicKey::from_hex(&hex[..63]), Err(Error::OddHexLength(63))));
+        assert!(matches!(
+            PublicKey::from_hex(&format!("{} ", &hex[..63])),
+            Err(Error::InvalidHexCharacter { character: ' ', position: 63 })
+        ));
+        assert!(matches!(
+            PublicKey::from_hex(&hex[..62]),
+            Err(Error::WrongDecodedLength { expected: 32, got: 31 })
+        ));
+    }
+
+    #[test]
+    fn test_from_bytes_validated() {
+        let id = Identity::from_seed(&[7u8; 32]);
+        let bytes = *id.public_key().as_bytes();
+        assert_eq!(PublicKey::from_bytes_validated(bytes).unwrap(), *id.public_key());
+
+        // Identity point (order 1), y = 2 (off the curve), and y = p + 3
+        // (a valid point, encoded non-canonically)
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut off_curve = [0u8; 32];
+        off_curve[0] = 2;
+        let mut non_canonical = [0xffu8; 32];
+        non_canonical[0] = 0xf0;
+        non_canonical[31] = 0x7f;
+        for bad in [identity, off_curve, non_canonical] {
+            assert!(matches!(PublicKey::from_bytes_validated(bad), Err(Error::InvalidPublicKey)));
+            // The unchecked constructor still takes them
+            assert_eq!(PublicKey::from_bytes(bad).as_bytes(), &bad);
+        }
+    }
+
+    #[test]
+    fn test_ed25519_scheme_tag() {
+        let id = Identity::from_seed(&[7u8; 32]);
+        assert_eq!(id.public_key().scheme(), SignatureScheme::Ed25519);
+        assert_eq!(id.private_key.scheme(), SignatureScheme::Ed25519);
+        assert_eq!(id.derive_facet("work").public_key().scheme(), SignatureScheme::Ed25519);
+
+        // Keys parsed from raw bytes default to Ed25519
+        let parsed = PublicKey::from_hex(&id.public_key().to_hex()).unwrap();
+        assert_eq!(parsed, *id.public_key());
+    }
+
+    #[test]
+    fn test_verify_routes_by_scheme() {
+        let id = Identity::from_seed(&[7u8; 32]);
+        let message = b"scheme dispatch";
+        let signature = id.sign(message);
+
+        let tagged = PublicKey::from_bytes_with_scheme(SignatureScheme::Ed25519, *id.public_key().as_bytes());
+        assert!(Identity::verify(&tagged, message, &signature));
+        assert_eq!(
+            Identity::verify(&tagged, message, &signature),
+            Identity::verify_ed25519(&tagged, message, &signature),
+        );
+        assert!(!Identity::verify(&tagged, b"other message", &signature));
+
+        // Ed25519 keeps the original HIT derivation
+        assert_eq!(SignatureScheme::Ed25519.hit_domain(), b"");
+        assert_eq!(id.hit().as_bytes()[..], Sha256::digest(id.public_key().as_bytes())[..16]);
+    }
+
+    #[cfg(feature = "stellar")]
+    #[test]
+    fn test_stellar_binding_verifies() {
+        let id = Identity::from_seed(&[7u8; 32]);
+        let binding = id.stellar_binding_proof();
+        assert!(binding.stellar_address.starts_with('G'));
+        assert_eq!(binding.stellar_address, id.stellar_address());
+        assert_eq!(binding.hit, id.hit());
+        binding.verify().unwrap();
+
+        // A tampered signature fails
+        let mut tampered = binding.clone();
+        tampered.signature[0] ^= 1;
+        assert!(matches!(tampered.verify(), Err(Error::SignatureVerificationFailed)));
+    }
+
+    #[cfg(feature = "stellar")]
+    #[test]
+    fn test_stellar_binding_rejects_foreign_address() {
+        let attacker = Identity::from_seed(&[7u8; 32]);
+        let victim = Identity::from_seed(&[8u8; 32]);
+
+        // Attacker claims the victim's account and signs the claim with
+        // their own key: the signature is good, the address isn't theirs
+        let mut forged = attacker.stellar_binding_proof();
+        forged.stellar_address = victim.stellar_address();
+        forged.signature = attacker.sign(&StellarBinding::signed_bytes(
+            attacker.public_key(),
+            &attacker.hit(),
+            &forged.stellar_address,
+        ));
+        assert!(matches!(forged.verify(), Err(Error::ProofVerificationFailed(_))));
+
+        // Not an account address at all
+        forged.stellar_address = "GNOTANADDRESS".into();
+        assert!(matches!(forged.verify(), Err(Error::ProofVerificationFailed(_))));
+
+        // Victim's key and address with the attacker's HIT
+        let mut wrong_hit = victim.stellar_binding_proof();
+        wrong_hit.hit = attacker.hit();
+        assert!(matches!(wrong_hit.verify(), Err(Error::ProofVerificationFailed(_))));
+    }
 }
@@ -29,7 +29,7 @@
 //!
 //! - `std` (default): Enable standard library support
 //! - `serde`: Enable serialization/deserialization
-//! - `stellar`: Enable Stellar address derivation
+//! - `stellar`: Enable Stellar address derivation and binding proofs
 //!
 //! ## Protocol Overview
 //!
@@ -55,18 +55,22 @@ pub mod session;
 pub mod messages;
 pub mod trust;
 pub mod trajectory;
+pub mod merkle;
 pub mod crypto;
 pub mod error;
 
 // Re-exports
-pub use identity::{Identity, PublicKey, PrivateKey};
+pub use identity::{Identity, PublicKey, PrivateKey, SignatureScheme};
+#[cfg(feature = "stellar")]
+pub use identity::StellarBinding;
 pub use hit::Hit;
 pub use handle::Handle;
 pub use handshake::{Handshake, HandshakeState};
 pub use session::Session;
-pub use messages::{Message, MessageType};
+pub use messages::{Message, MessageHeader, MessageType, VersionRange};
 pub use trust::{TrustLevel, TrustProof};
 pub use trajectory::{Breadcrumb, Epoch};
+pub use merkle::MerkleAccumulator;
 pub use error::{Error, Result};
 
 /// Protocol version
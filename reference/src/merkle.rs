@@ -0,0 +1,176 @@
+//! Merkle trees over epoch breadcrumbs
+//!
+//! The epoch `merkle_root` commits to every breadcrumb, so a verifier
+//! can check membership with a proof instead of the full history.
+//! The tree follows RFC 6962 (Certificate Transparency):
+//!
+//! ```text
+//! leaf(d)    = SHA-256(0x00 || d)
+//! node(l, r) = SHA-256(0x01 || l || r)
+//! MTH(D[n])  = node(MTH(D[0:k]), MTH(D[k:n]))   k = largest power of 2 < n
+//! ```
+//!
+//! The domain-separation prefixes stop a leaf from being passed off as
+//! an interior node. For a live epoch, [`MerkleAccumulator`] keeps only
+//! the roots of the perfect subtrees (the frontier) and updates in
+//! O(log n) per append, producing the same root as [`merkle_root`].
+
+use sha2::{Sha256, Digest};
+
+/// Hash of the empty tree
+pub fn empty_root() -> [u8; 32] {
+    Sha256::digest([]).into()
+}
+
+/// Leaf hash: SHA-256(0x00 || data)
+pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Interior node hash: SHA-256(0x01 || left || right)
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Batch Merkle root over already-hashed leaves
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => empty_root(),
+        1 => leaves[0],
+        n => {
+            let k = largest_power_of_two_below(n);
+            node_hash(&merkle_root(&leaves[..k]), &merkle_root(&leaves[k..]))
+        }
+    }
+}
+
+fn largest_power_of_two_below(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+/// Incremental Merkle root for an append-only leaf sequence
+///
+/// `frontier[h]` holds the root of a perfect subtree of 2^h leaves when
+/// bit h of `len` is set. Appending works like a binary counter: equal
+/// sized subtrees merge upwards, touching at most log2(n) nodes.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleAccumulator {
+    frontier: Vec<Option<[u8; 32]>>,
+    len: u64,
+}
+
+impl MerkleAccumulator {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a hashed leaf
+    pub fn push(&mut self, leaf: [u8; 32]) {
+        let mut carry = leaf;
+        for slot in self.frontier.iter_mut() {
+            match slot.take() {
+                Some(left) => carry = node_hash(&left, &carry),
+                None => {
+                    *slot = Some(carry);
+                    self.len += 1;
+                    return;
+                }
+            }
+        }
+        self.frontier.push(Some(carry));
+        self.len += 1;
+    }
+
+    /// Number of leaves appended
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// True if no leaves have been appended
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Current root, equal to [`merkle_root`] over the same leaves
+    pub fn root(&self) -> [u8; 32] {
+        // Smallest subtree is the rightmost; fold leftwards
+        let mut subtrees = self.frontier.iter().flatten();
+        let Some(&first) = subtrees.next() else {
+            return empty_root();
+        };
+        subtrees.fold(first, |acc, left| node_hash(left, &acc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<[u8; 32]> {
+        (0..n as u64).map(|i| leaf_hash(&i.to_be_bytes())).collect()
+    }
+
+    #[test]
+    fn test_incremental_matches_batch() {
+        let all = leaves(70);
+        let mut acc = MerkleAccumulator::new();
+        assert_eq!(acc.root(), merkle_root(&[]));
+
+        for (i, leaf) in all.iter().enumerate() {
+            acc.push(*leaf);
+            assert_eq!(acc.len(), i as u64 + 1);
+            assert_eq!(acc.root(), merkle_root(&all[..=i]), "after {} appends", i + 1);
+        }
+    }
+
+    #[test]
+    fn test_rfc6962_shape() {
+        let l = leaves(3);
+        let expected = node_hash(&node_hash(&l[0], &l[1]), &l[2]);
+        assert_eq!(merkle_root(&l), expected);
+    }
+
+    #[test]
+    fn test_epoch_incremental_root() {
+        use crate::identity::Identity;
+        use crate::trajectory::{Breadcrumb, Epoch};
+
+        let owner = *Identity::generate().public_key();
+        let crumb = |i: u64| Breadcrumb {
+            owner,
+            index: i,
+            timestamp: 1_700_000_000 + i * 600,
+            cell: 0x872a1072bffffff + i,
+            context: [i as u8; 32],
+            previous: [0u8; 32],
+            signature: [0u8; 64],
+        };
+
+        let mut epoch = Epoch {
+            owner,
+            breadcrumbs: Vec::new(),
+            merkle_root: empty_root(),
+            start_time: 1_700_000_000,
+            end_time: 1_700_000_000,
+            signature: [0u8; 64],
+        };
+        let mut acc = epoch.accumulator();
+        for i in 0..13 {
+            epoch.push_breadcrumb(&mut acc, crumb(i));
+            assert_eq!(epoch.merkle_root, epoch.compute_merkle_root());
+        }
+        assert_eq!(epoch.accumulator().root(), epoch.merkle_root);
+    }
+}
@@ -1,5 +1,32 @@
-//! Protocol messages - TODO: Implement
+//! Protocol messages
 //! See spec/MESSAGES.md for details
+//!
+//! A frame is the 56-byte common header of spec/MESSAGES.md §1, the
+//! payload and the sender's Ed25519 signature over both (integers
+//! big-endian):
+//!
+//! ```text
+//! offset  0  Version (1)
+//!         1  Msg Type (1)
+//!         2  Flags (2)
+//!         4  Sender HIT (16)
+//!        20  Recipient HIT (16)
+//!        36  Message ID (8)
+//!        44  Timestamp, Unix ms (8)
+//!        52  Payload Length (4)
+//!        56  Payload
+//!  56 + len  Signature (64)
+//! ```
+//!
+//! The version byte is checked before anything else is parsed, so a
+//! frame from a newer peer fails with `Error::UnsupportedVersion`
+//! instead of being misread. Peers agree on a version up front by
+//! exchanging a [`VersionRange`].
+
+use crate::error::{Error, Result};
+use crate::hit::Hit;
+use crate::identity::{Identity, PublicKey};
+use crate::{HIT_SIZE, PROTOCOL_VERSION, SIGNATURE_SIZE};
 
 /// Message type codes
 #[repr(u8)]
@@ -19,9 +46,323 @@ pub enum MessageType {
     Error = 0xF0,
 }
 
+impl TryFrom<u8> for MessageType {
+    type Error = Error;
+
+    fn try_from(code: u8) -> Result<Self> {
+        Ok(match code {
+            0x01 => Self::I1,
+            0x02 => Self::R1,
+            0x03 => Self::I2,
+            0x04 => Self::R2,
+            0x10 => Self::Data,
+            0x11 => Self::Ack,
+            0x12 => Self::Ping,
+            0x13 => Self::Pong,
+            0x14 => Self::Close,
+            0x20 => Self::Update,
+            0x21 => Self::UpdateAck,
+            0xF0 => Self::Error,
+            other => return Err(Error::UnknownMessageType(other)),
+        })
+    }
+}
+
+/// Size of the common header (spec/MESSAGES.md §1)
+pub const HEADER_SIZE: usize = 56;
+
+/// Offset of the Payload Length field in the header
+const PAYLOAD_LENGTH_OFFSET: usize = 52;
+
+/// Header flags (bits numbered from the most significant)
+pub mod flags {
+    /// E: payload is encrypted
+    pub const ENCRYPTED: u16 = 0x8000;
+    /// R: message is being relayed
+    pub const RELAY: u16 = 0x4000;
+    /// A: acknowledgment requested
+    pub const ACK_REQUESTED: u16 = 0x2000;
+    /// P: priority message
+    pub const PRIORITY: u16 = 0x1000;
+}
+
+/// Common header fields other than version and payload length, which
+/// framing fills in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageHeader {
+    /// Message type code
+    pub msg_type: MessageType,
+    /// Flag bits (see [`flags`])
+    pub flags: u16,
+    /// Sender's HIT
+    pub sender: Hit,
+    /// Recipient's HIT
+    pub recipient: Hit,
+    /// Unique message identifier
+    pub message_id: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+}
+
 /// Protocol message
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Message {
-    pub msg_type: MessageType,
+    /// Common header
+    pub header: MessageHeader,
+    /// Message-specific payload
     pub payload: Vec<u8>,
 }
 
+impl Message {
+    /// Create a message
+    pub fn new(header: MessageHeader, payload: Vec<u8>) -> Self {
+        Self { header, payload }
+    }
+
+    /// Header and payload as framed, tagged with `PROTOCOL_VERSION`:
+    /// the bytes the signature covers
+    ///
+    /// Fails with `InvalidMessageFormat` if the payload is too long
+    /// for the 4-byte length field.
+    pub fn signed_bytes(&self) -> Result<Vec<u8>> {
+        let length = u32::try_from(self.payload.len()).map_err(|_| Error::InvalidMessageFormat)?;
+        let header = &self.header;
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.payload.len() + SIGNATURE_SIZE);
+        bytes.push(PROTOCOL_VERSION);
+        bytes.push(header.msg_type as u8);
+        bytes.extend_from_slice(&header.flags.to_be_bytes());
+        bytes.extend_from_slice(header.sender.as_bytes());
+        bytes.extend_from_slice(header.recipient.as_bytes());
+        bytes.extend_from_slice(&header.message_id.to_be_bytes());
+        bytes.extend_from_slice(&header.timestamp_ms.to_be_bytes());
+        bytes.extend_from_slice(&length.to_be_bytes());
+        bytes.extend_from_slice(&self.payload);
+        Ok(bytes)
+    }
+
+    /// Encode as a frame signed by `sender`, whose HIT should be
+    /// `header.sender` ([`Message::verify`] rejects it otherwise)
+    pub fn encode(&self, sender: &Identity) -> Result<Vec<u8>> {
+        let mut frame = self.signed_bytes()?;
+        let signature = sender.sign(&frame);
+        frame.extend_from_slice(&signature);
+        Ok(frame)
+    }
+
+    /// Decode a frame produced by [`Message::encode`], without
+    /// checking the signature (see [`Message::verify`])
+    ///
+    /// Fails with `UnsupportedVersion` if the version byte is not
+    /// `PROTOCOL_VERSION`, with `UnknownMessageType` for an
+    /// unregistered type, and with `InvalidMessageFormat` if the frame
+    /// is truncated or its length field disagrees with the payload.
+    pub fn decode(frame: &[u8]) -> Result<Self> {
+        Ok(Self::split(frame)?.0)
+    }
+
+    /// Decode a frame and check its signature against `sender_key`,
+    /// which must be the key behind the header's sender HIT
+    ///
+    /// Fails with `SignatureVerificationFailed` if the HIT or the
+    /// signature doesn't match, besides the errors of
+    /// [`Message::decode`].
+    pub fn verify(frame: &[u8], sender_key: &PublicKey) -> Result<Self> {
+        let (message, signed, signature) = Self::split(frame)?;
+        if !message.header.sender.matches(sender_key) {
+            return Err(Error::SignatureVerificationFailed);
+        }
+        let signature: &[u8; SIGNATURE_SIZE] = signature.try_into().map_err(|_| Error::InvalidMessageFormat)?;
+        if !Identity::verify(sender_key, signed, signature) {
+            return Err(Error::SignatureVerificationFailed);
+        }
+        Ok(message)
+    }
+
+    /// Parse a frame into the message, the signed bytes and the
+    /// signature
+    fn split(frame: &[u8]) -> Result<(Self, &[u8], &[u8])> {
+        let (&version, _) = frame.split_first().ok_or(Error::InvalidMessageFormat)?;
+        if version != PROTOCOL_VERSION {
+            return Err(Error::UnsupportedVersion { got: version, supported: PROTOCOL_VERSION });
+        }
+        if frame.len() < HEADER_SIZE + SIGNATURE_SIZE {
+            return Err(Error::InvalidMessageFormat);
+        }
+
+        let msg_type = MessageType::try_from(frame[1])?;
+        let field = |offset: usize, len: usize| &frame[offset..offset + len];
+        let hit = |offset: usize| Hit::from_slice(field(offset, HIT_SIZE));
+        let u64_at = |offset: usize| u64::from_be_bytes(field(offset, 8).try_into().expect("8 bytes"));
+
+        let length = u32::from_be_bytes(field(PAYLOAD_LENGTH_OFFSET, 4).try_into().expect("4 bytes")) as usize;
+        if frame.len() - HEADER_SIZE - SIGNATURE_SIZE != length {
+            return Err(Error::InvalidMessageFormat);
+        }
+        let (signed, signature) = frame.split_at(HEADER_SIZE + length);
+
+        let header = MessageHeader {
+            msg_type,
+            flags: u16::from_be_bytes([frame[2], frame[3]]),
+            sender: hit(4)?,
+            recipient: hit(20)?,
+            message_id: u64_at(36),
+            timestamp_ms: u64_at(44),
+        };
+        let message = Self { header, payload: signed[HEADER_SIZE..].to_vec() };
+        Ok((message, signed, signature))
+    }
+}
+
+/// Inclusive range of protocol versions a peer can speak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionRange {
+    /// Oldest supported version
+    pub min: u8,
+    /// Newest supported version
+    pub max: u8,
+}
+
+impl VersionRange {
+    /// Range of versions; `min` must not exceed `max`
+    pub fn new(min: u8, max: u8) -> Result<Self> {
+        if min > max {
+            return Err(Error::InvalidMessageFormat);
+        }
+        Ok(Self { min, max })
+    }
+
+    /// Versions this implementation speaks
+    pub fn supported() -> Self {
+        Self { min: PROTOCOL_VERSION, max: PROTOCOL_VERSION }
+    }
+
+    /// Whether `version` lies in the range
+    pub fn contains(&self, version: u8) -> bool {
+        (self.min..=self.max).contains(&version)
+    }
+
+    /// Highest version both ranges share
+    ///
+    /// Fails with `UnsupportedVersion` carrying the peer's newest
+    /// version and ours if the ranges do not overlap.
+    pub fn negotiate(&self, peer: &VersionRange) -> Result<u8> {
+        let version = self.max.min(peer.max);
+        if version < self.min.max(peer.min) {
+            return Err(Error::UnsupportedVersion { got: peer.max, supported: self.max });
+        }
+        Ok(version)
+    }
+
+    /// Two-byte wire form: min, max
+    pub fn to_bytes(&self) -> [u8; 2] {
+        [self.min, self.max]
+    }
+
+    /// Parse the two-byte wire form
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes {
+            &[min, max] => Self::new(min, max),
+            _ => Err(Error::InvalidMessageFormat),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(msg_type: MessageType, sender: &Identity, payload: Vec<u8>) -> Message {
+        Message::new(
+            MessageHeader {
+                msg_type,
+                flags: flags::ACK_REQUESTED | flags::PRIORITY,
+                sender: sender.hit(),
+                recipient: Identity::from_seed(&[2u8; 32]).hit(),
+                message_id: 0x0102_0304_0506_0708,
+                timestamp_ms: 1_700_000_000_123,
+            },
+            payload,
+        )
+    }
+
+    #[test]
+    fn test_current_version_frame_round_trip() {
+        let sender = Identity::from_seed(&[1u8; 32]);
+        let message = message(MessageType::Ping, &sender, vec![1, 2, 3]);
+        let frame = message.encode(&sender).unwrap();
+
+        // Common header layout of spec/MESSAGES.md §1
+        assert_eq!(frame.len(), HEADER_SIZE + 3 + SIGNATURE_SIZE);
+        assert_eq!(frame[..4], [PROTOCOL_VERSION, 0x12, 0x30, 0x00]);
+        assert_eq!(frame[4..20], *sender.hit().as_bytes());
+        assert_eq!(frame[20..36], *message.header.recipient.as_bytes());
+        assert_eq!(frame[36..44], [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(frame[44..52], 1_700_000_000_123u64.to_be_bytes());
+        assert_eq!(frame[52..56], [0, 0, 0, 3]);
+        assert_eq!(frame[56..59], [1, 2, 3]);
+
+        assert_eq!(Message::decode(&frame).unwrap(), message);
+        assert_eq!(Message::verify(&frame, sender.public_key()).unwrap(), message);
+
+        assert!(matches!(Message::decode(&frame[..4]), Err(Error::InvalidMessageFormat)));
+        assert!(matches!(Message::decode(&frame[..frame.len() - 1]), Err(Error::InvalidMessageFormat)));
+    }
+
+    #[test]
+    fn test_frame_signature_checked() {
+        let sender = Identity::from_seed(&[1u8; 32]);
+        let frame = message(MessageType::Data, &sender, vec![0xAA; 8]).encode(&sender).unwrap();
+
+        let mut tampered = frame.clone();
+        tampered[HEADER_SIZE] ^= 1;
+        assert!(Message::decode(&tampered).is_ok());
+        assert!(matches!(
+            Message::verify(&tampered, sender.public_key()),
+            Err(Error::SignatureVerificationFailed)
+        ));
+
+        // Signed by someone other than the sender HIT names
+        let impostor = Identity::from_seed(&[3u8; 32]);
+        let forged = message(MessageType::Data, &sender, vec![0xAA; 8]).encode(&impostor).unwrap();
+        for key in [sender.public_key(), impostor.public_key()] {
+            assert!(matches!(Message::verify(&forged, key), Err(Error::SignatureVerificationFailed)));
+        }
+    }
+
+    #[test]
+    fn test_future_version_frame_rejected() {
+        let sender = Identity::from_seed(&[1u8; 32]);
+        let mut frame = message(MessageType::Data, &sender, vec![0xAA; 8]).encode(&sender).unwrap();
+        frame[0] = PROTOCOL_VERSION + 1;
+
+        match Message::decode(&frame) {
+            Err(Error::UnsupportedVersion { got, supported }) => {
+                assert_eq!(got, PROTOCOL_VERSION + 1);
+                assert_eq!(supported, PROTOCOL_VERSION);
+            }
+            other => panic!("expected UnsupportedVersion, got {other:?}"),
+        }
+
+        // Checked before the rest of the header, even a bare version byte
+        assert!(matches!(Message::decode(&frame[..1]), Err(Error::UnsupportedVersion { .. })));
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        let ours = VersionRange::new(1, 3).unwrap();
+        assert_eq!(ours.negotiate(&VersionRange::new(2, 5).unwrap()).unwrap(), 3);
+        assert_eq!(ours.negotiate(&VersionRange::new(1, 1).unwrap()).unwrap(), 1);
+        assert!(matches!(
+            ours.negotiate(&VersionRange::new(4, 6).unwrap()),
+            Err(Error::UnsupportedVersion { got: 6, supported: 3 })
+        ));
+
+        let supported = VersionRange::supported();
+        assert!(supported.contains(PROTOCOL_VERSION));
+        assert_eq!(VersionRange::from_bytes(&supported.to_bytes()).unwrap(), supported);
+        assert!(VersionRange::from_bytes(&[3, 1]).is_err());
+        assert!(VersionRange::from_bytes(&[1]).is_err());
+    }
+}
+
@@ -1,7 +1,9 @@
 //! Trajectory, breadcrumbs, and epochs
 //! See spec/TRAJECTORY.md for details
 
+use sha2::{Sha256, Digest};
 use crate::identity::PublicKey;
+use crate::merkle::{leaf_hash, merkle_root, MerkleAccumulator};
 
 /// Location breadcrumb
 pub struct Breadcrumb {
@@ -24,3 +26,52 @@ pub struct Epoch {
     pub signature: [u8; 64],
 }
 
+
+impl Breadcrumb {
+    /// Canonical hash of all breadcrumb fields, used as the Merkle leaf
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.owner.as_bytes());
+        hasher.update(self.index.to_be_bytes());
+        hasher.update(self.timestamp.to_be_bytes());
+        hasher.update(self.cell.to_be_bytes());
+        hasher.update(self.context);
+        hasher.update(self.previous);
+        hasher.update(self.signature);
+        hasher.finalize().into()
+    }
+
+    /// Merkle leaf hash of this breadcrumb
+    pub fn leaf(&self) -> [u8; 32] {
+        leaf_hash(&self.hash())
+    }
+}
+
+impl Epoch {
+    /// Recompute the Merkle root over all breadcrumbs
+    pub fn compute_merkle_root(&self) -> [u8; 32] {
+        let leaves: Vec<[u8; 32]> = self.breadcrumbs.iter().map(Breadcrumb::leaf).collect();
+        merkle_root(&leaves)
+    }
+
+    /// Accumulator seeded with this epoch's breadcrumbs, for appending
+    /// further breadcrumbs without rehashing the whole epoch
+    pub fn accumulator(&self) -> MerkleAccumulator {
+        let mut acc = MerkleAccumulator::new();
+        for b in &self.breadcrumbs {
+            acc.push(b.leaf());
+        }
+        acc
+    }
+
+    /// Append a breadcrumb and update `merkle_root` incrementally
+    ///
+    /// `acc` must cover exactly the epoch's current breadcrumbs.
+    pub fn push_breadcrumb(&mut self, acc: &mut MerkleAccumulator, breadcrumb: Breadcrumb) {
+        debug_assert_eq!(acc.len(), self.breadcrumbs.len() as u64);
+        acc.push(breadcrumb.leaf());
+        self.end_time = self.end_time.max(breadcrumb.timestamp);
+        self.breadcrumbs.push(breadcrumb);
+        self.merkle_root = acc.root();
+    }
+}
@@ -1,5 +1,18 @@
 //! Trust levels and verification
 //! See spec/TRUST.md for details
+//!
+//! A [`TrustProof`] travels in the handshake as a CBOR array of
+//! unsigned integers, `[tag]` or `[tag, value]`, with the tag taken
+//! from the proof types in spec/MESSAGES.md:
+//!
+//! ```text
+//! None        [0]
+//! Epoch       [1, epoch_count]
+//! Trajectory  [2, breadcrumb_count]
+//! Vouch       [3, voucher_trust]
+//! ```
+
+use crate::error::{Error, Result};
 
 /// Trust level (0-4)
 #[repr(u8)]
@@ -18,7 +31,25 @@ impl Default for TrustLevel {
     }
 }
 
+impl TryFrom<u8> for TrustLevel {
+    type Error = Error;
+
+    fn try_from(level: u8) -> Result<Self> {
+        Ok(match level {
+            0 => Self::Anonymous,
+            1 => Self::Verified,
+            2 => Self::Established,
+            3 => Self::Trusted,
+            4 => Self::Vouched,
+            other => return Err(Error::ProofVerificationFailed(
+                format!("trust level {other} out of range")
+            )),
+        })
+    }
+}
+
 /// Proof for trust verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrustProof {
     None,
     Epoch { epoch_count: u32 },
@@ -26,3 +57,184 @@ pub enum TrustProof {
     Vouch { voucher_trust: TrustLevel },
 }
 
+/// CBOR major type 0 (unsigned integer)
+const CBOR_UINT: u8 = 0x00;
+/// CBOR major type 4 (array)
+const CBOR_ARRAY: u8 = 0x80;
+
+impl TrustProof {
+    /// Wire tag of the variant (proof type in spec/MESSAGES.md)
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::None => 0x00,
+            Self::Epoch { .. } => 0x01,
+            Self::Trajectory { .. } => 0x02,
+            Self::Vouch { .. } => 0x03,
+        }
+    }
+
+    /// Encode as CBOR
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let value = match *self {
+            Self::None => None,
+            Self::Epoch { epoch_count } => Some(epoch_count),
+            Self::Trajectory { breadcrumb_count } => Some(breadcrumb_count),
+            Self::Vouch { voucher_trust } => Some(voucher_trust as u32),
+        };
+
+        let mut out = Vec::with_capacity(7);
+        out.push(CBOR_ARRAY | if value.is_some() { 2 } else { 1 });
+        write_uint(&mut out, self.tag() as u32);
+        if let Some(value) = value {
+            write_uint(&mut out, value);
+        }
+        out
+    }
+
+    /// Decode and validate a proof produced by [`TrustProof::to_cbor`]
+    ///
+    /// Fails with `InvalidMessageFormat` on malformed CBOR, and with
+    /// `ProofVerificationFailed` on a zero count or a voucher below
+    /// `Trusted` (spec/TRUST.md: vouchers must be level 3+).
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        let (&header, mut rest) = bytes.split_first().ok_or(Error::InvalidMessageFormat)?;
+        let items = match header {
+            h if h == CBOR_ARRAY | 1 => 1,
+            h if h == CBOR_ARRAY | 2 => 2,
+            _ => return Err(Error::InvalidMessageFormat),
+        };
+        let tag = read_uint(&mut rest)?;
+        let value = if items == 2 { Some(read_uint(&mut rest)?) } else { None };
+        if !rest.is_empty() {
+            return Err(Error::InvalidMessageFormat);
+        }
+
+        let proof = match (tag, value) {
+            (0x00, None) => Self::None,
+            (0x01, Some(epoch_count)) => Self::Epoch { epoch_count },
+            (0x02, Some(breadcrumb_count)) => Self::Trajectory { breadcrumb_count },
+            (0x03, Some(level)) => {
+                let level = u8::try_from(level).map_err(|_| Error::ProofVerificationFailed(
+                    format!("trust level {level} out of range")
+                ))?;
+                Self::Vouch { voucher_trust: TrustLevel::try_from(level)? }
+            }
+            _ => return Err(Error::InvalidMessageFormat),
+        };
+        proof.validate()?;
+        Ok(proof)
+    }
+
+    /// Check the proof's claims are well-formed
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            Self::Epoch { epoch_count: 0 } => Err(Error::ProofVerificationFailed(
+                "epoch proof with zero epochs".into()
+            )),
+            Self::Trajectory { breadcrumb_count: 0 } => Err(Error::ProofVerificationFailed(
+                "trajectory proof with zero breadcrumbs".into()
+            )),
+            Self::Vouch { voucher_trust } if voucher_trust < TrustLevel::Trusted => {
+                Err(Error::InsufficientTrust {
+                    required: TrustLevel::Trusted as u8,
+                    actual: voucher_trust as u8,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Shortest-form CBOR unsigned integer
+fn write_uint(out: &mut Vec<u8>, value: u32) {
+    match value {
+        0..=23 => out.push(CBOR_UINT | value as u8),
+        24..=0xFF => out.extend_from_slice(&[CBOR_UINT | 24, value as u8]),
+        0x100..=0xFFFF => {
+            out.push(CBOR_UINT | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        _ => {
+            out.push(CBOR_UINT | 26);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Read an unsigned integer of at most 32 bits, rejecting
+/// non-shortest encodings so each proof has one wire form
+fn read_uint(input: &mut &[u8]) -> Result<u32> {
+    let (&initial, rest) = input.split_first().ok_or(Error::InvalidMessageFormat)?;
+    let width = match initial {
+        0..=23 => 0,
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        _ => return Err(Error::InvalidMessageFormat),
+    };
+    if rest.len() < width {
+        return Err(Error::InvalidMessageFormat);
+    }
+    let value = match width {
+        0 => initial as u32,
+        _ => rest[..width].iter().fold(0u32, |acc, &b| (acc << 8) | b as u32),
+    };
+    let minimum = match width {
+        0 => 0,
+        1 => 24,
+        2 => 0x100,
+        _ => 0x1_0000,
+    };
+    if value < minimum {
+        return Err(Error::InvalidMessageFormat);
+    }
+    *input = &rest[width..];
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_proof_round_trip() {
+        let proofs = [
+            (TrustProof::None, vec![0x81, 0x00]),
+            (TrustProof::Epoch { epoch_count: 4 }, vec![0x82, 0x01, 0x04]),
+            (TrustProof::Trajectory { breadcrumb_count: 100_000 }, vec![0x82, 0x02, 0x1a, 0x00, 0x01, 0x86, 0xa0]),
+            (TrustProof::Vouch { voucher_trust: TrustLevel::Trusted }, vec![0x82, 0x03, 0x03]),
+        ];
+        for (proof, bytes) in proofs {
+            assert_eq!(proof.to_cbor(), bytes, "{proof:?}");
+            assert_eq!(TrustProof::from_cbor(&bytes).unwrap(), proof);
+        }
+
+        let epochs = TrustProof::Epoch { epoch_count: 300 };
+        assert_eq!(TrustProof::from_cbor(&epochs.to_cbor()).unwrap(), epochs);
+    }
+
+    #[test]
+    fn test_malformed_trust_proof_rejected() {
+        // Vouch with trust level 7
+        assert!(matches!(
+            TrustProof::from_cbor(&[0x82, 0x03, 0x07]),
+            Err(Error::ProofVerificationFailed(_))
+        ));
+        // Voucher below level 3
+        assert!(matches!(
+            TrustProof::from_cbor(&[0x82, 0x03, 0x01]),
+            Err(Error::InsufficientTrust { required: 3, actual: 1 })
+        ));
+        // Zero counts
+        assert!(TrustProof::from_cbor(&[0x82, 0x01, 0x00]).is_err());
+        assert!(TrustProof::from_cbor(&[0x82, 0x02, 0x00]).is_err());
+
+        // Structural errors: unknown tag, missing value, non-shortest
+        // integer, trailing bytes, truncation
+        for bytes in [&[0x82, 0x09, 0x01][..], &[0x81, 0x01], &[0x82, 0x01, 0x18, 0x05],
+                      &[0x81, 0x00, 0x00], &[0x82, 0x02, 0x19, 0x01], &[]] {
+            assert!(matches!(TrustProof::from_cbor(bytes), Err(Error::InvalidMessageFormat)), "{bytes:?}");
+        }
+    }
+}
+
@@ -0,0 +1,89 @@
+// trip-verifier/benches/displacements.rs
+//
+// Bulk displacement computation, with and without the "simd" feature,
+// and against parsing both cells of every pair (`h3_cell_distance_km`).
+// `compute_displacements` decodes each distinct cell once, which pays
+// off on chains revisiting a few places ("revisits": 61 cells) rather
+// than on ones never passing the same cell twice:
+//
+//   cargo bench --bench displacements
+//   cargo bench --bench displacements --features simd
+//
+// wide only uses the wider vector units the target enables; build with
+// RUSTFLAGS="-C target-cpu=native" to compare on the local machine.
+
+use chrono::{Duration, TimeZone, Utc};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use h3o::{LatLng, Resolution};
+use trip_verifier::breadcrumb::{
+    compute_displacements, h3_cell_distance_km, haversine_batch_km, Breadcrumb, MetaFlags,
+};
+
+const PAIRS: usize = 20_000;
+
+/// Deterministic points scattered around Rome.
+fn points(n: usize, offset: f64) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64 + offset;
+            (41.9 + 0.05 * (t * 0.37).sin(), 12.5 + 0.05 * (t * 0.23).cos())
+        })
+        .collect()
+}
+
+/// Breadcrumbs at `points`, ten minutes apart.
+fn chain(points: Vec<(f64, f64)>) -> Vec<Breadcrumb> {
+    let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    points.into_iter().enumerate()
+        .map(|(i, (lat, lon))| Breadcrumb {
+            index: i as u64,
+            identity_public_key: String::new(),
+            timestamp: start + Duration::minutes(10 * i as i64),
+            location_cell: LatLng::new(lat, lon).unwrap().to_cell(Resolution::Ten).to_string(),
+            location_resolution: 10,
+            context_digest: String::new(),
+            previous_hash: None,
+            meta_flags: MetaFlags {
+                battery: None,
+                sampling: "normal".to_string(),
+                state: "unknown".to_string(),
+                network: "unknown".to_string(),
+                accuracy: None,
+                manual: false,
+            },
+            signature: String::new(),
+            block_hash: String::new(),
+            beacon_digest: None,
+            skip_hash: None,
+        })
+        .collect()
+}
+
+fn bench_displacements(c: &mut Criterion) {
+    let (from, to) = (points(PAIRS, 0.0), points(PAIRS, 0.5));
+    c.bench_function("haversine_batch_km/20k", |b| {
+        b.iter(|| haversine_batch_km(black_box(&from), black_box(&to)))
+    });
+
+    let places = points(61, 0.0);
+    let chains = [
+        ("20k", chain(points(PAIRS, 0.0))),
+        ("20k-revisits", chain((0..PAIRS).map(|i| places[i * 7 % places.len()]).collect())),
+    ];
+    for (name, breadcrumbs) in &chains {
+        c.bench_function(&format!("compute_displacements/{name}"), |b| {
+            b.iter(|| compute_displacements(black_box(breadcrumbs)).unwrap())
+        });
+        // Uncached: both cells of every pair parsed again
+        c.bench_function(&format!("h3_cell_distance_km/{name}"), |b| {
+            b.iter(|| {
+                black_box(breadcrumbs).windows(2)
+                    .map(|pair| h3_cell_distance_km(&pair[0].location_cell, &pair[1].location_cell))
+                    .collect::<Vec<f64>>()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, bench_displacements);
+criterion_main!(benches);
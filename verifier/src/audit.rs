@@ -0,0 +1,215 @@
+// trip-verifier/src/audit.rs
+//
+// Verification Audit Log
+// =======================
+//
+// A tamper-evident record of every decision the Verifier makes.
+// Each entry is signed with the Verifier's key and hash-linked to
+// the previous one, mirroring the breadcrumb chain itself:
+//
+//   entry_hash = SHA-256(data_to_sign ":" signature)
+//   previous_hash of entry i = entry_hash of entry i − 1
+//
+// Rewriting any past entry breaks its signature or its hash, and
+// every link after it. Operators can later prove what they decided
+// and when. Identities are logged by HIT, not full public key.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::breadcrumb::TIMESTAMP_FORMAT;
+use crate::certificate::{PoHCertificate, decode_hex_field};
+use crate::compact::hit;
+use crate::criticality::CriticalityResult;
+use crate::error::{TripError, Result};
+
+/// One logged verification decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub index: u64,
+    pub identity_hit: String,              // hex, 32 chars
+    pub is_human: bool,
+    pub trust_score: f64,
+    pub timestamp: DateTime<Utc>,
+    pub certificate_fingerprint: Option<String>,
+    pub previous_hash: Option<String>,
+    pub signature: String,                 // Ed25519 hex, by the Verifier
+    pub entry_hash: String,
+}
+
+impl AuditEntry {
+    /// Canonical JSON covered by the signature.
+    pub fn data_to_sign(&self) -> String {
+        serde_json::json!({
+            "index": self.index,
+            "identity_hit": self.identity_hit,
+            "is_human": self.is_human,
+            "trust_score": self.trust_score,
+            "timestamp": self.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            "cert": self.certificate_fingerprint,
+            "prev_hash": self.previous_hash.as_deref().unwrap_or("genesis"),
+        })
+        .to_string()
+    }
+
+    fn compute_hash(&self) -> String {
+        let content = format!("{}:{}", self.data_to_sign(), self.signature);
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+}
+
+/// Append-only, hash-chained log of verification decisions.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a signed entry for one verification.
+    pub fn append(
+        &mut self,
+        verifier_signing_key: &SigningKey,
+        identity_key: &str,
+        result: &CriticalityResult,
+        certificate: Option<&PoHCertificate>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<&AuditEntry> {
+        let identity: [u8; 32] = decode_hex_field(identity_key, "identity_key")?;
+
+        let mut entry = AuditEntry {
+            index: self.entries.len() as u64,
+            identity_hit: hex::encode(hit(&identity)),
+            is_human: result.is_human,
+            trust_score: result.trust_score,
+            timestamp,
+            certificate_fingerprint: certificate.map(|c| c.fingerprint()).transpose()?,
+            previous_hash: self.entries.last().map(|e| e.entry_hash.clone()),
+            signature: String::new(),
+            entry_hash: String::new(),
+        };
+
+        let signature = verifier_signing_key.sign(entry.data_to_sign().as_bytes());
+        entry.signature = hex::encode(signature.to_bytes());
+        entry.entry_hash = entry.compute_hash();
+
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("just pushed"))
+    }
+
+    pub fn verify(&self, verifier_key: &VerifyingKey) -> Result<()> {
+        verify_audit_log(&self.entries, verifier_key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Check an audit log's indices, hash links, entry hashes and
+/// Verifier signatures. Fails at the first tampered entry.
+pub fn verify_audit_log(entries: &[AuditEntry], verifier_key: &VerifyingKey) -> Result<()> {
+    let mut previous: Option<&str> = None;
+
+    for (i, entry) in entries.iter().enumerate() {
+        if entry.index != i as u64 {
+            return Err(TripError::ChainIntegrity(
+                format!("Audit index gap: expected {}, got {}", i, entry.index)
+            ));
+        }
+
+        if entry.previous_hash.as_deref() != previous {
+            return Err(TripError::ChainIntegrity(
+                format!("Audit link broken at index {}", entry.index)
+            ));
+        }
+
+        if entry.compute_hash() != entry.entry_hash {
+            return Err(TripError::ChainIntegrity(
+                format!("Audit entry hash mismatch at index {}", entry.index)
+            ));
+        }
+
+        let invalid = || TripError::SignatureInvalid { index: entry.index };
+        let sig_bytes: [u8; 64] = decode_hex_field(&entry.signature, "signature")?;
+        verifier_key
+            .verify(entry.data_to_sign().as_bytes(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| invalid())?;
+
+        previous = Some(&entry.entry_hash);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::{BreadcrumbChain, tests::random_walk_breadcrumbs};
+    use crate::criticality::CriticalityEngine;
+
+    fn logged_decisions(n: usize) -> (AuditLog, SigningKey) {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(100, 4)).unwrap();
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        let cert = PoHCertificate::from_criticality_result(
+            &result,
+            chain.identity.clone(),
+            hex::encode(key.verifying_key().to_bytes()),
+            chain.breadcrumbs.last().unwrap().block_hash.clone(),
+            3600,
+        );
+
+        let mut log = AuditLog::new();
+        for i in 0..n {
+            let at = DateTime::from_timestamp(1_700_000_000 + i as i64 * 60, 0).unwrap();
+            let cert = (i % 2 == 0).then_some(&cert);
+            log.append(&key, &chain.identity, &result, cert, at).unwrap();
+        }
+        (log, key)
+    }
+
+    #[test]
+    fn test_audit_log_valid_chain() {
+        let (log, key) = logged_decisions(5);
+        assert_eq!(log.len(), 5);
+        assert!(log.entries[0].previous_hash.is_none());
+        assert_eq!(log.entries[3].previous_hash.as_ref(), Some(&log.entries[2].entry_hash));
+        assert_eq!(log.entries[0].identity_hit.len(), 32);
+        assert!(log.entries[0].certificate_fingerprint.is_some());
+        assert!(log.entries[1].certificate_fingerprint.is_none());
+        log.verify(&key.verifying_key()).unwrap();
+
+        let other = SigningKey::from_bytes(&[1u8; 32]);
+        assert!(matches!(
+            log.verify(&other.verifying_key()),
+            Err(TripError::SignatureInvalid { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_audit_log_mutation_detected() {
+        let (mut log, key) = logged_decisions(5);
+        log.entries[2].trust_score = 99.0;
+        assert!(matches!(
+            log.verify(&key.verifying_key()),
+            Err(TripError::ChainIntegrity(msg)) if msg.contains("index 2")
+        ));
+
+        // Re-hashing the edited entry still fails its signature
+        log.entries[2].entry_hash = log.entries[2].compute_hash();
+        log.entries[3].previous_hash = Some(log.entries[2].entry_hash.clone());
+        assert!(matches!(
+            log.verify(&key.verifying_key()),
+            Err(TripError::SignatureInvalid { index: 2 })
+        ));
+    }
+}
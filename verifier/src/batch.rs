@@ -0,0 +1,239 @@
+// trip-verifier/src/batch.rs
+//
+// Batch Statistics
+// ================
+//
+// An operator verifying thousands of chains a day watches the batch,
+// not the chain: how many came out human, where α is landing, whether
+// trust scores are drifting. `BatchSummary` folds `CriticalityResult`s
+// in one at a time and `finalize` turns them into the numbers a
+// dashboard or daily report shows:
+//
+// - outcome counts (Human / NotHuman / Inconclusive)
+// - mean chain length
+// - mean, quartiles and a fixed-bin histogram of α and of the trust score
+//
+// α is only counted for chains where the PSD was measured (not
+// stationary or constant-step chains, see `PsdClassification`), so
+// the α statistics can cover fewer results than the counts. Fixed
+// bins keep histograms from different days comparable.
+
+use serde::{Deserialize, Serialize};
+use crate::criticality::{CriticalityResult, Outcome};
+use crate::psd::PsdClassification;
+
+/// α histogram range: [0, 2) in 0.1 bins, covering white noise (0)
+/// through Brownian motion (2). Values outside land in the end bins.
+pub const ALPHA_HISTOGRAM: (f64, f64, usize) = (0.0, 2.0, 20);
+
+/// Trust score histogram range: [0, 100] in 10-point bins.
+pub const TRUST_HISTOGRAM: (f64, f64, usize) = (0.0, 100.0, 10);
+
+/// Running tally of a batch of results.
+#[derive(Debug, Clone, Default)]
+pub struct BatchSummary {
+    human: usize,
+    not_human: usize,
+    inconclusive: usize,
+    /// Measured α of each result
+    alphas: Vec<f64>,
+    trust_scores: Vec<f64>,
+    total_chain_length: usize,
+}
+
+/// Spread of one quantity across a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    /// None for an empty sample.
+    fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let at = |q: f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize];
+        Some(Self {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            min: sorted[0],
+            p25: at(0.25),
+            median: at(0.5),
+            p75: at(0.75),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// Counts over equal-width bins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    /// Lower edge of the first bin
+    pub start: f64,
+    /// Width of each bin
+    pub width: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Histogram of `values` over [start, end) in `bins` bins; values
+    /// outside the range are counted in the first or last bin.
+    fn from_values(values: &[f64], (start, end, bins): (f64, f64, usize)) -> Self {
+        let width = (end - start) / bins as f64;
+        let mut counts = vec![0; bins];
+        for &v in values {
+            let bin = ((v - start) / width).floor().clamp(0.0, (bins - 1) as f64) as usize;
+            counts[bin] += 1;
+        }
+        Self { start, width, counts }
+    }
+
+    /// Lower and upper edge of bin `i`.
+    pub fn bin_range(&self, i: usize) -> (f64, f64) {
+        let lower = self.start + i as f64 * self.width;
+        (lower, lower + self.width)
+    }
+}
+
+/// Finalized statistics of a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchStatistics {
+    /// Results accumulated
+    pub results: usize,
+    pub human: usize,
+    pub not_human: usize,
+    pub inconclusive: usize,
+    /// Mean number of breadcrumbs per chain; 0.0 for an empty batch
+    pub mean_chain_length: f64,
+    /// None for an empty batch
+    pub trust_score: Option<Distribution>,
+    /// None if no result had a measured α
+    pub alpha: Option<Distribution>,
+    /// Over `TRUST_HISTOGRAM`
+    pub trust_histogram: Histogram,
+    /// Over `ALPHA_HISTOGRAM`
+    pub alpha_histogram: Histogram,
+}
+
+impl BatchStatistics {
+    /// Share of results classified human [0, 1]
+    pub fn human_rate(&self) -> f64 {
+        if self.results == 0 {
+            return 0.0;
+        }
+        self.human as f64 / self.results as f64
+    }
+}
+
+impl BatchSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one result to the batch.
+    pub fn accumulate(&mut self, result: &CriticalityResult) {
+        match result.outcome() {
+            Outcome::Human => self.human += 1,
+            Outcome::NotHuman => self.not_human += 1,
+            Outcome::Inconclusive => self.inconclusive += 1,
+        }
+        if result.psd.classification != PsdClassification::NotMeasured {
+            self.alphas.push(result.psd.alpha);
+        }
+        self.trust_scores.push(result.trust_score);
+        self.total_chain_length += result.chain_length;
+    }
+
+    /// Results accumulated so far
+    pub fn len(&self) -> usize {
+        self.trust_scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trust_scores.is_empty()
+    }
+
+    /// Statistics of everything accumulated so far. The summary can
+    /// keep accumulating afterwards.
+    pub fn finalize(&self) -> BatchStatistics {
+        let mean_chain_length = match self.len() {
+            0 => 0.0,
+            n => self.total_chain_length as f64 / n as f64,
+        };
+        BatchStatistics {
+            results: self.len(),
+            human: self.human,
+            not_human: self.not_human,
+            inconclusive: self.inconclusive,
+            mean_chain_length,
+            trust_score: Distribution::from_values(&self.trust_scores),
+            alpha: Distribution::from_values(&self.alphas),
+            trust_histogram: Histogram::from_values(&self.trust_scores, TRUST_HISTOGRAM),
+            alpha_histogram: Histogram::from_values(&self.alphas, ALPHA_HISTOGRAM),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::BreadcrumbChain;
+    use crate::chain::tests::random_walk_breadcrumbs;
+    use crate::criticality::CriticalityEngine;
+    use crate::criticality::tests::ordered_displacements;
+
+    #[test]
+    fn test_batch_summary_accumulates_mixed_results() {
+        let engine = CriticalityEngine::with_defaults();
+        let mut results: Vec<CriticalityResult> = [(300, 5), (300, 7), (100, 3), (70, 9)]
+            .into_iter()
+            .map(|(n, seed)| {
+                let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(n, seed)).unwrap();
+                engine.evaluate(&chain).unwrap()
+            })
+            .collect();
+        // Pink-noise ordered Lévy steps on a jittered half-hour cadence
+        let displacements = ordered_displacements(400, 1, 0.25);
+        let intervals: Vec<f64> = (0..displacements.len())
+            .map(|i| 1800.0 + ((i * 37) % 120) as f64)
+            .collect();
+        results.push(engine.evaluate_displacements(&displacements, &intervals).unwrap());
+
+        let mut summary = BatchSummary::new();
+        assert_eq!(summary.finalize().alpha, None);
+        for result in &results {
+            summary.accumulate(result);
+        }
+        let stats = summary.finalize();
+
+        // Random walks aren't human and the two short ones are too
+        // short to call; the pink-noise mover is human
+        assert_eq!(results[4].outcome(), Outcome::Human);
+        assert_eq!(stats.results, 5);
+        assert_eq!((stats.human, stats.not_human, stats.inconclusive), (1, 2, 2));
+        assert_eq!(stats.human_rate(), 0.2);
+
+        let trust = stats.trust_score.as_ref().unwrap();
+        let mean_trust = results.iter().map(|r| r.trust_score).sum::<f64>() / 5.0;
+        assert!((trust.mean - mean_trust).abs() < 1e-9);
+        assert_eq!(trust.max, results.iter().map(|r| r.trust_score).fold(f64::MIN, f64::max));
+        // 400 displacements span 401 breadcrumbs
+        assert_eq!(stats.mean_chain_length, (300.0 + 300.0 + 100.0 + 70.0 + 401.0) / 5.0);
+
+        let alpha = stats.alpha.as_ref().unwrap();
+        assert_eq!(alpha.count, 5);
+        assert!(alpha.min <= alpha.median && alpha.median <= alpha.max);
+        assert_eq!(stats.alpha_histogram.counts.iter().sum::<usize>(), 5);
+        assert_eq!(stats.trust_histogram.counts.iter().sum::<usize>(), 5);
+        assert_eq!(stats.trust_histogram.bin_range(9), (90.0, 100.0));
+    }
+}
@@ -0,0 +1,166 @@
+// trip-verifier/src/beacon.rs
+//
+// Evidence Freshness via Beacons
+// ===============================
+//
+// Statistics alone can't tell a chain recorded last week from one
+// synthesized last year and replayed today. To bind a chain to real
+// time, recent breadcrumbs commit to a periodically published,
+// unpredictable beacon value (e.g. a public randomness beacon or a
+// server-signed timestamp) in their signed `beacon_digest` field.
+// A breadcrumb can't reference a beacon before it is published, so a
+// fresh beacon proves the breadcrumb was signed after that moment.
+//
+// The beacon source is pluggable: the verifier only needs to look up
+// when a given digest was published (BeaconSource).
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use crate::chain::BreadcrumbChain;
+use crate::error::{TripError, Result};
+
+/// Where beacon values come from.
+pub trait BeaconSource {
+    /// Publication time of `digest`, or None if it was never published.
+    fn published_at(&self, digest: &str) -> Option<DateTime<Utc>>;
+}
+
+/// In-memory beacon history, e.g. mirrored from a public beacon.
+#[derive(Debug, Clone, Default)]
+pub struct BeaconLog {
+    published: HashMap<String, DateTime<Utc>>,
+}
+
+impl BeaconLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn publish(&mut self, digest: impl Into<String>, at: DateTime<Utc>) {
+        self.published.insert(digest.into(), at);
+    }
+}
+
+impl BeaconSource for BeaconLog {
+    fn published_at(&self, digest: &str) -> Option<DateTime<Utc>> {
+        self.published.get(digest).copied()
+    }
+}
+
+/// Freshness requirements for beacon references.
+#[derive(Debug, Clone)]
+pub struct BeaconPolicy {
+    /// How many of the newest breadcrumbs must reference a beacon
+    pub recent_breadcrumbs: usize,
+    /// Maximum age of a referenced beacon, both when the breadcrumb
+    /// was signed and (for the newest one) at verification time
+    pub window: Duration,
+}
+
+impl Default for BeaconPolicy {
+    fn default() -> Self {
+        Self {
+            recent_breadcrumbs: 3,
+            window: Duration::hours(24),
+        }
+    }
+}
+
+/// Check that the newest breadcrumbs reference known, recent beacons.
+///
+/// Each of the last `recent_breadcrumbs` breadcrumbs must carry a
+/// `beacon_digest` known to `source`, published no later than the
+/// breadcrumb and no more than `window` before it. The newest beacon
+/// must also be within `window` of `now`, so a chain can't be
+/// pre-computed and submitted long after the fact.
+pub fn verify_beacon_freshness(
+    chain: &BreadcrumbChain,
+    source: &dyn BeaconSource,
+    policy: &BeaconPolicy,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let recent = chain.breadcrumbs.len().saturating_sub(policy.recent_breadcrumbs);
+
+    for b in &chain.breadcrumbs[recent..] {
+        let digest = b.beacon_digest.as_deref().ok_or_else(|| TripError::ChainIntegrity(
+            format!("Missing beacon reference at index {}", b.index)
+        ))?;
+        let published = source.published_at(digest).ok_or_else(|| TripError::ChainIntegrity(
+            format!("Unknown beacon at index {}", b.index)
+        ))?;
+
+        if published > b.timestamp {
+            return Err(TripError::ChainIntegrity(
+                format!("Beacon at index {} published after the breadcrumb", b.index)
+            ));
+        }
+        if b.timestamp - published > policy.window {
+            return Err(TripError::ChainIntegrity(
+                format!("Stale beacon at index {}", b.index)
+            ));
+        }
+    }
+
+    let newest = chain.breadcrumbs.last()
+        .and_then(|b| b.beacon_digest.as_deref())
+        .and_then(|digest| source.published_at(digest));
+    match newest {
+        Some(published) if now - published <= policy.window => Ok(()),
+        Some(_) => Err(TripError::ChainIntegrity(
+            "Newest beacon is older than the freshness window".to_string()
+        )),
+        // Only reachable with recent_breadcrumbs == 0
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::tests::random_walk_breadcrumbs;
+
+    fn beacon_chain(with_beacons: bool) -> (BreadcrumbChain, BeaconLog) {
+        let mut breadcrumbs = random_walk_breadcrumbs(20, 2);
+        let mut beacons = BeaconLog::new();
+
+        if with_beacons {
+            for (i, b) in breadcrumbs.iter_mut().enumerate().skip(15) {
+                let digest = format!("{:064x}", 0xbeac0 + i);
+                beacons.publish(digest.clone(), b.timestamp - Duration::minutes(5));
+                b.beacon_digest = Some(digest);
+            }
+        }
+
+        (BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap(), beacons)
+    }
+
+    #[test]
+    fn test_fresh_beacons_accepted() {
+        let (chain, beacons) = beacon_chain(true);
+        let now = chain.breadcrumbs.last().unwrap().timestamp + Duration::hours(1);
+        verify_beacon_freshness(&chain, &beacons, &BeaconPolicy::default(), now).unwrap();
+
+        // Same chain submitted a week later
+        let late = now + Duration::days(7);
+        assert!(verify_beacon_freshness(&chain, &beacons, &BeaconPolicy::default(), late).is_err());
+    }
+
+    #[test]
+    fn test_missing_beacons_flagged() {
+        let (chain, beacons) = beacon_chain(false);
+        let now = chain.breadcrumbs.last().unwrap().timestamp;
+        match verify_beacon_freshness(&chain, &beacons, &BeaconPolicy::default(), now) {
+            Err(TripError::ChainIntegrity(msg)) => assert!(msg.contains("Missing beacon"), "{msg}"),
+            other => panic!("expected missing beacon error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_beacon_is_signed() {
+        let (chain, _) = beacon_chain(true);
+        let b = chain.breadcrumbs.last().unwrap();
+        let mut stripped = b.clone();
+        stripped.beacon_digest = None;
+        assert_ne!(b.data_to_sign(), stripped.data_to_sign());
+    }
+}
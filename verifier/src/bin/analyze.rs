@@ -1,34 +1,50 @@
 use std::env;
 use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use trip_verifier::breadcrumb::Breadcrumb;
+use trip_verifier::breadcrumb::{Breadcrumb, read_breadcrumbs};
 use trip_verifier::chain::BreadcrumbChain;
 use trip_verifier::criticality::CriticalityEngine;
 use trip_verifier::certificate::PoHCertificate;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let verbose = args.contains(&"--verbose".to_string());
+    let profile_path = args.iter()
+        .position(|a| a == "--profile")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+    let levy_debug_path = args.iter()
+        .position(|a| a == "--levy-debug")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
     let file_path = args.iter()
-        .filter(|a| !a.starts_with('-') && *a != &args[0])
-        .next();
+        .skip(1)
+        .find(|a| {
+            !a.starts_with('-')
+                && Some(*a) != profile_path.as_ref()
+                && Some(*a) != levy_debug_path.as_ref()
+        });
 
     let file_path = match file_path {
         Some(p) => p.clone(),
         None => {
-            eprintln!("Usage: analyze [--verbose] <chain_export.json>");
+            eprintln!(
+                "Usage: analyze [--profile <profile.json>] [--levy-debug <cdf.csv>] <chain_export.json|.ndjson>"
+            );
             process::exit(1);
         }
     };
 
     println!("Loading chain from: {}", file_path);
-    let json_str = match fs::read_to_string(&file_path) {
-        Ok(s) => s,
+    let file = match fs::File::open(&file_path) {
+        Ok(f) => f,
         Err(e) => { eprintln!("Error reading file: {e}"); process::exit(1); }
     };
 
-    let breadcrumbs: Vec<Breadcrumb> = match serde_json::from_str(&json_str) {
+    // JSON array or NDJSON, read as it streams in
+    let breadcrumbs: Vec<Breadcrumb> = match read_breadcrumbs(BufReader::new(file)) {
         Ok(b) => b,
         Err(e) => { eprintln!("Error parsing JSON: {e}"); process::exit(1); }
     };
@@ -49,6 +65,10 @@ fn main() {
     println!("  Breadcrumbs:  {}", chain.len());
     println!("  Unique cells: {}", chain.unique_cells());
     println!("  Duration:     {:.1} hours", chain.duration_seconds() / 3600.0);
+    let dwell = chain.dwell_report();
+    println!("  Dwell ratio:  {:.2} ({} of {} long intervals stationary){}",
+        dwell.dwell_ratio(), dwell.stationary, dwell.long_intervals,
+        if dwell.is_low_liveness() { "  LOW LIVENESS" } else { "" });
     println!("  Chain hash:   {}...", &chain.head_hash()[..16.min(chain.head_hash().len())]);
 
     let displacements = chain.displacement_series();
@@ -72,7 +92,10 @@ fn main() {
     }
 
     println!("\n=== Criticality Engine ===");
-    let engine = CriticalityEngine::with_defaults();
+    let mut engine = CriticalityEngine::with_defaults();
+    if profile_path.is_some() {
+        engine = engine.with_profile_export();
+    }
 
     match engine.evaluate(&chain) {
         Ok(result) => {
@@ -86,6 +109,7 @@ fn main() {
             println!("\n  --- Levy Flight ---");
             println!("  beta  = {:.4}  ({})", result.levy.beta, result.levy.classification.label());
             println!("  kappa = {:.2} km", result.levy.kappa_km);
+            println!("  r_g   = {:.2} km (radius of gyration)", result.radius_of_gyration_km);
             println!("  KS    = {:.4}", result.levy.ks_statistic);
             println!("  Human [0.80, 1.20] -> {}",
                 if result.levy.classification.is_human() { "PASS" } else { "FAIL" });
@@ -93,28 +117,84 @@ fn main() {
             println!("\n  --- Hamiltonian ---");
             println!("  Mean energy:  {:.4}", result.hamiltonian.mean_energy);
             println!("  Max energy:   {:.4}", result.hamiltonian.max_energy);
+            println!("  Variance:     {:.2e}{}", result.hamiltonian.energy_variance,
+                if result.verdict.over_smooth { "  (over-smooth)" } else { "" });
             println!("  Green:{} Yellow:{} Orange:{} Red:{}",
                 result.hamiltonian.alert_count.green,
                 result.hamiltonian.alert_count.yellow,
                 result.hamiltonian.alert_count.orange,
                 result.hamiltonian.alert_count.red);
 
+            println!("\n  --- Replay ---");
+            println!("  Periodicity:  {:.4} (lag {})",
+                result.replay.replay_periodicity, result.replay.best_lag);
+            match result.diurnal_strength {
+                Some(strength) => println!("  Diurnal:      {:.4}{}", strength,
+                    if result.verdict.diurnal_pass { "" } else { "  (no day/night cycle)" }),
+                None => println!("  Diurnal:      n/a (chain too short)"),
+            }
+            if let Some(persistence) = result.directional_persistence {
+                println!("  Heading:      {:.4}{}", persistence,
+                    if result.verdict.directional_pass { "" } else { "  (never turns back)" });
+            }
+            match &result.waiting_times {
+                Some(fit) => println!("  Waiting:      gamma = {:.3} ({}, LR {:+.2})",
+                    fit.exponent, fit.classification.label(), fit.likelihood_ratio),
+                None => println!("  Waiting:      n/a (too few moves)"),
+            }
+            if let Some(path) = &result.path_psd {
+                println!("  Path PSD:     alpha = {:.3} (excess {:+.2}){}", path.psd.alpha, path.alpha_excess,
+                    if path.suspicious { "  (path retraces itself)" } else { "" });
+            }
+
             println!("\n  === VERDICT ===");
             println!("  Trust Score:  {:.1} / 100", result.trust_score);
             println!("  Confidence:   {:.1}%", result.confidence * 100.0);
-            println!("  Result:       {}", if result.is_human { "HUMAN" } else { "NOT VERIFIED" });
+            if result.verdict.stationary {
+                println!("  Note:         stationary chain; mobility not measured, trust capped");
+            }
+            if let Some(resolution) = result.verdict.recommended_resolution {
+                println!("  Warning:      H3 resolution too coarse for this movement; use {resolution}");
+            }
+            println!("  Result:       {}", result.outcome().label());
             println!("\n  {}", result.verdict.summary);
 
             // Save certificate
-            let cert = PoHCertificate::from_criticality_result(
+            let mut cert = PoHCertificate::from_criticality_result(
                 &result, chain.identity.clone(),
-                "0".repeat(64), chain.unique_cells(),
+                "0".repeat(64),
                 chain.head_hash().to_string(), 3600,
             );
+            if let Ok(digest) = engine.digest() {
+                cert = cert.with_config_digest(digest);
+            }
             if let Ok(json) = cert.to_json() {
-                let cert_path = file_path.replace(".json", "_poh.json");
-                let _ = fs::write(&cert_path, &json);
-                println!("\n  Certificate: {cert_path}");
+                let cert_path = certificate_path(Path::new(&file_path));
+                match fs::write(&cert_path, &json) {
+                    Ok(()) => println!("\n  Certificate: {}", cert_path.display()),
+                    Err(e) => eprintln!("Error writing certificate: {e}"),
+                }
+            }
+
+            if let Some(path) = &levy_debug_path {
+                let mut csv = String::from("displacement_km,empirical_cdf,fitted_cdf\n");
+                for (x, empirical, fitted) in result.levy.cdf_points(200) {
+                    csv.push_str(&format!("{x},{empirical},{fitted}\n"));
+                }
+                match fs::write(path, csv) {
+                    Ok(()) => println!("  Lévy CDFs:   {path}"),
+                    Err(e) => eprintln!("Error writing Lévy CDFs: {e}"),
+                }
+            }
+
+            if let (Some(path), Some(profile)) = (&profile_path, &result.profile) {
+                match serde_json::to_string_pretty(profile) {
+                    Ok(json) => match fs::write(path, json) {
+                        Ok(()) => println!("  Profile:     {path}"),
+                        Err(e) => eprintln!("Error writing profile: {e}"),
+                    },
+                    Err(e) => eprintln!("Error encoding profile: {e}"),
+                }
             }
         }
         Err(e) => {
@@ -124,3 +204,10 @@ fn main() {
         }
     }
 }
+
+/// `<stem>_poh.json` next to the export, whatever its extension: never
+/// the export itself.
+fn certificate_path(export: &Path) -> PathBuf {
+    let stem = export.file_stem().unwrap_or_default().to_string_lossy();
+    export.with_file_name(format!("{stem}_poh.json"))
+}
@@ -2,9 +2,40 @@
 //
 // Breadcrumb: the atomic unit of TRIP Evidence.
 // Matches the JSON structure produced by the Flutter BreadcrumbBlock.
+//
+// Incoming timestamps (see `parse_timestamp`) must be RFC 3339 with an
+// explicit offset; any offset is accepted and normalized to UTC, which
+// is the form that is signed, hashed and compared. A leap second
+// (23:59:60) is rejected: Unix time has no slot for it, and read as
+// the following second it could tie with or precede the next
+// breadcrumb and trip the monotonicity check. Timestamps without an
+// offset are ambiguous and rejected too.
+//
+// Some Attesters write the timestamp as an integer Unix time instead,
+// in seconds or milliseconds (see `timestamp_from_unix`). It is read
+// into the same UTC instant; signatures and hashes still cover the
+// RFC 3339 form, and breadcrumbs are always written back out in it.
+//
+// Exports come as one JSON array or as NDJSON (one breadcrumb object
+// per line, for large exports written and read as a stream).
+// `read_breadcrumbs` tells them apart by the first non-whitespace
+// character and reads either from any reader without holding the whole
+// file as one string (`parse_breadcrumbs` does the same for a string
+// already in memory, `read_breadcrumbs_ndjson` for known NDJSON).
 
-use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::io::BufRead;
+use chrono::{DateTime, Timelike, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::certificate::decode_hex_field;
+use crate::error::{TripError, Result, ensure_finite};
+
+/// Timestamp format in signed and hashed data: RFC 3339 UTC with
+/// millisecond precision. Timestamps are compared at this precision,
+/// since digits beyond it aren't covered by the signature.
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
 
 /// A single breadcrumb — signed attestation of spatiotemporal presence.
 /// This is what arrives from the Attester (mobile device).
@@ -12,6 +43,7 @@ use serde::{Deserialize, Serialize};
 pub struct Breadcrumb {
     pub index: u64,
     pub identity_public_key: String,
+    #[serde(deserialize_with = "deserialize_timestamp")]
     pub timestamp: DateTime<Utc>,
     pub location_cell: String,       // H3 hex string
     pub location_resolution: u8,     // H3 resolution (typically 10)
@@ -20,6 +52,13 @@ pub struct Breadcrumb {
     pub meta_flags: MetaFlags,
     pub signature: String,           // Ed25519 hex signature
     pub block_hash: String,          // SHA-256 of block content + signature
+    /// Beacon value this breadcrumb commits to (see `beacon`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon_digest: Option<String>,
+    /// Block hash of the last exported breadcrumb when the ones in
+    /// between were lost (see `GapTolerance`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,15 +80,189 @@ pub struct MetaFlags {
 fn default_sampling() -> String { "normal".to_string() }
 fn default_unknown() -> String { "unknown".to_string() }
 
+/// Parse an incoming breadcrumb timestamp and normalize it to UTC
+/// (see the policy at the top of this file).
+pub fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
+    let invalid = |why: &str| TripError::InvalidBreadcrumb(format!("timestamp {s:?}: {why}"));
+    let parsed = DateTime::parse_from_rfc3339(s)
+        .map_err(|e| invalid(&format!("not RFC 3339 with an offset ({e})")))?;
+    if parsed.nanosecond() >= 1_000_000_000 {
+        return Err(invalid("leap second"));
+    }
+    Ok(parsed.with_timezone(&Utc))
+}
+
+/// Integer timestamps at or above this magnitude are milliseconds,
+/// below it seconds: 1e11 seconds is the year 5138, 1e11 milliseconds
+/// is March 1973.
+pub const UNIX_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Read an integer Unix timestamp, in seconds or milliseconds by
+/// magnitude (see `UNIX_MILLIS_THRESHOLD`).
+pub fn timestamp_from_unix(value: i64) -> Result<DateTime<Utc>> {
+    let parsed = match value.unsigned_abs() >= UNIX_MILLIS_THRESHOLD as u64 {
+        true => DateTime::from_timestamp_millis(value),
+        false => DateTime::from_timestamp(value, 0),
+    };
+    parsed.ok_or_else(|| TripError::InvalidBreadcrumb(format!("timestamp {value}: out of range")))
+}
+
+fn deserialize_timestamp<'de, D>(deserializer: D) -> std::result::Result<DateTime<Utc>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct TimestampVisitor;
+
+    impl serde::de::Visitor<'_> for TimestampVisitor {
+        type Value = DateTime<Utc>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("an RFC 3339 timestamp or an integer Unix time")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, s: &str) -> std::result::Result<Self::Value, E> {
+            parse_timestamp(s).map_err(E::custom)
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> std::result::Result<Self::Value, E> {
+            timestamp_from_unix(value).map_err(E::custom)
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> std::result::Result<Self::Value, E> {
+            let value = i64::try_from(value)
+                .map_err(|_| E::custom(format!("timestamp {value}: out of range")))?;
+            self.visit_i64(value)
+        }
+    }
+
+    deserializer.deserialize_any(TimestampVisitor)
+}
+
+/// Parse breadcrumbs as exported by an Attester: a JSON array, or
+/// NDJSON when the first non-whitespace character isn't `[`.
+pub fn parse_breadcrumbs(json: &str) -> Result<Vec<Breadcrumb>> {
+    read_breadcrumbs(json.as_bytes())
+}
+
+/// Like `parse_breadcrumbs`, reading the export from `reader` (a file,
+/// say) as it goes.
+pub fn read_breadcrumbs(mut reader: impl BufRead) -> Result<Vec<Breadcrumb>> {
+    let io_error = |e: std::io::Error| TripError::InvalidBreadcrumb(e.to_string());
+
+    // Skip a byte order mark and leading whitespace, counting lines so
+    // NDJSON errors still name the right one
+    let mut skipped_lines = 0;
+    let mut at_start = true;
+    let is_array = loop {
+        let buffer = reader.fill_buf().map_err(io_error)?;
+        if at_start && buffer.starts_with(b"\xef\xbb\xbf") {
+            reader.consume(3);
+            at_start = false;
+            continue;
+        }
+        at_start = false;
+        let Some(&byte) = buffer.first() else { break false };
+        if !byte.is_ascii_whitespace() {
+            break byte == b'[';
+        }
+        skipped_lines += usize::from(byte == b'\n');
+        reader.consume(1);
+    };
+
+    if is_array {
+        serde_json::from_reader(reader).map_err(|e| TripError::InvalidBreadcrumb(e.to_string()))
+    } else {
+        ndjson_from_line(reader, skipped_lines + 1)
+    }
+}
+
+/// Read NDJSON breadcrumbs, one object per line; blank lines are
+/// skipped. Errors name the 1-based line.
+pub fn read_breadcrumbs_ndjson(reader: impl BufRead) -> Result<Vec<Breadcrumb>> {
+    ndjson_from_line(reader, 1)
+}
+
+/// NDJSON breadcrumbs from `reader`, whose first line is line `first`.
+fn ndjson_from_line(reader: impl BufRead, first: usize) -> Result<Vec<Breadcrumb>> {
+    let mut breadcrumbs = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let i = i + first - 1;
+        let line = line.map_err(|e| TripError::InvalidBreadcrumb(format!("line {}: {e}", i + 1)))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let breadcrumb = serde_json::from_str(line)
+            .map_err(|e| TripError::InvalidBreadcrumb(format!("line {}: {e}", i + 1)))?;
+        breadcrumbs.push(breadcrumb);
+    }
+    Ok(breadcrumbs)
+}
+
 impl Breadcrumb {
-    /// Extract the H3 cell index as u64 for geospatial computations
-    pub fn h3_cell(&self) -> Option<u64> {
-        u64::from_str_radix(&self.location_cell, 16).ok()
+    /// Extract the H3 cell index as u64 for geospatial computations.
+    /// Errors with `InvalidH3Cell` unless `location_cell` is a valid
+    /// cell index (see `parse_cell`).
+    pub fn h3_cell(&self) -> Result<u64> {
+        parse_cell(&self.location_cell).map(u64::from)
     }
 
-    /// Unix timestamp in seconds (for time series)
+    /// Unix timestamp in milliseconds, the precision that is signed.
+    pub fn timestamp_millis(&self) -> i64 {
+        self.timestamp.timestamp_millis()
+    }
+
+    /// Unix timestamp in seconds, to the millisecond (for time series).
+    /// Prefer `interval_seconds` for intervals; it checks the order.
     pub fn unix_seconds(&self) -> f64 {
-        self.timestamp.timestamp() as f64
+        self.timestamp_millis() as f64 / 1e3
+    }
+
+    /// Canonical bytes signed by the Attester.
+    /// Matches the Flutter BreadcrumbBlock.dataToSign JSON. The
+    /// "beacon" and "skip" keys are only present when set, so
+    /// breadcrumbs without them sign exactly as before.
+    pub fn data_to_sign(&self) -> String {
+        let mut data = serde_json::json!({
+            "index": self.index,
+            "identity": self.identity_public_key,
+            "timestamp": self.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            "loc_cell": self.location_cell,
+            "loc_res": self.location_resolution,
+            "context": self.context_digest,
+            "prev_hash": self.previous_hash.as_deref().unwrap_or("genesis"),
+            "meta": self.meta_flags,
+        });
+        if let Some(beacon) = &self.beacon_digest {
+            data["beacon"] = serde_json::Value::String(beacon.clone());
+        }
+        if let Some(skip) = &self.skip_hash {
+            data["skip"] = serde_json::Value::String(skip.clone());
+        }
+        data.to_string()
+    }
+
+    /// Expected `block_hash`: SHA-256 of the canonical signed bytes
+    /// and the signature, `data_to_sign() + ":" + signature`.
+    /// Set it after signing; `verify_block_hashes` recomputes it.
+    pub fn compute_block_hash(&self) -> String {
+        let content = format!("{}:{}", self.data_to_sign(), self.signature);
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+
+    /// Verify this breadcrumb's Ed25519 signature against its own
+    /// `identity_public_key`, without needing the rest of the chain.
+    pub fn verify_signature(&self) -> Result<()> {
+        let invalid = || TripError::SignatureInvalid { index: self.index };
+
+        let key_bytes: [u8; 32] = decode_hex_field(&self.identity_public_key, "identity_public_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(&self.signature, "signature")?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| invalid())?;
+        let signature = Signature::from_bytes(&sig_bytes);
+
+        key.verify(self.data_to_sign().as_bytes(), &signature)
+            .map_err(|_| invalid())
     }
 }
 
@@ -59,67 +272,234 @@ impl Breadcrumb {
 pub struct Displacement {
     pub dt_seconds: f64,        // time interval
     pub distance_km: f64,       // great-circle distance
+    pub uncertainty_km: f64,    // quantization band (see `DistanceMode`)
     pub from_cell: String,
     pub to_cell: String,
     pub timestamp: DateTime<Utc>,
 }
 
+/// How a displacement's length is derived from its two H3 cells.
+///
+/// Cells hide where inside them the user actually was. Center-to-center
+/// distance reports every move within one cell as 0 and every move into
+/// a neighbour as exactly one cell pitch, however short it was. At
+/// resolution 7 (~5.2 km² cells, ~1.2 km edge) that means moves under
+/// ~1 km read as 0, while a 100 m hop across a boundary reads as
+/// ~2.2 km. At the usual resolution 10 the same effect is ~60 m.
+///
+/// Each displacement carries `uncertainty_km`, the RMS error this
+/// quantization adds: with the true positions uniform over each hexagon,
+/// a point sits √(5A/(18√3)) from its cell center on average (RMS, A the
+/// cell area), and the two endpoints' errors add in quadrature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DistanceMode {
+    /// Great-circle distance between cell centers.
+    #[default]
+    CellCenter,
+    /// Expected RMS distance between uniform positions in the two
+    /// cells: √(d_center² + uncertainty²). Never 0, so same-cell moves
+    /// register, and neighbour moves are widened by the band.
+    Quantized,
+}
+
+impl DistanceMode {
+    /// Displacement length under this mode.
+    pub fn distance_km(&self, d: &Displacement) -> f64 {
+        match self {
+            Self::CellCenter => d.distance_km,
+            Self::Quantized => d.distance_km.hypot(d.uncertainty_km),
+        }
+    }
+}
+
+/// RMS distance (km) of a uniform point in the cell from its center.
+/// Returns 0.0 if the cell can't be parsed.
+pub fn cell_quantization_km(hex_str: &str) -> f64 {
+    parse_cell(hex_str).map(quantization_km).unwrap_or(0.0)
+}
+
+fn quantization_km(cell: h3o::CellIndex) -> f64 {
+    (5.0 * cell.area_km2() / (18.0 * 3f64.sqrt())).sqrt()
+}
+
+/// Interval between two breadcrumbs in seconds.
+///
+/// Computed in integer milliseconds, the precision timestamps are
+/// signed at (see `TIMESTAMP_FORMAT`), and only converted to f64 at
+/// the end, so sub-second spacing survives and large timestamps don't
+/// lose precision. Errors if the interval is zero, negative, or
+/// overflows.
+pub fn interval_seconds(from: &Breadcrumb, to: &Breadcrumb) -> Result<f64> {
+    let millis = to.timestamp_millis()
+        .checked_sub(from.timestamp_millis())
+        .ok_or_else(|| TripError::ChainIntegrity(
+            format!("Interval overflow at index {}", to.index)
+        ))?;
+
+    if millis <= 0 {
+        return Err(TripError::ChainIntegrity(
+            format!("Non-positive interval at index {}: {}ms", to.index, millis)
+        ));
+    }
+
+    Ok(millis as f64 / 1e3)
+}
+
 /// Compute displacements from an ordered breadcrumb chain.
 /// Uses H3 cell centers for distance calculation (privacy-preserving:
 /// we never need raw GPS, only the quantized cells).
-pub fn compute_displacements(breadcrumbs: &[Breadcrumb]) -> Vec<Displacement> {
+pub fn compute_displacements(breadcrumbs: &[Breadcrumb]) -> Result<Vec<Displacement>> {
     if breadcrumbs.len() < 2 {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
+    // Chains revisit the same few cells, so decode each one once. A
+    // cell that doesn't decode fails the chain rather than reading as
+    // a 0 km move.
+    let mut cells: HashMap<&str, CellGeometry> = HashMap::new();
+    for b in breadcrumbs {
+        if !cells.contains_key(b.location_cell.as_str()) {
+            cells.insert(b.location_cell.as_str(), CellGeometry::decode(&b.location_cell)?);
+        }
+    }
+
+    // Distances in one batch, so the "simd" feature can vectorize them
+    let geometry = |b: &Breadcrumb| cells[b.location_cell.as_str()];
+    let (from, to): (Vec<_>, Vec<_>) = breadcrumbs.windows(2)
+        .map(|pair| {
+            let (a, b) = (geometry(&pair[0]), geometry(&pair[1]));
+            ((a.lat, a.lon), (b.lat, b.lon))
+        })
+        .unzip();
+    let distances = haversine_batch_km(&from, &to);
+
     let mut displacements = Vec::with_capacity(breadcrumbs.len() - 1);
 
-    for pair in breadcrumbs.windows(2) {
+    for (pair, &dist) in breadcrumbs.windows(2).zip(&distances) {
         let b0 = &pair[0];
         let b1 = &pair[1];
 
-        let dt = (b1.unix_seconds() - b0.unix_seconds()).max(0.001);
-
-        // Convert H3 cells to lat/lon centers for distance
-        let dist = h3_cell_distance_km(&b0.location_cell, &b1.location_cell);
+        let dt = interval_seconds(b0, b1)?;
+        let uncertainty = geometry(b0).quantization_km.hypot(geometry(b1).quantization_km);
 
         displacements.push(Displacement {
             dt_seconds: dt,
             distance_km: dist,
+            uncertainty_km: uncertainty,
             from_cell: b0.location_cell.clone(),
             to_cell: b1.location_cell.clone(),
             timestamp: b1.timestamp,
         });
     }
 
-    displacements
+    let distances: Vec<f64> = displacements.iter().map(|d| d.distance_km).collect();
+    ensure_finite("displacement_km", &distances)?;
+
+    Ok(displacements)
 }
 
 /// Haversine distance between two H3 cell centers, in km.
-/// Falls back to 0.0 if cells can't be parsed.
+/// Falls back to 0.0 if cells can't be parsed; chains never get here
+/// with such a cell, as `compute_displacements` rejects it.
 pub fn h3_cell_distance_km(cell_a: &str, cell_b: &str) -> f64 {
-    let (lat_a, lon_a) = match h3_cell_to_latlon(cell_a) {
-        Some(c) => c,
-        None => return 0.0,
-    };
-    let (lat_b, lon_b) = match h3_cell_to_latlon(cell_b) {
-        Some(c) => c,
-        None => return 0.0,
-    };
-    haversine_km(lat_a, lon_a, lat_b, lon_b)
+    match (h3_cell_to_latlon(cell_a), h3_cell_to_latlon(cell_b)) {
+        (Ok((lat_a, lon_a)), Ok((lat_b, lon_b))) => haversine_km(lat_a, lon_a, lat_b, lon_b),
+        _ => 0.0,
+    }
 }
 
 /// Convert H3 hex string to (lat, lon) center coordinates.
 /// Uses the h3o crate.
-fn h3_cell_to_latlon(hex_str: &str) -> Option<(f64, f64)> {
-    let index = u64::from_str_radix(hex_str, 16).ok()?;
-    let cell = h3o::CellIndex::try_from(index).ok()?;
-    let ll = h3o::LatLng::from(cell);
-    Some((ll.lat(), ll.lng()))
+pub(crate) fn h3_cell_to_latlon(hex_str: &str) -> Result<(f64, f64)> {
+    let ll = h3o::LatLng::from(parse_cell(hex_str)?);
+    Ok((ll.lat(), ll.lng()))
+}
+
+/// Decoded cell center and quantization band, cached per chain.
+#[derive(Debug, Clone, Copy)]
+struct CellGeometry {
+    lat: f64,
+    lon: f64,
+    quantization_km: f64,
+}
+
+impl CellGeometry {
+    fn decode(hex_str: &str) -> Result<Self> {
+        let cell = parse_cell(hex_str)?;
+        let ll = h3o::LatLng::from(cell);
+        Ok(Self { lat: ll.lat(), lon: ll.lng(), quantization_km: quantization_km(cell) })
+    }
+}
+
+/// Parse an H3 cell from its hex string.
+///
+/// The string is the standard H3 representation: the 64-bit index
+/// written most significant digit first, as `h3o` and the reference
+/// H3 library print it (`8a1e8052db1ffff` at resolution 10, the
+/// leading `8` being the cell mode). It is not a byte dump, so there
+/// is no endianness to choose: `u64::from_str_radix` reads it exactly.
+/// An index serialized from little-endian bytes by mistake comes out
+/// with its mode and resolution bits scrambled.
+///
+/// Hex alone isn't enough: the index must also be a valid cell
+/// (cell mode, zero reserved bits, a real base cell, unused
+/// resolution digits set to 7). Anything else is `InvalidH3Cell`.
+pub(crate) fn parse_cell(hex_str: &str) -> Result<h3o::CellIndex> {
+    u64::from_str_radix(hex_str, 16).ok()
+        .and_then(|index| h3o::CellIndex::try_from(index).ok())
+        .ok_or_else(|| TripError::InvalidH3Cell(hex_str.to_string()))
+}
+
+/// Batches shorter than this use the scalar path even with "simd".
+pub const SIMD_MIN_BATCH: usize = 64;
+
+/// Haversine distances (km) between matching (lat, lon) entries of
+/// `from` and `to`, in degrees. With the "simd" feature, batches of
+/// at least `SIMD_MIN_BATCH` pairs are computed four at a time.
+pub fn haversine_batch_km(from: &[(f64, f64)], to: &[(f64, f64)]) -> Vec<f64> {
+    debug_assert_eq!(from.len(), to.len());
+
+    #[cfg(feature = "simd")]
+    if from.len() >= SIMD_MIN_BATCH {
+        return simd::haversine_batch_km(from, to);
+    }
+
+    from.iter().zip(to)
+        .map(|(&(lat1, lon1), &(lat2, lon2))| haversine_km(lat1, lon1, lat2, lon2))
+        .collect()
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use wide::f64x4;
+
+    /// Same formula as `haversine_km`, four pairs per step; the
+    /// remainder falls back to scalar.
+    pub(super) fn haversine_batch_km(from: &[(f64, f64)], to: &[(f64, f64)]) -> Vec<f64> {
+        let lane = |points: &[(f64, f64)], f: fn(&(f64, f64)) -> f64| {
+            f64x4::from([f(&points[0]), f(&points[1]), f(&points[2]), f(&points[3])]).to_radians()
+        };
+        let (half, diameter) = (f64x4::splat(0.5), f64x4::splat(2.0 * 6371.0));
+
+        let mut out = Vec::with_capacity(from.len());
+        for (a, b) in from.chunks_exact(4).zip(to.chunks_exact(4)) {
+            let (lat1, lon1) = (lane(a, |p| p.0), lane(a, |p| p.1));
+            let (lat2, lon2) = (lane(b, |p| p.0), lane(b, |p| p.1));
+            let sin_dlat = ((lat2 - lat1) * half).sin();
+            let sin_dlon = ((lon2 - lon1) * half).sin();
+            let h = sin_dlat * sin_dlat + lat1.cos() * lat2.cos() * sin_dlon * sin_dlon;
+            out.extend_from_slice(&(diameter * h.sqrt().asin()).to_array());
+        }
+
+        let done = out.len();
+        out.extend(from[done..].iter().zip(&to[done..])
+            .map(|(&(lat1, lon1), &(lat2, lon2))| super::haversine_km(lat1, lon1, lat2, lon2)));
+        out
+    }
 }
 
 /// Haversine great-circle distance in kilometers.
-fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+pub(crate) fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     const R: f64 = 6371.0; // Earth radius in km
     let dlat = (lat2 - lat1).to_radians();
     let dlon = (lon2 - lon1).to_radians();
@@ -132,6 +512,200 @@ fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_breadcrumb(key: &SigningKey) -> Breadcrumb {
+        let mut b = Breadcrumb {
+            index: 0,
+            identity_public_key: hex::encode(key.verifying_key().to_bytes()),
+            timestamp: "2025-01-15T10:00:00Z".parse().unwrap(),
+            location_cell: "8a1e8052da1ffff".to_string(),
+            location_resolution: 10,
+            context_digest: "ab".repeat(32),
+            previous_hash: None,
+            meta_flags: MetaFlags {
+                battery: Some(80),
+                sampling: default_sampling(),
+                state: default_unknown(),
+                network: default_unknown(),
+                accuracy: Some(5.0),
+                manual: false,
+            },
+            signature: String::new(),
+            block_hash: String::new(),
+            beacon_digest: None,
+            skip_hash: None,
+        };
+        b.signature = hex::encode(key.sign(b.data_to_sign().as_bytes()).to_bytes());
+        b
+    }
+
+    fn breadcrumb_at(key: &SigningKey, index: u64, cell: h3o::CellIndex) -> Breadcrumb {
+        let mut b = signed_breadcrumb(key);
+        b.index = index;
+        b.timestamp += chrono::Duration::minutes(30 * index as i64);
+        b.location_cell = cell.to_string();
+        b.location_resolution = u8::from(cell.resolution());
+        b
+    }
+
+    #[test]
+    fn test_quantized_distance_at_resolution_7() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let home = h3o::LatLng::new(41.9028, 12.4964).unwrap().to_cell(h3o::Resolution::Seven);
+        let neighbour = home.grid_disk::<Vec<_>>(1).into_iter().find(|&c| c != home).unwrap();
+
+        let chain = [
+            breadcrumb_at(&key, 0, home),
+            breadcrumb_at(&key, 1, home),
+            breadcrumb_at(&key, 2, neighbour),
+        ];
+        let displacements = compute_displacements(&chain).unwrap();
+        let (stay, hop) = (&displacements[0], &displacements[1]);
+
+        // Center-to-center: a move within the cell vanishes
+        assert_eq!(DistanceMode::CellCenter.distance_km(stay), 0.0);
+        assert!(hop.distance_km > 1.5 && hop.distance_km < 3.0, "pitch: {}", hop.distance_km);
+
+        // ~1.3 km band at res 7, from ~0.9 km RMS per endpoint
+        assert!(stay.uncertainty_km > 1.0 && stay.uncertainty_km < 1.6, "{}", stay.uncertainty_km);
+        assert_eq!(DistanceMode::Quantized.distance_km(stay), stay.uncertainty_km);
+
+        let widened = DistanceMode::Quantized.distance_km(hop);
+        assert!(widened > hop.distance_km);
+        assert!((widened - hop.distance_km.hypot(hop.uncertainty_km)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cached_distances_match_uncached() {
+        let mut chain = crate::chain::tests::random_walk_breadcrumbs(60, 21);
+        // Revisit earlier cells so the cache is actually hit
+        for i in 30..60 {
+            chain[i].location_cell = chain[i % 7].location_cell.clone();
+        }
+
+        let displacements = compute_displacements(&chain).unwrap();
+        for (d, pair) in displacements.iter().zip(chain.windows(2)) {
+            let (a, b) = (&pair[0].location_cell, &pair[1].location_cell);
+            assert_eq!(d.distance_km, h3_cell_distance_km(a, b));
+            assert_eq!(d.uncertainty_km, cell_quantization_km(a).hypot(cell_quantization_km(b)));
+        }
+    }
+
+    #[test]
+    fn test_h3_cell_must_be_valid_index() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut b = signed_breadcrumb(&key);
+        b.location_cell = "8a1e8052db1ffff".to_string();
+        let index = b.h3_cell().unwrap();
+        assert_eq!(index, 0x08a1_e805_2db1_ffff);
+        let cell = h3o::CellIndex::try_from(index).unwrap();
+        assert_eq!(cell.resolution(), h3o::Resolution::Ten);
+        assert_eq!(cell.to_string(), b.location_cell);
+        assert!(h3_cell_to_latlon(&b.location_cell).is_ok());
+
+        // Hex, but not a cell: the same index byte-swapped, and all ones
+        let swapped = format!("{:x}", index.swap_bytes());
+        for invalid in [swapped.as_str(), "ffffffffffffffff", "not-a-cell"] {
+            b.location_cell = invalid.to_string();
+            assert!(matches!(b.h3_cell(), Err(TripError::InvalidH3Cell(s)) if s == invalid));
+            assert!(matches!(h3_cell_to_latlon(invalid), Err(TripError::InvalidH3Cell(_))));
+        }
+
+        // In a chain it fails the displacements rather than reading as 0 km
+        let mut chain = crate::chain::tests::random_walk_breadcrumbs(10, 21);
+        chain[4].location_cell = swapped.clone();
+        assert!(matches!(
+            compute_displacements(&chain),
+            Err(TripError::InvalidH3Cell(s)) if s == swapped
+        ));
+    }
+
+    #[test]
+    fn test_sub_second_intervals_preserved() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut b0 = signed_breadcrumb(&key);
+        let mut b1 = signed_breadcrumb(&key);
+        b0.timestamp = "2025-01-15T10:00:00.100Z".parse().unwrap();
+        b1.timestamp = "2025-01-15T10:00:00.500Z".parse().unwrap();
+        b1.index = 1;
+
+        assert!((b1.unix_seconds() - b0.unix_seconds() - 0.4).abs() < 1e-6);
+
+        let dt = interval_seconds(&b0, &b1).unwrap();
+        assert!((dt - 0.4).abs() < 1e-9, "dt = {dt}");
+
+        let displacements = compute_displacements(&[b0.clone(), b1.clone()]).unwrap();
+        assert!((displacements[0].dt_seconds - 0.4).abs() < 1e-9);
+
+        // Across a second boundary: 0.9s, not 1s
+        b1.timestamp = "2025-01-15T10:00:01.000Z".parse().unwrap();
+        assert!((interval_seconds(&b0, &b1).unwrap() - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_positive_interval_errors() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let b0 = signed_breadcrumb(&key);
+        let mut b1 = signed_breadcrumb(&key);
+        b1.index = 1;
+        assert!(interval_seconds(&b0, &b1).is_err());
+        assert!(compute_displacements(&[b0.clone(), b1.clone()]).is_err());
+
+        b1.timestamp = b0.timestamp - chrono::Duration::milliseconds(1);
+        assert!(interval_seconds(&b0, &b1).is_err());
+    }
+
+    #[test]
+    fn test_timestamp_normalized_to_utc() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let b = signed_breadcrumb(&key);
+        let json = serde_json::to_string(&vec![&b]).unwrap()
+            .replace("2025-01-15T10:00:00Z", "2025-01-15T12:00:00+02:00");
+        assert!(json.contains("+02:00"));
+
+        let parsed = parse_breadcrumbs(&json).unwrap();
+        assert_eq!(parsed[0].timestamp, b.timestamp);
+        assert_eq!(parsed[0].timestamp.to_rfc3339(), "2025-01-15T10:00:00+00:00");
+        assert!(parsed[0].verify_signature().is_ok());
+
+        for bad in [
+            "2025-01-15T10:00:00",        // no offset
+            "2025-02-30T10:00:00Z",       // no such day
+            "2016-12-31T23:59:60Z",       // leap second
+            "15/01/2025 10:00",
+        ] {
+            assert!(
+                matches!(parse_timestamp(bad), Err(TripError::InvalidBreadcrumb(_))),
+                "{bad} accepted"
+            );
+            let json = serde_json::to_string(&vec![&b]).unwrap()
+                .replace("2025-01-15T10:00:00Z", bad);
+            assert!(matches!(parse_breadcrumbs(&json), Err(TripError::InvalidBreadcrumb(_))), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_verify_signature_valid() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let b = signed_breadcrumb(&key);
+        assert!(b.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature_tampered() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut b = signed_breadcrumb(&key);
+        b.location_cell = "8a1e8052db1ffff".to_string();
+        assert!(matches!(
+            b.verify_signature(),
+            Err(TripError::SignatureInvalid { index: 0 })
+        ));
+
+        let mut b = signed_breadcrumb(&key);
+        b.signature = "zz".repeat(64);
+        assert!(b.verify_signature().is_err());
+    }
 
     #[test]
     fn test_haversine_rome_to_naples() {
@@ -146,4 +720,83 @@ mod tests {
         let d = haversine_km(41.9028, 12.4964, 41.9028, 12.4964);
         assert!(d < 0.001);
     }
+
+    #[test]
+    fn test_batch_haversine_matches_scalar() {
+        // Long enough for the SIMD path, with a remainder that is not
+        let from: Vec<(f64, f64)> = (0..SIMD_MIN_BATCH + 3)
+            .map(|i| (-80.0 + 1.6 * i as f64, -179.0 + 5.3 * i as f64))
+            .collect();
+        let to: Vec<(f64, f64)> = from.iter().map(|&(lat, lon)| (lat * 0.5, -lon)).collect();
+
+        let batch = haversine_batch_km(&from, &to);
+        assert_eq!(batch.len(), from.len());
+        for ((&(lat1, lon1), &(lat2, lon2)), d) in from.iter().zip(&to).zip(batch) {
+            let scalar = haversine_km(lat1, lon1, lat2, lon2);
+            assert!((d - scalar).abs() < 1e-9, "batch {d} vs scalar {scalar}");
+        }
+    }
+
+    #[test]
+    fn test_unix_timestamps_accepted() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signed = signed_breadcrumb(&key);
+        let json = serde_json::to_value(&signed).unwrap();
+        let with_timestamp = |timestamp: serde_json::Value| {
+            let mut json = json.clone();
+            json["timestamp"] = timestamp;
+            serde_json::from_value::<Breadcrumb>(json)
+        };
+
+        let instant: DateTime<Utc> = "2025-01-15T10:00:00Z".parse().unwrap();
+        let rfc3339 = with_timestamp("2025-01-15T11:00:00+01:00".into()).unwrap();
+        let seconds = with_timestamp(1_736_935_200i64.into()).unwrap();
+        let millis = with_timestamp(1_736_935_200_000i64.into()).unwrap();
+        assert_eq!(rfc3339.timestamp, instant);
+        assert_eq!(seconds.timestamp, instant);
+        assert_eq!(millis.timestamp, instant);
+        assert_eq!(with_timestamp(1_736_935_200_250u64.into()).unwrap().timestamp_millis(), 1_736_935_200_250);
+
+        // Signed over the RFC 3339 form either way, and written back in it
+        assert_eq!(seconds.data_to_sign(), rfc3339.data_to_sign());
+        assert!(serde_json::to_string(&millis).unwrap().contains("\"2025-01-15T10:00:00"));
+
+        assert!(with_timestamp(i64::MAX.into()).is_err());
+        assert!(with_timestamp(1.5e9.into()).is_err());
+    }
+
+    #[test]
+    fn test_ndjson_matches_array() {
+        use crate::chain::BreadcrumbChain;
+        use crate::chain::tests::{random_walk_breadcrumbs, sign_breadcrumbs};
+
+        let mut breadcrumbs = random_walk_breadcrumbs(80, 3);
+        sign_breadcrumbs(&mut breadcrumbs, &SigningKey::from_bytes(&[7u8; 32]));
+        let array = serde_json::to_string_pretty(&breadcrumbs).unwrap();
+        let ndjson: String = breadcrumbs.iter()
+            .map(|b| serde_json::to_string(b).unwrap() + "\n")
+            .collect();
+
+        let from_array = BreadcrumbChain::from_breadcrumbs(parse_breadcrumbs(&array).unwrap()).unwrap();
+        let from_ndjson = BreadcrumbChain::from_breadcrumbs(parse_breadcrumbs(&ndjson).unwrap()).unwrap();
+        from_ndjson.verify_signatures().unwrap();
+        assert_eq!(
+            serde_json::to_string(&from_ndjson.breadcrumbs).unwrap(),
+            serde_json::to_string(&from_array.breadcrumbs).unwrap()
+        );
+        assert_eq!(from_ndjson.head_hash(), from_array.head_hash());
+
+        // Streamed from a reader, with blank lines and CRLF endings
+        let spaced = format!("\r\n{}", ndjson.replace('\n', "\r\n\r\n"));
+        let streamed = read_breadcrumbs_ndjson(std::io::BufReader::new(spaced.as_bytes())).unwrap();
+        assert_eq!(streamed.len(), 80);
+        assert_eq!(parse_breadcrumbs(&format!("  \n{spaced}")).unwrap().len(), 80);
+
+        // A bad line is named
+        let broken = ndjson.replacen("\n", "\n{\"index\": \n", 1);
+        assert!(matches!(
+            parse_breadcrumbs(&broken),
+            Err(TripError::InvalidBreadcrumb(msg)) if msg.starts_with("line 2:")
+        ));
+    }
 }
@@ -0,0 +1,224 @@
+// trip-verifier/src/calibration.rs
+//
+// False-Accept Calibration
+// =========================
+//
+// A trust score is a ranking, not a probability. To set acceptance
+// policy against a target false-accept rate (FAR), a Verifier runs
+// the engine over labelled farmed/synthetic chains and records, per
+// (trust score, chain length), the share of them that were accepted.
+//
+// Table format (JSON):
+//
+//   {
+//     "trust_scores":  [50.0, 70.0, 90.0],
+//     "chain_lengths": [100, 500, 2000],
+//     "false_accept_rates": [
+//       [0.20, 0.05, 0.010],    // chain_lengths[0], one rate per score
+//       [0.10, 0.02, 0.004],
+//       [0.05, 0.01, 0.001]
+//     ]
+//   }
+//
+// Both axes are non-empty, finite and strictly increasing; every
+// constructor (`new`, `from_json`, serde) checks this, so a table in
+// hand is always usable. Lookups interpolate bilinearly and clamp to
+// the edge of the grid outside it, so a score above the highest
+// calibrated one gets that row's lowest measured rate, never an
+// extrapolated smaller one.
+
+use serde::{Deserialize, Serialize};
+use crate::error::{TripError, Result};
+
+/// Empirical false-accept rates over (chain length, trust score).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawCalibrationTable")]
+pub struct CalibrationTable {
+    /// Trust score grid points [0, 100], strictly increasing
+    trust_scores: Vec<f64>,
+    /// Chain length grid points, strictly increasing
+    chain_lengths: Vec<usize>,
+    /// `false_accept_rates[i][j]` is the rate at
+    /// (`chain_lengths[i]`, `trust_scores[j]`), in [0, 1]
+    false_accept_rates: Vec<Vec<f64>>,
+}
+
+/// `CalibrationTable` as decoded, before validation.
+#[derive(Deserialize)]
+struct RawCalibrationTable {
+    trust_scores: Vec<f64>,
+    chain_lengths: Vec<usize>,
+    false_accept_rates: Vec<Vec<f64>>,
+}
+
+impl TryFrom<RawCalibrationTable> for CalibrationTable {
+    type Error = TripError;
+
+    fn try_from(raw: RawCalibrationTable) -> Result<Self> {
+        Self::new(raw.trust_scores, raw.chain_lengths, raw.false_accept_rates)
+    }
+}
+
+impl CalibrationTable {
+    /// A table over the given axes; `false_accept_rates[i][j]` is the
+    /// rate at (`chain_lengths[i]`, `trust_scores[j]`). Fails with
+    /// `DeserializeError` unless both axes are non-empty, finite and
+    /// strictly increasing and every rate is in [0, 1].
+    pub fn new(
+        trust_scores: Vec<f64>,
+        chain_lengths: Vec<usize>,
+        false_accept_rates: Vec<Vec<f64>>,
+    ) -> Result<Self> {
+        let table = Self { trust_scores, chain_lengths, false_accept_rates };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Load and validate a table from its JSON form.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| TripError::DeserializeError(format!("Calibration decode error: {e}")))
+    }
+
+    /// Trust score grid points.
+    pub fn trust_scores(&self) -> &[f64] {
+        &self.trust_scores
+    }
+
+    /// Chain length grid points.
+    pub fn chain_lengths(&self) -> &[usize] {
+        &self.chain_lengths
+    }
+
+    /// Check axis ordering, dimensions and rate range.
+    fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| Err(TripError::DeserializeError(format!("Calibration table: {msg}")));
+
+        if self.trust_scores.is_empty() || self.chain_lengths.is_empty() {
+            return invalid("empty axis");
+        }
+        if self.trust_scores.iter().any(|s| !s.is_finite()) {
+            return invalid("trust score not finite");
+        }
+        if !self.trust_scores.windows(2).all(|w| w[0] < w[1]) {
+            return invalid("trust scores not strictly increasing");
+        }
+        if !self.chain_lengths.windows(2).all(|w| w[0] < w[1]) {
+            return invalid("chain lengths not strictly increasing");
+        }
+        if self.false_accept_rates.len() != self.chain_lengths.len()
+            || self.false_accept_rates.iter().any(|row| row.len() != self.trust_scores.len())
+        {
+            return invalid("rate grid does not match axes");
+        }
+        if self.false_accept_rates.iter().flatten().any(|r| !(0.0..=1.0).contains(r)) {
+            return invalid("rate outside [0, 1]");
+        }
+        Ok(())
+    }
+
+    /// Interpolated false-accept rate for a verdict. A NaN trust score
+    /// is treated as the lowest calibrated one, the most conservative
+    /// reading.
+    pub fn false_accept_rate(&self, trust_score: f64, chain_length: usize) -> f64 {
+        let (i0, i1, u) = bracket(
+            &self.chain_lengths.iter().map(|&n| n as f64).collect::<Vec<_>>(),
+            chain_length as f64,
+        );
+        let (j0, j1, v) = bracket(&self.trust_scores, trust_score);
+
+        let rate = |i: usize, j: usize| self.false_accept_rates[i][j];
+        let low = rate(i0, j0) + v * (rate(i0, j1) - rate(i0, j0));
+        let high = rate(i1, j0) + v * (rate(i1, j1) - rate(i1, j0));
+        low + u * (high - low)
+    }
+}
+
+/// Grid indices around `x` and the fraction of the way from the
+/// first to the second, clamped to the ends of `axis` (a NaN `x` to
+/// the start). `axis` is non-empty and strictly increasing.
+fn bracket(axis: &[f64], x: f64) -> (usize, usize, f64) {
+    let last = axis.len() - 1;
+    if x.is_nan() || x <= axis[0] {
+        return (0, 0, 0.0);
+    }
+    if x >= axis[last] {
+        return (last, last, 0.0);
+    }
+    let hi = axis.partition_point(|&a| a <= x);
+    let lo = hi - 1;
+    (lo, hi, (x - axis[lo]) / (axis[hi] - axis[lo]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"{
+        "trust_scores": [50.0, 70.0, 90.0],
+        "chain_lengths": [100, 500, 2000],
+        "false_accept_rates": [
+            [0.20, 0.05, 0.010],
+            [0.10, 0.02, 0.004],
+            [0.05, 0.01, 0.001]
+        ]
+    }"#;
+
+    #[test]
+    fn test_interpolated_false_accept_rates() {
+        let table = CalibrationTable::from_json(FIXTURE).unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+
+        // Grid points
+        assert!(close(table.false_accept_rate(70.0, 500), 0.02));
+        // Along one axis
+        assert!(close(table.false_accept_rate(60.0, 100), 0.125));
+        assert!(close(table.false_accept_rate(90.0, 1250), 0.0025));
+        // Bilinear
+        assert!(close(table.false_accept_rate(80.0, 300), 0.5 * 0.03 + 0.5 * 0.012));
+        // Clamped outside the grid
+        assert!(close(table.false_accept_rate(99.0, 5000), 0.001));
+        assert!(close(table.false_accept_rate(10.0, 20), 0.20));
+    }
+
+    #[test]
+    fn test_invalid_tables_rejected() {
+        let bad = [
+            FIXTURE.replace("[50.0, 70.0, 90.0]", "[50.0, 90.0, 70.0]"),
+            FIXTURE.replace("[100, 500, 2000]", "[100, 500]"),
+            FIXTURE.replace("0.20", "1.20"),
+            FIXTURE.replace("[50.0, 70.0, 90.0]", "[]"),
+            FIXTURE.replace("[100, 500, 2000]", "[]"),
+            "{}".to_string(),
+        ];
+        for json in &bad {
+            assert!(matches!(CalibrationTable::from_json(json), Err(TripError::DeserializeError(_))));
+            // Plain serde goes through the same checks
+            assert!(serde_json::from_str::<CalibrationTable>(json).is_err());
+        }
+
+        let invalid = [
+            CalibrationTable::new(vec![], vec![100], vec![vec![]]),
+            CalibrationTable::new(vec![50.0], vec![], vec![]),
+            CalibrationTable::new(vec![f64::NAN], vec![100], vec![vec![0.1]]),
+            CalibrationTable::new(vec![50.0, f64::INFINITY], vec![100], vec![vec![0.1, 0.05]]),
+            CalibrationTable::new(vec![50.0], vec![100], vec![vec![f64::NAN]]),
+            CalibrationTable::new(vec![70.0, 50.0], vec![100], vec![vec![0.1, 0.2]]),
+        ];
+        for table in invalid {
+            assert!(matches!(table, Err(TripError::DeserializeError(_))));
+        }
+    }
+
+    #[test]
+    fn test_non_finite_lookups_do_not_panic() {
+        let table = CalibrationTable::from_json(FIXTURE).unwrap();
+        assert_eq!(table.false_accept_rate(f64::NAN, 100), 0.20);
+        assert_eq!(table.false_accept_rate(f64::NEG_INFINITY, 100), 0.20);
+        assert_eq!(table.false_accept_rate(f64::INFINITY, 100), 0.010);
+
+        let single = CalibrationTable::new(vec![60.0], vec![100], vec![vec![0.3]]).unwrap();
+        assert_eq!(single.false_accept_rate(f64::NAN, 5000), 0.3);
+        assert_eq!(single.false_accept_rate(80.0, 10), 0.3);
+    }
+}
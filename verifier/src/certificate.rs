@@ -12,26 +12,221 @@
 //   1: alpha,              (float)
 //   2: beta,               (float)
 //   3: kappa,              (float)
-//   4: trust_score,        (uint)
+//   4: trust_score,        (uint, milli-points)
 //   5: confidence,         (float)
 //   6: chain_length,       (uint)
 //   7: unique_cells,       (uint)
 //   8: mean_hamiltonian,   (float)
 //   9: verifier_key,       (bstr .size 32)
-//  10: issued_at,          (uint, Unix seconds)
+//  10: issued_at,          (uint, Unix milliseconds)
 //  11: valid_seconds,      (uint)
 //  12: nonce,              (bstr .size 16) [Active Verification]
 //  13: chain_head_hash,    (bstr .size 32) [Active Verification]
 //  14: verifier_signature, (bstr .size 64)
+//  15: config_digest,      (bstr .size 32) [optional]
 // }
+//
+// Float precision: the statistics are rounded to a few decimal places
+// (DEFAULT_CERT_DECIMALS) when the certificate is built. Digits past
+// the estimators' own error only fingerprint the user's mobility
+// profile more finely and bloat the payload, so this is both a
+// privacy and a size measure. The trust score is carried as a whole
+// number of milli-points (TRUST_SCORE_DECIMALS) and issued_at as Unix
+// milliseconds, so a certificate as built decodes back unchanged and
+// the Verifier signs exactly the values it holds.
+//
+// Signed bytes are deterministic CBOR (RFC 8949 §4.2), so any
+// implementation re-encoding the same certificate gets the same bytes:
+// - integers and lengths in their shortest form
+// - floats in the shortest of half/single/double that keeps the exact
+//   value; NaN as the half-precision 0xf97e00
+// - definite lengths only
+// - map keys sorted bytewise by their own encoding, no duplicates
+//   (for keys 0-15 this is plain numeric order)
+//
+// That signed encoding is fixed. How a whole certificate travels is
+// not: `CertificateCodec` encodes and decodes it, with `CborCodec`
+// (fields 0-15 as above) and `JsonCodec` (API responses) provided.
+// An integrator needing MessagePack or another CBOR library implements
+// the trait; signatures still cover `to_cbor_signable`, so they verify
+// whatever codec carried the certificate.
 
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use crate::criticality::CriticalityResult;
+use crate::chain::BreadcrumbChain;
+use crate::clock::{Clock, SystemClock};
+use crate::criticality::{CriticalityConfig, CriticalityEngine, CriticalityResult};
 use crate::error::{TripError, Result};
+use crate::revocation::RevocationList;
+
+/// Decimal places kept for certificate floats by default.
+pub const DEFAULT_CERT_DECIMALS: u32 = 3;
+
+/// Most decimal places `with_decimals` rounds to; an f64 carries no
+/// more significant digits than this anyway.
+pub const MAX_CERT_DECIMALS: u32 = 15;
+
+/// Decimal places of the trust score, which CBOR field 4 carries as
+/// integer milli-points; `with_decimals` never keeps more.
+pub const TRUST_SCORE_DECIMALS: u32 = 3;
+
+/// Clock skew tolerated by default when checking validity (seconds).
+/// Verifier and Relying Party clocks are rarely in exact agreement;
+/// 30 s covers ordinary NTP-synced drift without noticeably extending
+/// a certificate's lifetime.
+pub const DEFAULT_CLOCK_SKEW_SECONDS: u64 = 30;
+
+/// Trust tier of a certificate, bucketed from the trust score.
+/// These are bands of a single result's score, not the epoch- and
+/// vouch-based trust levels of spec/TRUST.md.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TrustTier {
+    /// T < 40 — not verified as human
+    Untrusted,
+    /// T ∈ [40, 60) — barely verified
+    Borderline,
+    /// T ∈ [60, 75)
+    Moderate,
+    /// T ∈ [75, 90)
+    Strong,
+    /// T ≥ 90
+    Excellent,
+}
+
+impl TrustTier {
+    pub fn from_trust_score(score: f64) -> Self {
+        match score {
+            s if s >= 90.0 => Self::Excellent,
+            s if s >= 75.0 => Self::Strong,
+            s if s >= 60.0 => Self::Moderate,
+            s if s >= 40.0 => Self::Borderline,
+            _ => Self::Untrusted,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Untrusted => "untrusted",
+            Self::Borderline => "borderline",
+            Self::Moderate => "moderate",
+            Self::Strong => "strong",
+            Self::Excellent => "excellent",
+        }
+    }
+}
+
+/// Certificate validity per trust tier (seconds).
+///
+/// Higher-trust results stay valid longer, reducing re-verification
+/// load; borderline results expire quickly so they are re-checked
+/// against fresh Evidence. Defaults:
+///
+/// | Tier       | Validity |
+/// |------------|----------|
+/// | Untrusted  | 15 min   |
+/// | Borderline | 1 h      |
+/// | Moderate   | 4 h      |
+/// | Strong     | 12 h     |
+/// | Excellent  | 24 h     |
+///
+/// Every validity is capped at `max_valid_seconds` (7 days by
+/// default). A certificate is a bearer credential: once issued it
+/// can't be recalled short of revoking the Verifier key (see
+/// `RevocationList`), and the movement it vouches for goes stale
+/// within days. A misconfigured tier or a seconds/milliseconds mix-up
+/// would otherwise issue one valid for years.
+///
+/// Building a certificate clamps its validity to the cap; `sign`,
+/// `to_compact_binary` and both forms' `verify` reject one over it,
+/// however `valid_seconds` got there.
+#[derive(Debug, Clone)]
+pub struct ValidityPolicy {
+    pub untrusted: u64,
+    pub borderline: u64,
+    pub moderate: u64,
+    pub strong: u64,
+    pub excellent: u64,
+    /// Longest validity issued, whatever the tier (seconds)
+    pub max_valid_seconds: u64,
+}
+
+/// Default cap on certificate validity: 7 days.
+pub const DEFAULT_MAX_VALID_SECONDS: u64 = 7 * 86_400;
+
+impl Default for ValidityPolicy {
+    fn default() -> Self {
+        Self {
+            untrusted: 900,
+            borderline: 3_600,
+            moderate: 14_400,
+            strong: 43_200,
+            excellent: 86_400,
+            max_valid_seconds: DEFAULT_MAX_VALID_SECONDS,
+        }
+    }
+}
+
+impl ValidityPolicy {
+    /// Same validity for every tier, still capped at
+    /// `DEFAULT_MAX_VALID_SECONDS`.
+    pub fn fixed(valid_seconds: u64) -> Self {
+        Self {
+            untrusted: valid_seconds,
+            borderline: valid_seconds,
+            moderate: valid_seconds,
+            strong: valid_seconds,
+            excellent: valid_seconds,
+            max_valid_seconds: DEFAULT_MAX_VALID_SECONDS,
+        }
+    }
+
+    /// Validity for `tier`, capped at `max_valid_seconds`.
+    pub fn for_tier(&self, tier: TrustTier) -> u64 {
+        self.cap(match tier {
+            TrustTier::Untrusted => self.untrusted,
+            TrustTier::Borderline => self.borderline,
+            TrustTier::Moderate => self.moderate,
+            TrustTier::Strong => self.strong,
+            TrustTier::Excellent => self.excellent,
+        })
+    }
+
+    /// `valid_seconds` clamped to `max_valid_seconds`. A clamp is
+    /// logged as a warning with the `tracing` feature and is silent
+    /// without it; compare the result with the request to detect it.
+    pub fn cap(&self, valid_seconds: u64) -> u64 {
+        if valid_seconds > self.max_valid_seconds {
+            trace_event!(
+                warn,
+                requested = valid_seconds,
+                max = self.max_valid_seconds,
+                "certificate validity clamped"
+            );
+            return self.max_valid_seconds;
+        }
+        valid_seconds
+    }
+
+    /// Reject a validity longer than `max_valid_seconds`, as `sign`
+    /// and `verify` do.
+    pub fn check(&self, valid_seconds: u64) -> Result<()> {
+        if valid_seconds > self.max_valid_seconds {
+            return Err(TripError::CertificateError(format!(
+                "Certificate validity {} s exceeds the {} s cap",
+                valid_seconds, self.max_valid_seconds
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn valid_seconds(&self, trust_score: f64) -> u64 {
+        self.for_tier(TrustTier::from_trust_score(trust_score))
+    }
+}
 
 /// PoH Certificate — the Attestation Result.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PoHCertificate {
     /// Ed25519 public key of the identity (Attester)
     pub identity_key: String,     // hex, 64 chars
@@ -75,27 +270,74 @@ pub struct PoHCertificate {
     /// Chain head hash at time of verification
     pub chain_head_hash: Option<String>, // hex, 64 chars
 
-    /// Ed25519 signature by the Verifier over fields 0-13
+    /// Ed25519 signature by the Verifier over fields 0-13 and 15
     pub verifier_signature: Option<String>, // hex, 128 chars
+
+    /// SHA-256 of the engine setup used (see `CriticalityEngine::digest`)
+    #[serde(default)]
+    pub config_digest: Option<String>, // hex, 64 chars
+}
+
+/// How a verdict moved between two certificates for the same
+/// identity (see `PoHCertificate::diff`). Deltas are later − earlier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateDiff {
+    pub trust_score: f64,
+    pub confidence: f64,
+    pub chain_length: i64,
+    pub alpha: f64,
+    pub beta: f64,
+    /// Time between the two issuances (seconds)
+    pub elapsed_seconds: i64,
+    pub tier_before: TrustTier,
+    pub tier_after: TrustTier,
+}
+
+impl CertificateDiff {
+    /// Did the identity cross the human threshold (trust tier above
+    /// `Untrusted`), in either direction?
+    pub fn human_flipped(&self) -> bool {
+        (self.tier_before == TrustTier::Untrusted) != (self.tier_after == TrustTier::Untrusted)
+    }
 }
 
 impl PoHCertificate {
-    /// Create a certificate from a CriticalityResult.
+    /// Create a certificate from a CriticalityResult, with floats
+    /// rounded to `DEFAULT_CERT_DECIMALS`.
     ///
     /// # Arguments
     /// * `result` — output of the Criticality Engine
     /// * `identity_key` — Attester's Ed25519 public key hex
     /// * `verifier_key` — Verifier's Ed25519 public key hex
-    /// * `unique_cells` — number of unique H3 cells
     /// * `chain_head_hash` — hash of the most recent breadcrumb
-    /// * `valid_seconds` — certificate validity duration
+    /// * `valid_seconds` — certificate validity duration, capped at
+    ///   `DEFAULT_MAX_VALID_SECONDS`; another cap takes
+    ///   `with_valid_seconds` or `with_validity_policy`
     pub fn from_criticality_result(
         result: &CriticalityResult,
         identity_key: String,
         verifier_key: String,
-        unique_cells: usize,
         chain_head_hash: String,
         valid_seconds: u64,
+    ) -> Self {
+        Self::from_criticality_result_with_clock(
+            result,
+            identity_key,
+            verifier_key,
+            chain_head_hash,
+            valid_seconds,
+            &SystemClock,
+        )
+    }
+
+    /// Like `from_criticality_result`, issued at `clock`'s time.
+    pub fn from_criticality_result_with_clock(
+        result: &CriticalityResult,
+        identity_key: String,
+        verifier_key: String,
+        chain_head_hash: String,
+        valid_seconds: u64,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             identity_key,
@@ -105,15 +347,96 @@ impl PoHCertificate {
             trust_score: result.trust_score,
             confidence: result.confidence,
             chain_length: result.chain_length as u64,
-            unique_cells: unique_cells as u64,
+            // Taken from the engine's own count, never from the caller
+            unique_cells: result.unique_cells as u64,
             mean_hamiltonian: result.hamiltonian.mean_energy,
             verifier_key,
-            issued_at: Utc::now(),
-            valid_seconds,
+            // CBOR field 10 carries milliseconds
+            issued_at: DateTime::from_timestamp_millis(clock.now().timestamp_millis())
+                .expect("milliseconds of a valid timestamp are in range"),
+            valid_seconds: ValidityPolicy::default().cap(valid_seconds),
             nonce: None,
             chain_head_hash: Some(chain_head_hash),
             verifier_signature: None,
+            config_digest: None,
         }
+        .with_decimals(DEFAULT_CERT_DECIMALS)
+    }
+
+    /// Round the float statistics (α, β, κ, trust score, confidence,
+    /// mean Hamiltonian) to `decimals` places, at most
+    /// `MAX_CERT_DECIMALS` (`TRUST_SCORE_DECIMALS` for the trust
+    /// score).
+    ///
+    /// The trust score keeps the tier of its unrounded value: one that
+    /// would round up onto a tier boundary (39.9996 → 40.000) is
+    /// truncated instead. Rounding can only remove precision; call it
+    /// before `sign`, as the signature covers the values as they are.
+    pub fn with_decimals(mut self, decimals: u32) -> Self {
+        let rescale = |x: f64, decimals: u32, to_integer: fn(f64) -> f64| {
+            let scale = 10f64.powi(decimals.min(MAX_CERT_DECIMALS) as i32);
+            let scaled = x * scale;
+            // Past 2^52 every f64 is already an integer
+            if scaled.abs() < 2f64.powi(52) { to_integer(scaled) / scale } else { x }
+        };
+        let round = |x: f64| rescale(x, decimals, f64::round);
+
+        let tier = self.trust_tier();
+        let trust_decimals = decimals.min(TRUST_SCORE_DECIMALS);
+        self.trust_score = match rescale(self.trust_score, trust_decimals, f64::round) {
+            rounded if TrustTier::from_trust_score(rounded) == tier => rounded,
+            _ => rescale(self.trust_score, trust_decimals, f64::trunc),
+        };
+        self.alpha = round(self.alpha);
+        self.beta = round(self.beta);
+        self.kappa = round(self.kappa);
+        self.confidence = round(self.confidence);
+        self.mean_hamiltonian = round(self.mean_hamiltonian);
+        self
+    }
+
+    /// Sign fields 0-13 (and 15) with the Verifier key.
+    ///
+    /// The signing key must match `verifier_key`, and `valid_seconds`
+    /// must be within `DEFAULT_MAX_VALID_SECONDS`.
+    pub fn sign(self, verifier_signing_key: &SigningKey) -> Result<Self> {
+        self.sign_with_policy(verifier_signing_key, &ValidityPolicy::default())
+    }
+
+    /// Like `sign`, with `valid_seconds` within `policy`'s
+    /// `max_valid_seconds`.
+    pub fn sign_with_policy(mut self, verifier_signing_key: &SigningKey, policy: &ValidityPolicy) -> Result<Self> {
+        policy.check(self.valid_seconds)?;
+        if hex::encode(verifier_signing_key.verifying_key().to_bytes()) != self.verifier_key {
+            return Err(TripError::CertificateError(
+                "Signing key does not match verifier_key".to_string()
+            ));
+        }
+        let signature = verifier_signing_key.sign(&self.to_cbor_signable()?);
+        self.verifier_signature = Some(hex::encode(signature.to_bytes()));
+        Ok(self)
+    }
+
+    /// Check the Verifier signature against `verifier_key`.
+    pub fn verify_signature(&self) -> Result<()> {
+        let invalid = || TripError::CertificateError("Invalid verifier signature".to_string());
+
+        let key_bytes: [u8; 32] = decode_hex_field(&self.verifier_key, "verifier_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(
+            self.verifier_signature.as_deref().ok_or_else(invalid)?,
+            "verifier_signature",
+        )?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| invalid())?;
+        key.verify(&self.to_cbor_signable()?, &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| invalid())
+    }
+
+    /// Record the digest of the engine config behind this verdict,
+    /// so a Relying Party can fetch that config and reproduce it.
+    pub fn with_config_digest(mut self, digest: String) -> Self {
+        self.config_digest = Some(digest);
+        self
     }
 
     /// Set the Active Verification nonce (from Relying Party).
@@ -122,16 +445,53 @@ impl PoHCertificate {
         self
     }
 
-    /// Encode the certificate to CBOR bytes (fields 0-13, for signing).
+    /// Cross-check `unique_cells` against the chain it was issued for.
+    pub fn verify_unique_cells(&self, chain: &BreadcrumbChain) -> Result<()> {
+        let actual = chain.unique_cells() as u64;
+        if self.unique_cells != actual {
+            return Err(TripError::CertificateError(
+                format!(
+                    "unique_cells mismatch: certificate {}, chain {}",
+                    self.unique_cells, actual
+                )
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set validity from the certificate's trust tier, capped at the
+    /// policy's `max_valid_seconds`.
+    pub fn with_validity_policy(mut self, policy: &ValidityPolicy) -> Self {
+        self.valid_seconds = policy.valid_seconds(self.trust_score);
+        self
+    }
+
+    /// Set validity to `valid_seconds`, capped at the policy's
+    /// `max_valid_seconds`.
+    pub fn with_valid_seconds(mut self, valid_seconds: u64, policy: &ValidityPolicy) -> Self {
+        self.valid_seconds = policy.cap(valid_seconds);
+        self
+    }
+
+    /// Trust tier of this certificate's trust score.
+    pub fn trust_tier(&self) -> TrustTier {
+        TrustTier::from_trust_score(self.trust_score)
+    }
+
+    /// Encode the certificate to CBOR bytes (fields 0-13 and 15, for signing).
     pub fn to_cbor_signable(&self) -> Result<Vec<u8>> {
+        canonical_cbor(&ciborium::Value::Map(self.signable_fields()?))
+    }
+
+    /// Map entries for fields 0-13 and 15, in field order.
+    fn signable_fields(&self) -> Result<Vec<(ciborium::Value, ciborium::Value)>> {
         use ciborium::Value;
 
         let mut map = Vec::new();
 
         // 0: identity_key
-        let id_bytes = hex::decode(&self.identity_key)
-            .map_err(|e| TripError::CertificateError(format!("Invalid identity hex: {e}")))?;
-        map.push((Value::Integer(0.into()), Value::Bytes(id_bytes)));
+        let id_bytes: [u8; 32] = decode_hex_field(&self.identity_key, "identity_key")?;
+        map.push((Value::Integer(0.into()), Value::Bytes(id_bytes.to_vec())));
 
         // 1: alpha
         map.push((Value::Integer(1.into()), Value::Float(self.alpha)));
@@ -142,8 +502,14 @@ impl PoHCertificate {
         // 3: kappa
         map.push((Value::Integer(3.into()), Value::Float(self.kappa)));
 
-        // 4: trust_score
-        map.push((Value::Integer(4.into()), Value::Integer((self.trust_score as i64).into())));
+        // 4: trust_score (milli-points)
+        let milli_points = (self.trust_score * 10f64.powi(TRUST_SCORE_DECIMALS as i32)).round();
+        if !(0.0..=u32::MAX as f64).contains(&milli_points) {
+            return Err(TripError::CertificateError(format!(
+                "trust_score {} cannot be encoded", self.trust_score
+            )));
+        }
+        map.push((Value::Integer(4.into()), Value::Integer((milli_points as u64).into())));
 
         // 5: confidence
         map.push((Value::Integer(5.into()), Value::Float(self.confidence)));
@@ -158,12 +524,11 @@ impl PoHCertificate {
         map.push((Value::Integer(8.into()), Value::Float(self.mean_hamiltonian)));
 
         // 9: verifier_key
-        let vk_bytes = hex::decode(&self.verifier_key)
-            .map_err(|e| TripError::CertificateError(format!("Invalid verifier hex: {e}")))?;
-        map.push((Value::Integer(9.into()), Value::Bytes(vk_bytes)));
+        let vk_bytes: [u8; 32] = decode_hex_field(&self.verifier_key, "verifier_key")?;
+        map.push((Value::Integer(9.into()), Value::Bytes(vk_bytes.to_vec())));
 
-        // 10: issued_at (Unix seconds)
-        map.push((Value::Integer(10.into()), Value::Integer((self.issued_at.timestamp() as i64).into())));
+        // 10: issued_at (Unix milliseconds)
+        map.push((Value::Integer(10.into()), Value::Integer(self.issued_at.timestamp_millis().into())));
 
         // 11: valid_seconds
         map.push((Value::Integer(11.into()), Value::Integer((self.valid_seconds as i64).into())));
@@ -175,28 +540,105 @@ impl PoHCertificate {
 
         // 13: chain_head_hash (if present)
         if let Some(ref hash) = self.chain_head_hash {
-            let hash_bytes = hex::decode(hash)
-                .map_err(|e| TripError::CertificateError(format!("Invalid hash hex: {e}")))?;
-            map.push((Value::Integer(13.into()), Value::Bytes(hash_bytes)));
+            let hash_bytes: [u8; 32] = decode_hex_field(hash, "chain_head_hash")?;
+            map.push((Value::Integer(13.into()), Value::Bytes(hash_bytes.to_vec())));
         }
 
-        let cbor_value = Value::Map(map);
-        let mut buf = Vec::new();
-        ciborium::into_writer(&cbor_value, &mut buf)
-            .map_err(|e| TripError::CertificateError(format!("CBOR encode error: {e}")))?;
+        // 15: config_digest (if present)
+        if let Some(ref digest) = self.config_digest {
+            let digest_bytes: [u8; 32] = decode_hex_field(digest, "config_digest")?;
+            map.push((Value::Integer(15.into()), Value::Bytes(digest_bytes.to_vec())));
+        }
 
-        Ok(buf)
+        Ok(map)
     }
 
-    /// Encode the full certificate (including signature) to CBOR.
+    /// Encode the full certificate to CBOR: the signable fields plus
+    /// 14 (verifier_signature) once signed.
     pub fn to_cbor(&self) -> Result<Vec<u8>> {
-        let signable = self.to_cbor_signable()?;
+        use ciborium::Value;
 
-        // For the full certificate, we'd add field 14 (signature)
-        // This is a simplified version; full implementation would
-        // reconstruct the map with the signature field.
-        // For now, return the signable portion.
-        Ok(signable)
+        let mut map = self.signable_fields()?;
+        if let Some(ref signature) = self.verifier_signature {
+            let sig_bytes: [u8; 64] = decode_hex_field(signature, "verifier_signature")?;
+            map.push((Value::Integer(14.into()), Value::Bytes(sig_bytes.to_vec())));
+        }
+        canonical_cbor(&Value::Map(map))
+    }
+
+    /// Decode a certificate from its CBOR encoding (`to_cbor`).
+    ///
+    /// Fields 0-11 are required; 12-15 are optional. Unknown or
+    /// duplicate keys are rejected, so decoding and re-encoding a
+    /// canonical certificate gives back the same bytes. The signature
+    /// is not checked; call `verify` for that.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        use ciborium::Value;
+
+        let error = |msg: String| TripError::DeserializeError(format!("Certificate decode error: {msg}"));
+        let value: Value = ciborium::de::from_reader(bytes).map_err(|e| error(e.to_string()))?;
+        let Value::Map(entries) = value else {
+            return Err(error("not a map".to_string()));
+        };
+
+        let mut fields: [Option<Value>; 16] = Default::default();
+        for (key, value) in entries {
+            let key = key.as_integer()
+                .and_then(|k| usize::try_from(k).ok())
+                .filter(|&k| k < fields.len())
+                .ok_or_else(|| error(format!("unknown key {key:?}")))?;
+            if fields[key].replace(value).is_some() {
+                return Err(error(format!("duplicate key {key}")));
+            }
+        }
+
+        let mut take = |key: usize| fields[key].take();
+        let required = |key: usize, value: Option<Value>| value.ok_or_else(|| error(format!("missing field {key}")));
+        let float = |key: usize, value: Option<Value>| match required(key, value)? {
+            Value::Float(x) => Ok(x),
+            other => Err(error(format!("field {key}: expected a float, got {other:?}"))),
+        };
+        let uint = |key: usize, value: Option<Value>| {
+            required(key, value)?.as_integer()
+                .and_then(|i| u64::try_from(i).ok())
+                .ok_or_else(|| error(format!("field {key}: expected an unsigned integer")))
+        };
+        let bytes = |key: usize, value: Value, len: Option<usize>| match value {
+            Value::Bytes(b) if len.is_none_or(|len| b.len() == len) => Ok(b),
+            other => Err(error(format!("field {key}: expected {len:?} bytes, got {other:?}"))),
+        };
+        let hex_of = |key: usize, value: Value, len: usize| bytes(key, value, Some(len)).map(hex::encode);
+
+        let issued_at = uint(10, take(10))?;
+        let issued_at = i64::try_from(issued_at).ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| error(format!("field 10: timestamp {issued_at} out of range")))?;
+
+        Ok(Self {
+            identity_key: hex_of(0, required(0, take(0))?, 32)?,
+            alpha: float(1, take(1))?,
+            beta: float(2, take(2))?,
+            kappa: float(3, take(3))?,
+            trust_score: uint(4, take(4))? as f64 / 10f64.powi(TRUST_SCORE_DECIMALS as i32),
+            confidence: float(5, take(5))?,
+            chain_length: uint(6, take(6))?,
+            unique_cells: uint(7, take(7))?,
+            mean_hamiltonian: float(8, take(8))?,
+            verifier_key: hex_of(9, required(9, take(9))?, 32)?,
+            issued_at,
+            valid_seconds: uint(11, take(11))?,
+            nonce: take(12).map(|v| bytes(12, v, None)).transpose()?,
+            chain_head_hash: take(13).map(|v| hex_of(13, v, 32)).transpose()?,
+            verifier_signature: take(14).map(|v| hex_of(14, v, 64)).transpose()?,
+            config_digest: take(15).map(|v| hex_of(15, v, 32)).transpose()?,
+        })
+    }
+
+    /// SHA-256 of the signable CBOR encoding, hex.
+    /// Identifies exactly which certificate was issued.
+    pub fn fingerprint(&self) -> Result<String> {
+        use sha2::{Digest, Sha256};
+        Ok(hex::encode(Sha256::digest(self.to_cbor_signable()?)))
     }
 
     /// Encode to JSON for API responses.
@@ -205,23 +647,798 @@ impl PoHCertificate {
             .map_err(|e| TripError::CertificateError(format!("JSON encode error: {e}")))
     }
 
-    /// Is this certificate still valid?
+    /// Encode the full certificate with `codec`.
+    pub fn encode_with(&self, codec: &dyn CertificateCodec) -> Result<Vec<u8>> {
+        codec.encode(self)
+    }
+
+    /// Decode a certificate `codec` encoded. The signature is not
+    /// checked; call `verify` for that.
+    pub fn decode_with(codec: &dyn CertificateCodec, bytes: &[u8]) -> Result<Self> {
+        codec.decode(bytes)
+    }
+
+    /// Is this certificate valid now, within the default clock skew
+    /// (`DEFAULT_CLOCK_SKEW_SECONDS`)?
     pub fn is_valid(&self) -> bool {
-        let now = Utc::now();
-        let expires_at = self.issued_at + chrono::Duration::seconds(self.valid_seconds as i64);
-        now < expires_at
+        self.is_valid_with_clock(&SystemClock)
+    }
+
+    /// Is this certificate valid at `clock`'s time, within the default
+    /// clock skew?
+    pub fn is_valid_with_clock(&self, clock: &dyn Clock) -> bool {
+        self.is_valid_at(clock.now(), DEFAULT_CLOCK_SKEW_SECONDS)
+    }
+
+    /// Is this certificate valid at `now`, given clocks that may
+    /// disagree by up to `clock_skew_seconds`? The grace applies at
+    /// both ends: just past expiry on a fast clock, and just before
+    /// issuance on a slow one.
+    ///
+    /// Note that validity has a start as well as an end: a certificate
+    /// dated more than the skew after `now` cannot have been issued
+    /// yet and is rejected. (Before the skew grace, only expiry was
+    /// checked.) A skew too large to represent is unbounded.
+    pub fn is_valid_at(&self, now: DateTime<Utc>, clock_skew_seconds: u64) -> bool {
+        within_validity(self.issued_at, self.valid_seconds, now, clock_skew_seconds)
+    }
+
+    /// Check the Verifier signature and validity now, within the
+    /// default clock skew.
+    pub fn verify(&self) -> Result<()> {
+        self.verify_with_clock(&SystemClock)
+    }
+
+    /// Like `verify`, also rejecting a certificate whose Verifier key
+    /// was revoked before it was issued (see `RevocationList`).
+    pub fn verify_with_revocations(&self, revocations: &RevocationList) -> Result<()> {
+        self.verify_with_revocations_and_clock(revocations, &SystemClock)
+    }
+
+    /// Like `verify_with_revocations`, checking validity at `clock`'s
+    /// time.
+    pub fn verify_with_revocations_and_clock(
+        &self,
+        revocations: &RevocationList,
+        clock: &dyn Clock,
+    ) -> Result<()> {
+        self.verify_with_clock(clock)?;
+        revocations.check(self)
+    }
+
+    /// Check the Verifier signature and validity at `clock`'s time,
+    /// within the default clock skew.
+    pub fn verify_with_clock(&self, clock: &dyn Clock) -> Result<()> {
+        self.verify_at(clock.now(), DEFAULT_CLOCK_SKEW_SECONDS)
+    }
+
+    /// Check the Verifier signature and validity at `now`, within
+    /// `clock_skew_seconds`. A validity over `DEFAULT_MAX_VALID_SECONDS`
+    /// fails too.
+    pub fn verify_at(&self, now: DateTime<Utc>, clock_skew_seconds: u64) -> Result<()> {
+        self.verify_at_with_policy(now, clock_skew_seconds, &ValidityPolicy::default())
+    }
+
+    /// Like `verify_at`, accepting validities up to `policy`'s
+    /// `max_valid_seconds`.
+    pub fn verify_at_with_policy(
+        &self,
+        now: DateTime<Utc>,
+        clock_skew_seconds: u64,
+        policy: &ValidityPolicy,
+    ) -> Result<()> {
+        self.verify_signature()?;
+        policy.check(self.valid_seconds)?;
+        if !self.is_valid_at(now, clock_skew_seconds) {
+            return Err(TripError::CertificateError(format!(
+                "Certificate not valid at {now}: valid {} to {}",
+                self.issued_at, self.expires_at()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Expiry timestamp (issued_at + valid_seconds), saturating at
+    /// the latest representable time.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        expiry(self.issued_at, self.valid_seconds)
     }
 
     /// Is this an Active Verification certificate (has nonce)?
     pub fn is_active_verification(&self) -> bool {
         self.nonce.is_some()
     }
+
+    /// Changes from this certificate to a later one for the same
+    /// identity, for monitoring a verdict over time.
+    ///
+    /// Fails if the two name different `identity_key`s. Signatures are
+    /// not checked; verify both first if they come from outside.
+    pub fn diff(&self, later: &Self) -> Result<CertificateDiff> {
+        if !self.identity_key.eq_ignore_ascii_case(&later.identity_key) {
+            return Err(TripError::CertificateError(
+                "Cannot diff certificates for different identity_key".to_string()
+            ));
+        }
+        Ok(CertificateDiff {
+            trust_score: later.trust_score - self.trust_score,
+            confidence: later.confidence - self.confidence,
+            chain_length: later.chain_length as i64 - self.chain_length as i64,
+            alpha: later.alpha - self.alpha,
+            beta: later.beta - self.beta,
+            elapsed_seconds: (later.issued_at - self.issued_at).num_seconds(),
+            tier_before: self.trust_tier(),
+            tier_after: later.trust_tier(),
+        })
+    }
+}
+
+/// Wire encoding of a whole certificate, signature included.
+///
+/// Only the transport changes: the Verifier signature is always over
+/// `to_cbor_signable`, so a codec can't affect whether it verifies.
+pub trait CertificateCodec {
+    fn encode(&self, certificate: &PoHCertificate) -> Result<Vec<u8>>;
+    fn decode(&self, bytes: &[u8]) -> Result<PoHCertificate>;
+}
+
+/// Deterministic CBOR, fields 0-15 (`to_cbor` / `from_cbor`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+impl CertificateCodec for CborCodec {
+    fn encode(&self, certificate: &PoHCertificate) -> Result<Vec<u8>> {
+        certificate.to_cbor()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<PoHCertificate> {
+        PoHCertificate::from_cbor(bytes)
+    }
+}
+
+/// Pretty-printed JSON (`to_json`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl CertificateCodec for JsonCodec {
+    fn encode(&self, certificate: &PoHCertificate) -> Result<Vec<u8>> {
+        certificate.to_json().map(String::into_bytes)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<PoHCertificate> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| TripError::DeserializeError(format!("Certificate decode error: {e}")))
+    }
+}
+
+/// Largest |Δα| and |Δβ| between a certificate and a recomputation
+/// that `verify_certificate_against_chain` accepts: the certificate's
+/// own rounding (`DEFAULT_CERT_DECIMALS`) plus floating-point noise.
+pub const RECOMPUTATION_EXPONENT_TOLERANCE: f64 = 0.01;
+
+/// Largest |Δ trust score| (points) `verify_certificate_against_chain`
+/// accepts.
+pub const RECOMPUTATION_TRUST_TOLERANCE: f64 = 0.5;
+
+/// Check a certificate that arrived with its chain against a fresh run
+/// of the engine, catching a forged or edited verdict (say, a high
+/// trust tier on a chain that earns a low one).
+///
+/// The certificate must name the chain's identity, length and (if it
+/// has one) head hash; α, β and the trust score must then match the
+/// recomputation under `config` within the `RECOMPUTATION_*`
+/// tolerances, so the trust tier matches too except within the
+/// tolerance of a tier boundary. A certificate carrying a
+/// `config_digest` for another config is rejected up front, as its
+/// numbers are not expected to reproduce. The Verifier signature is
+/// not checked here (see `verify_signature`).
+pub fn verify_certificate_against_chain(
+    cert: &PoHCertificate,
+    chain: &BreadcrumbChain,
+    config: &CriticalityConfig,
+) -> Result<()> {
+    let mismatch = |what: String| Err(TripError::CertificateError(format!("Recomputation mismatch: {what}")));
+
+    if !cert.identity_key.eq_ignore_ascii_case(&chain.identity) {
+        return mismatch("certificate was issued to another identity".to_string());
+    }
+    if cert.chain_length != chain.len() as u64 {
+        return mismatch(format!("chain_length: certificate {}, chain {}", cert.chain_length, chain.len()));
+    }
+    if cert.chain_head_hash.as_deref().is_some_and(|hash| !hash.eq_ignore_ascii_case(chain.head_hash())) {
+        return mismatch("chain_head_hash does not match the chain".to_string());
+    }
+    if let Some(digest) = &cert.config_digest {
+        if *digest != config.digest()? {
+            return mismatch("certificate was issued under another config".to_string());
+        }
+    }
+
+    let result = CriticalityEngine::new(config.clone()).evaluate(chain)?;
+    let checks = [
+        ("alpha", cert.alpha, result.psd.alpha, RECOMPUTATION_EXPONENT_TOLERANCE),
+        ("beta", cert.beta, result.levy.beta, RECOMPUTATION_EXPONENT_TOLERANCE),
+        ("trust_score", cert.trust_score, result.trust_score, RECOMPUTATION_TRUST_TOLERANCE),
+    ];
+    for (field, claimed, recomputed, tolerance) in checks {
+        // Written so that a NaN claim fails
+        let consistent = (claimed - recomputed).abs() <= tolerance;
+        if !consistent {
+            return mismatch(format!(
+                "{field}: certificate {claimed}, recomputed {recomputed:.3} (tolerance {tolerance})"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Deterministic CBOR encoding (RFC 8949 §4.2) of `value`; see the
+/// rules in the header of this file. Tags and simple values other
+/// than booleans and null are not used by certificates and rejected.
+pub(crate) fn canonical_cbor(value: &ciborium::Value) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_canonical(value, &mut out)?;
+    Ok(out)
+}
+
+fn write_canonical(value: &ciborium::Value, out: &mut Vec<u8>) -> Result<()> {
+    use ciborium::Value;
+
+    match value {
+        Value::Integer(i) => {
+            let i = i128::from(*i);
+            if i >= 0 {
+                write_head(out, 0, i as u64);
+            } else {
+                write_head(out, 1, (-1 - i) as u64);
+            }
+        }
+        Value::Bytes(bytes) => {
+            write_head(out, 2, bytes.len() as u64);
+            out.extend_from_slice(bytes);
+        }
+        Value::Text(text) => {
+            write_head(out, 3, text.len() as u64);
+            out.extend_from_slice(text.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(out, 4, items.len() as u64);
+            for item in items {
+                write_canonical(item, out)?;
+            }
+        }
+        Value::Map(entries) => {
+            let mut encoded = Vec::with_capacity(entries.len());
+            for (key, value) in entries {
+                encoded.push((canonical_cbor(key)?, canonical_cbor(value)?));
+            }
+            encoded.sort_by(|a, b| a.0.cmp(&b.0));
+            if encoded.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err(TripError::CertificateError("CBOR encode error: duplicate map key".to_string()));
+            }
+            write_head(out, 5, encoded.len() as u64);
+            for (key, value) in encoded {
+                out.extend_from_slice(&key);
+                out.extend_from_slice(&value);
+            }
+        }
+        Value::Float(x) => write_float(out, *x),
+        Value::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        Value::Null => out.push(0xf6),
+        other => {
+            return Err(TripError::CertificateError(format!("CBOR encode error: unsupported value {other:?}")));
+        }
+    }
+    Ok(())
+}
+
+/// Major type and argument in the shortest form.
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    match n {
+        0..=23 => out.push(major | n as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, n as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(n as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+}
+
+/// Shortest float encoding that round-trips `x` exactly.
+fn write_float(out: &mut Vec<u8>, x: f64) {
+    if x.is_nan() {
+        out.extend_from_slice(&[0xf9, 0x7e, 0x00]);
+        return;
+    }
+    let single = x as f32;
+    if single as f64 != x || x.is_sign_negative() != single.is_sign_negative() {
+        out.push(0xfb);
+        out.extend_from_slice(&x.to_be_bytes());
+    } else if let Some(half) = f32_to_f16_exact(single) {
+        out.push(0xf9);
+        out.extend_from_slice(&half.to_be_bytes());
+    } else {
+        out.push(0xfa);
+        out.extend_from_slice(&single.to_be_bytes());
+    }
+}
+
+/// IEEE half-precision bits of `x`, if `x` is exactly representable.
+fn f32_to_f16_exact(x: f32) -> Option<u16> {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    match exponent {
+        // ±0 (f32 subnormals are far below the f16 range)
+        0 => (mantissa == 0).then_some(sign),
+        // ±Inf (NaN is handled by the caller)
+        0xff => Some(sign | 0x7c00),
+        _ => {
+            let e = exponent - 127;
+            if (-14..=15).contains(&e) {
+                // Normal: the 13 mantissa bits f16 lacks must be zero
+                (mantissa & 0x1fff == 0)
+                    .then(|| sign | (((e + 15) as u16) << 10) | (mantissa >> 13) as u16)
+            } else if (-24..-14).contains(&e) {
+                // Subnormal: significand × 2^(e+1) units of 2^-24
+                let significand = mantissa | 0x80_0000;
+                let shift = -(e + 1) as u32;
+                (significand & ((1 << shift) - 1) == 0)
+                    .then(|| sign | (significand >> shift) as u16)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// `issued_at` + `valid_seconds`, saturating at the latest
+/// representable time.
+pub(crate) fn expiry(issued_at: DateTime<Utc>, valid_seconds: u64) -> DateTime<Utc> {
+    i64::try_from(valid_seconds).ok()
+        .and_then(chrono::Duration::try_seconds)
+        .and_then(|valid| issued_at.checked_add_signed(valid))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// Is `now` within [issued_at, expiry), widened by
+/// `clock_skew_seconds` on both sides?
+pub(crate) fn within_validity(
+    issued_at: DateTime<Utc>,
+    valid_seconds: u64,
+    now: DateTime<Utc>,
+    clock_skew_seconds: u64,
+) -> bool {
+    let skew = i64::try_from(clock_skew_seconds).ok()
+        .and_then(chrono::Duration::try_seconds)
+        .unwrap_or(chrono::Duration::MAX);
+    let not_before = issued_at.checked_sub_signed(skew).unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let not_after = expiry(issued_at, valid_seconds).checked_add_signed(skew).unwrap_or(DateTime::<Utc>::MAX_UTC);
+    now >= not_before && now < not_after
+}
+
+/// Decode a hex key, signature or hash field into exactly `N` bytes
+/// (certificates, breadcrumbs and other signed records), naming the
+/// field and what is wrong with it: empty (`EmptyHex`, usually an unset
+/// value), or `InvalidHex` for an odd length, the first non-hex
+/// character (e.g. stray whitespace) or the wrong decoded length.
+/// Neither is specific to certificates, whatever record the field is in.
+pub(crate) fn decode_hex_field<const N: usize>(hex_str: &str, field: &str) -> Result<[u8; N]> {
+    if hex_str.trim().is_empty() {
+        return Err(TripError::EmptyHex(field.to_string()));
+    }
+    let bytes = hex::decode(hex_str).map_err(|e| TripError::InvalidHex(match e {
+        hex::FromHexError::OddLength => {
+            format!("{field}: odd hex length ({} characters)", hex_str.len())
+        }
+        hex::FromHexError::InvalidHexCharacter { c, index } => {
+            format!("{field}: non-hex character {c:?} at position {index}")
+        }
+        other => format!("{field}: {other}"),
+    }))?;
+
+    let got = bytes.len();
+    bytes.try_into().map_err(|_| TripError::InvalidHex(
+        format!("{field}: wrong decoded length, expected {N} bytes, got {got}")
+    ))
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    pub(crate) fn test_cert(trust_score: f64) -> PoHCertificate {
+        PoHCertificate {
+            identity_key: "a".repeat(64),
+            alpha: 0.55,
+            beta: 1.0,
+            kappa: 50.0,
+            trust_score,
+            confidence: 0.85,
+            chain_length: 300,
+            unique_cells: 42,
+            mean_hamiltonian: 0.15,
+            verifier_key: "b".repeat(64),
+            issued_at: Utc::now(),
+            valid_seconds: 3600,
+            nonce: None,
+            chain_head_hash: Some("c".repeat(64)),
+            verifier_signature: None,
+            config_digest: None,
+        }
+    }
+
+    /// RFC 8949 Appendix A examples
+    #[test]
+    fn test_canonical_cbor_vectors() {
+        use ciborium::Value;
+
+        let hex_of = |v: Value| hex::encode(canonical_cbor(&v).unwrap());
+        let ints: [(i64, &str); 9] = [
+            (0, "00"), (23, "17"), (24, "1818"), (100, "1864"), (1000, "1903e8"),
+            (1_000_000, "1a000f4240"), (1_000_000_000_000, "1b000000e8d4a51000"),
+            (-1, "20"), (-1000, "3903e7"),
+        ];
+        for (i, expected) in ints {
+            assert_eq!(hex_of(Value::Integer(i.into())), expected, "{i}");
+        }
+        let floats: [(f64, &str); 15] = [
+            (0.0, "f90000"), (-0.0, "f98000"), (1.0, "f93c00"), (1.1, "fb3ff199999999999a"),
+            (1.5, "f93e00"), (65504.0, "f97bff"), (100000.0, "fa47c35000"),
+            (3.4028234663852886e+38, "fa7f7fffff"), (1.0e+300, "fb7e37e43c8800759c"),
+            (5.960464477539063e-8, "f90001"), (0.00006103515625, "f90400"), (-4.0, "f9c400"),
+            (-4.1, "fbc010666666666666"), (f64::INFINITY, "f97c00"), (f64::NAN, "f97e00"),
+        ];
+        for (x, expected) in floats {
+            assert_eq!(hex_of(Value::Float(x)), expected, "{x}");
+        }
+
+        // Keys sorted by their encoding regardless of insertion order:
+        // 10 (0x0a) < 100 (0x1864) < -1 (0x20) < "z" (0x617a)
+        let map = Value::Map(vec![
+            (Value::Text("z".into()), Value::Integer(1.into())),
+            (Value::Integer((-1).into()), Value::Integer(2.into())),
+            (Value::Integer(100.into()), Value::Integer(3.into())),
+            (Value::Integer(10.into()), Value::Integer(4.into())),
+        ]);
+        assert_eq!(hex_of(map), "a40a041864032002617a01");
+
+        let duplicate = Value::Map(vec![
+            (Value::Integer(1.into()), Value::Null),
+            (Value::Integer(1.into()), Value::Bool(true)),
+        ]);
+        assert!(canonical_cbor(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_signable_cbor_vector() {
+        let mut cert = test_cert(62.0);
+        cert.identity_key = "11".repeat(32);
+        cert.verifier_key = "22".repeat(32);
+        cert.chain_head_hash = None;
+        cert.issued_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let expected = [
+            "ac",                                   // map(12)
+            "00", "5820", &"11".repeat(32),         // 0: identity_key
+            "01", "fb3fe199999999999a",             // 1: alpha 0.55
+            "02", "f93c00",                         // 2: beta 1.0
+            "03", "f95240",                         // 3: kappa 50.0
+            "04", "19f230",                         // 4: trust_score 62.000
+            "05", "fb3feb333333333333",             // 5: confidence 0.85
+            "06", "19012c",                         // 6: chain_length 300
+            "07", "182a",                           // 7: unique_cells 42
+            "08", "fb3fc3333333333333",             // 8: mean_hamiltonian 0.15
+            "09", "5820", &"22".repeat(32),         // 9: verifier_key
+            "0a", "1b0000018bcfe56800",             // 10: issued_at (ms)
+            "0b", "190e10",                         // 11: valid_seconds 3600
+        ].concat();
+        assert_eq!(hex::encode(cert.to_cbor_signable().unwrap()), expected);
+    }
+
+    #[test]
+    fn test_full_cbor_round_trip() {
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let mut cert = test_cert(75.125)
+            .with_nonce(vec![9u8; 16])
+            .with_config_digest("d".repeat(64));
+        cert.issued_at = DateTime::from_timestamp_millis(Utc::now().timestamp() * 1000 + 123).unwrap();
+        cert.verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+
+        // Unsigned: nothing beyond the signable fields
+        assert_eq!(cert.to_cbor().unwrap(), cert.to_cbor_signable().unwrap());
+
+        let signed = cert.sign(&verifier).unwrap();
+        let bytes = signed.to_cbor().unwrap();
+        // Key 14, a two-byte bstr head and the 64-byte signature
+        assert_eq!(bytes.len(), signed.to_cbor_signable().unwrap().len() + 1 + 2 + 64);
+        let decoded = PoHCertificate::from_cbor(&bytes).unwrap();
+        assert_eq!(decoded.verifier_signature, signed.verifier_signature);
+        // Lossless: fractional score and sub-second issuance survive
+        assert_eq!(decoded.trust_score, 75.125);
+        assert_eq!(decoded.issued_at, signed.issued_at);
+        assert_eq!(decoded.to_cbor().unwrap(), bytes);
+        decoded.verify().unwrap();
+
+        // A tampered field no longer verifies after decoding
+        let mut tampered = PoHCertificate::from_cbor(&bytes).unwrap();
+        tampered.unique_cells += 1;
+        let tampered = PoHCertificate::from_cbor(&tampered.to_cbor().unwrap()).unwrap();
+        assert!(tampered.verify().is_err());
+
+        // Not a certificate
+        let decode_error = |bytes: &[u8]| matches!(
+            PoHCertificate::from_cbor(bytes), Err(TripError::DeserializeError(_))
+        );
+        assert!(decode_error(&[0xff]));
+        assert!(decode_error(&bytes[..bytes.len() - 1]));
+        assert!(decode_error(&canonical_cbor(&ciborium::Value::Map(vec![])).unwrap()));
+    }
+
+    #[test]
+    fn test_certificate_diff() {
+        let earlier = test_cert(35.0);
+        let mut later = test_cert(62.5);
+        later.alpha = 0.6;
+        later.beta = 0.95;
+        later.confidence = 0.9;
+        later.chain_length = 420;
+        later.issued_at = earlier.issued_at + chrono::Duration::days(7);
+
+        let diff = earlier.diff(&later).unwrap();
+        assert_eq!(diff.trust_score, 27.5);
+        assert_eq!(diff.chain_length, 120);
+        assert!((diff.alpha - 0.05).abs() < 1e-12);
+        assert!((diff.beta + 0.05).abs() < 1e-12);
+        assert!((diff.confidence - 0.05).abs() < 1e-12);
+        assert_eq!(diff.elapsed_seconds, 7 * 86400);
+        assert_eq!((diff.tier_before, diff.tier_after), (TrustTier::Untrusted, TrustTier::Moderate));
+        assert!(diff.human_flipped());
+
+        // Reversed, every delta changes sign and the flip is still seen
+        let back = later.diff(&earlier).unwrap();
+        assert_eq!(back.chain_length, -120);
+        assert!(back.human_flipped());
+        assert!(!earlier.diff(&earlier).unwrap().human_flipped());
+
+        let mut stranger = test_cert(80.0);
+        stranger.identity_key = "d".repeat(64);
+        assert!(matches!(earlier.diff(&stranger), Err(TripError::CertificateError(_))));
+    }
+
+    #[test]
+    fn test_unique_cells_from_chain() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+        use crate::criticality::CriticalityEngine;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 7)).unwrap();
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        let mut cert = PoHCertificate::from_criticality_result(
+            &result,
+            chain.identity.clone(),
+            "b".repeat(64),
+            chain.head_hash().to_string(),
+            3600,
+        );
+
+        assert_eq!(cert.unique_cells, chain.unique_cells() as u64);
+        assert!(cert.verify_unique_cells(&chain).is_ok());
+
+        cert.unique_cells += 1;
+        assert!(cert.verify_unique_cells(&chain).is_err());
+    }
+
+    #[test]
+    fn test_certificate_against_chain() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let config = CriticalityConfig::default();
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 7)).unwrap();
+        let result = CriticalityEngine::new(config.clone()).evaluate(&chain).unwrap();
+        let cert = PoHCertificate::from_criticality_result(
+            &result,
+            chain.identity.clone(),
+            "b".repeat(64),
+            chain.head_hash().to_string(),
+            3600,
+        ).with_config_digest(config.digest().unwrap());
+        assert!(verify_certificate_against_chain(&cert, &chain, &config).is_ok());
+
+        // Hex case is not significant
+        let mut upper = cert.clone();
+        upper.identity_key = upper.identity_key.to_ascii_uppercase();
+        upper.chain_head_hash = upper.chain_head_hash.map(|hash| hash.to_ascii_uppercase());
+        assert!(verify_certificate_against_chain(&upper, &chain, &config).is_ok());
+
+        // A forged top-tier certificate for the same chain
+        let mut forged = cert.clone();
+        forged.trust_score = 95.0;
+        assert_eq!(forged.trust_tier(), TrustTier::Excellent);
+        match verify_certificate_against_chain(&forged, &chain, &config) {
+            Err(TripError::CertificateError(msg)) => assert!(msg.contains("trust_score"), "{msg}"),
+            other => panic!("expected a recomputation mismatch, got {other:?}"),
+        }
+
+        let mut forged = cert.clone();
+        forged.alpha = 0.55;
+        assert!(verify_certificate_against_chain(&forged, &chain, &config).is_err());
+
+        let other_config = CriticalityConfig { alpha_max: 0.85, ..Default::default() };
+        assert!(verify_certificate_against_chain(&cert, &chain, &other_config).is_err());
+    }
+
+    #[test]
+    fn test_rounded_floats_still_verify() {
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let mut cert = test_cert(78.4567);
+        cert.alpha = 0.551_234_567;
+        cert.kappa = 42.123_456;
+        cert.verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+
+        let cert = cert.with_decimals(DEFAULT_CERT_DECIMALS).sign(&verifier).unwrap();
+        let json = cert.to_json().unwrap();
+        assert!(json.contains("\"alpha\": 0.551,"), "{json}");
+        assert!(json.contains("\"kappa\": 42.123,"), "{json}");
+        assert!(json.contains("\"trust_score\": 78.457,"), "{json}");
+
+        // A Relying Party parsing the JSON recovers the signed values
+        let parsed: PoHCertificate = serde_json::from_str(&json).unwrap();
+        parsed.verify_signature().unwrap();
+
+        let two = parsed.clone().with_decimals(2);
+        assert!(two.to_json().unwrap().contains("\"alpha\": 0.55,"));
+        assert!(two.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_rounding_keeps_trust_tier() {
+        for (score, expected) in [(39.9996, 39.999), (59.99951, 59.999), (89.9999, 89.999), (40.0004, 40.0)] {
+            let before = test_cert(score).trust_tier();
+            let cert = test_cert(score).with_decimals(DEFAULT_CERT_DECIMALS);
+            assert_eq!(cert.trust_tier(), before, "score {score}");
+            assert_eq!(cert.trust_score, expected);
+        }
+        // Below the boundary rounding still rounds
+        assert_eq!(test_cert(39.9984).with_decimals(DEFAULT_CERT_DECIMALS).trust_score, 39.998);
+
+        // Out-of-range precision neither overflows nor loses values,
+        // except the trust score, which CBOR carries in milli-points
+        let mut cert = test_cert(78.4567);
+        cert.kappa = 42.123_456;
+        let cert = cert.with_decimals(u32::MAX);
+        assert_eq!(cert.trust_score, 78.457);
+        assert_eq!(cert.kappa, 42.123_456);
+    }
+
+    #[test]
+    fn test_malformed_hex_fields() {
+        let error = |cert: PoHCertificate| match cert.to_cbor_signable() {
+            Err(TripError::InvalidHex(msg)) => msg,
+            other => panic!("expected invalid hex, got {other:?}"),
+        };
+
+        let mut cert = test_cert(70.0);
+        cert.identity_key.clear();
+        assert!(matches!(cert.to_cbor_signable(), Err(TripError::EmptyHex(field)) if field == "identity_key"));
+
+        let mut cert = test_cert(70.0);
+        cert.verifier_key = " \t".to_string();
+        assert!(matches!(cert.to_cbor_signable(), Err(TripError::EmptyHex(field)) if field == "verifier_key"));
+
+        let mut cert = test_cert(70.0);
+        cert.identity_key.pop();
+        assert_eq!(error(cert), "identity_key: odd hex length (63 characters)");
+
+        let mut cert = test_cert(70.0);
+        cert.verifier_key.replace_range(10..11, " ");
+        assert_eq!(error(cert), "verifier_key: non-hex character ' ' at position 10");
+
+        let mut cert = test_cert(70.0);
+        cert.chain_head_hash = Some("c".repeat(62));
+        assert_eq!(error(cert), "chain_head_hash: wrong decoded length, expected 32 bytes, got 31");
+
+        let mut cert = test_cert(70.0);
+        cert.verifier_signature = Some("00".repeat(63));
+        match cert.verify_signature() {
+            Err(TripError::InvalidHex(msg)) => {
+                assert_eq!(msg, "verifier_signature: wrong decoded length, expected 64 bytes, got 63")
+            }
+            other => panic!("expected invalid hex, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_config_digest_in_signable_cbor() {
+        let cert = test_cert(70.0);
+        let digest = crate::criticality::CriticalityConfig::default().digest().unwrap();
+        let with_digest = cert.clone().with_config_digest(digest.clone());
+
+        assert_eq!(with_digest.config_digest.as_deref(), Some(digest.as_str()));
+        assert_ne!(with_digest.to_cbor_signable().unwrap(), cert.to_cbor_signable().unwrap());
+        assert_ne!(with_digest.fingerprint().unwrap(), cert.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn test_validity_policy_by_tier() {
+        let policy = ValidityPolicy::default();
+
+        let excellent = test_cert(95.0).with_validity_policy(&policy);
+        let borderline = test_cert(45.0).with_validity_policy(&policy);
+
+        assert_eq!(excellent.trust_tier(), TrustTier::Excellent);
+        assert_eq!(borderline.trust_tier(), TrustTier::Borderline);
+        assert_eq!(excellent.valid_seconds, 86_400);
+        assert_eq!(borderline.valid_seconds, 3_600);
+        assert!(excellent.expires_at() > borderline.expires_at());
+
+        let fixed = test_cert(95.0).with_validity_policy(&ValidityPolicy::fixed(600));
+        assert_eq!(fixed.valid_seconds, 600);
+    }
+
+    #[test]
+    fn test_validity_capped() {
+        use crate::clock::FixedClock;
+
+        // Ten years, as asked for by a misconfigured caller
+        let ten_years = 10 * 365 * 86_400;
+        let result = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&[0.5; 100], &[900.0; 100])
+            .unwrap();
+        let cert = PoHCertificate::from_criticality_result_with_clock(
+            &result,
+            "a".repeat(64),
+            "b".repeat(64),
+            "c".repeat(64),
+            ten_years,
+            &FixedClock::new(Utc::now()),
+        );
+        assert_eq!(cert.valid_seconds, DEFAULT_MAX_VALID_SECONDS);
+        assert_eq!(cert.expires_at() - cert.issued_at, chrono::Duration::days(7));
+
+        let fixed = test_cert(95.0).with_validity_policy(&ValidityPolicy::fixed(ten_years));
+        assert_eq!(fixed.valid_seconds, DEFAULT_MAX_VALID_SECONDS);
+
+        // A deployment that really wants longer says so
+        let policy = ValidityPolicy { max_valid_seconds: 30 * 86_400, ..ValidityPolicy::fixed(ten_years) };
+        assert_eq!(test_cert(95.0).with_validity_policy(&policy).valid_seconds, 30 * 86_400);
+        assert_eq!(test_cert(95.0).with_valid_seconds(ten_years, &policy).valid_seconds, 30 * 86_400);
+        assert_eq!(policy.cap(3_600), 3_600);
+
+        // However the field got there, an over-cap validity is never signed
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let mut cert = test_cert(95.0);
+        cert.verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+        cert.valid_seconds = ten_years;
+        let over_cap = |r: Result<PoHCertificate>| matches!(
+            r, Err(TripError::CertificateError(msg)) if msg.contains("exceeds")
+        );
+        assert!(over_cap(cert.clone().sign(&verifier)));
+        assert!(over_cap(cert.clone().sign_with_policy(&verifier, &policy)));
+
+        // Nor accepted beyond the Relying Party's own cap
+        cert.valid_seconds = 30 * 86_400;
+        assert!(over_cap(cert.clone().sign(&verifier)));
+        let cert = cert.sign_with_policy(&verifier, &policy).unwrap();
+        let now = cert.issued_at;
+        assert!(matches!(
+            cert.verify_at(now, DEFAULT_CLOCK_SKEW_SECONDS),
+            Err(TripError::CertificateError(msg)) if msg.contains("exceeds")
+        ));
+        assert!(cert.verify().is_err());
+        cert.verify_at_with_policy(now, DEFAULT_CLOCK_SKEW_SECONDS, &policy).unwrap();
+    }
+
     #[test]
     fn test_certificate_validity() {
         let cert = PoHCertificate {
@@ -240,9 +1457,122 @@ mod tests {
             nonce: Some(vec![0u8; 16]),
             chain_head_hash: Some("c".repeat(64)),
             verifier_signature: None,
+            config_digest: None,
         };
 
         assert!(cert.is_valid());
         assert!(cert.is_active_verification());
     }
+
+    #[test]
+    fn test_validity_clock_skew_grace() {
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let mut cert = test_cert(75.0);
+        cert.verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+        let cert = cert.sign(&verifier).unwrap();
+        let expiry = cert.expires_at();
+        let secs = chrono::Duration::seconds;
+
+        // 10 s past expiry on a fast clock
+        assert!(!cert.is_valid_at(expiry + secs(10), 0));
+        assert!(cert.is_valid_at(expiry + secs(10), DEFAULT_CLOCK_SKEW_SECONDS));
+        assert!(!cert.is_valid_at(expiry + secs(30), DEFAULT_CLOCK_SKEW_SECONDS));
+        assert!(cert.verify_at(expiry + secs(10), DEFAULT_CLOCK_SKEW_SECONDS).is_ok());
+        assert!(matches!(
+            cert.verify_at(expiry + secs(10), 0),
+            Err(TripError::CertificateError(_))
+        ));
+
+        // 5 s before issuance on a slow clock
+        assert!(!cert.is_valid_at(cert.issued_at - secs(5), 0));
+        assert!(cert.is_valid_at(cert.issued_at - secs(5), DEFAULT_CLOCK_SKEW_SECONDS));
+        cert.verify().unwrap();
+
+        // Dated in the future beyond the skew: not issued yet
+        assert!(!cert.is_valid_at(cert.issued_at - secs(60), DEFAULT_CLOCK_SKEW_SECONDS));
+
+        // Extreme skews and validities saturate instead of panicking
+        assert!(cert.is_valid_at(expiry + secs(86_400), u64::MAX));
+        assert!(cert.is_valid_at(DateTime::<Utc>::MIN_UTC, u64::MAX));
+        let mut forever = cert.clone();
+        forever.valid_seconds = u64::MAX;
+        assert_eq!(forever.expires_at(), DateTime::<Utc>::MAX_UTC);
+        assert!(forever.is_valid_at(expiry + secs(86_400 * 365), 0));
+
+        // The grace doesn't excuse a bad signature
+        let mut forged = cert.clone();
+        forged.trust_score = 99.0;
+        assert!(forged.verify_at(cert.issued_at, DEFAULT_CLOCK_SKEW_SECONDS).is_err());
+    }
+
+    #[test]
+    fn test_validity_follows_clock() {
+        use chrono::TimeZone;
+        use crate::chain::tests::random_walk_breadcrumbs;
+        use crate::clock::FixedClock;
+
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let issued = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let clock = FixedClock::new(issued);
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 7)).unwrap();
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        let cert = PoHCertificate::from_criticality_result_with_clock(
+            &result,
+            chain.identity.clone(),
+            hex::encode(verifier.verifying_key().to_bytes()),
+            "c".repeat(64),
+            3600,
+            &clock,
+        ).sign(&verifier).unwrap();
+        assert_eq!(cert.issued_at, issued);
+
+        // Valid up to the last second of the skew grace, then not
+        let grace = chrono::Duration::seconds(DEFAULT_CLOCK_SKEW_SECONDS as i64 - 1);
+        clock.set(cert.expires_at() + grace);
+        assert!(cert.is_valid_with_clock(&clock));
+        cert.verify_with_clock(&clock).unwrap();
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(!cert.is_valid_with_clock(&clock));
+        assert!(matches!(cert.verify_with_clock(&clock), Err(TripError::CertificateError(_))));
+    }
+
+    #[test]
+    fn test_custom_codec_round_trip() {
+        /// A codec of the integrator's own: hex text over CBOR
+        struct HexCodec;
+
+        impl CertificateCodec for HexCodec {
+            fn encode(&self, certificate: &PoHCertificate) -> Result<Vec<u8>> {
+                Ok(hex::encode(certificate.to_cbor()?).into_bytes())
+            }
+
+            fn decode(&self, bytes: &[u8]) -> Result<PoHCertificate> {
+                let cbor = hex::decode(bytes)
+                    .map_err(|e| TripError::DeserializeError(format!("Certificate decode error: {e}")))?;
+                PoHCertificate::from_cbor(&cbor)
+            }
+        }
+
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let mut cert = test_cert(78.125)
+            .with_nonce(vec![9u8; 16])
+            .with_config_digest("d".repeat(64));
+        cert.issued_at = DateTime::from_timestamp_millis(Utc::now().timestamp() * 1000 + 123).unwrap();
+        cert.verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+        let cert = cert.sign(&verifier).unwrap();
+
+        // Every codec carries every field unchanged, not just the signed bytes
+        let codecs: [&dyn CertificateCodec; 3] = [&CborCodec, &JsonCodec, &HexCodec];
+        for codec in codecs {
+            let bytes = cert.encode_with(codec).unwrap();
+            let decoded = PoHCertificate::decode_with(codec, &bytes).unwrap();
+            assert_eq!(decoded, cert);
+            decoded.verify_signature().unwrap();
+        }
+        assert_eq!(cert.encode_with(&CborCodec).unwrap(), cert.to_cbor().unwrap());
+        assert!(matches!(
+            PoHCertificate::decode_with(&HexCodec, b"not hex"),
+            Err(TripError::DeserializeError(_))
+        ));
+    }
 }
@@ -3,14 +3,380 @@
 // BreadcrumbChain: an ordered, verified sequence of breadcrumbs
 // from a single identity. Chain verification ensures:
 // 1. Hash chaining (each block references the previous)
-// 2. Monotonic timestamps
+// 2. Monotonic timestamps, in the same order as the indices
 // 3. Index ordering
 // 4. Ed25519 signature validity
+//
+// Lenient mode tolerates a bounded number of missing indices (lost in
+// sync), as long as the breadcrumb after each gap links back across it
+// with a signed `skip_hash`. Tolerated gaps are reported, not hidden.
+//
+// A chain can be exported as an anonymized `TestVector` for bug
+// reports: same movement and timing structure, different identity
+// and place on the globe.
+//
+// `compressibility_ratio` is a cheap randomness heuristic next to the
+// statistical tests: scripted chains are repetitive and compress well.
+// `effective_sample_size` discounts the chain length for autocorrelated
+// displacements, for the Criticality Engine's confidence.
+// `context_report` flags a sensor context that never changes.
+// `mobility_signature` hashes the movement alone, to spot one
+// trajectory submitted under several identities.
+// `spatial_clusters` finds the few places (home, work, ...) a person's
+// movement is organized around.
 
-use crate::breadcrumb::{Breadcrumb, Displacement, compute_displacements};
+use std::collections::HashMap;
+use std::io::Write;
+use chrono::{DateTime, Utc};
+use flate2::{Compression, write::DeflateEncoder};
+use nalgebra::{Rotation3, Vector3};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::breadcrumb::{
+    Breadcrumb, Displacement, DistanceMode, compute_displacements, h3_cell_to_latlon, haversine_km,
+    parse_cell,
+};
 use crate::error::{TripError, Result};
-use sha2::{Sha256, Digest};
-use serde_json;
+
+/// `compressibility_ratio` above which a chain is suspiciously
+/// repetitive. Organic chains of 100–1000 breadcrumbs land around
+/// 2; a fixed loop on a fixed clock compresses well past 10.
+pub const HIGH_COMPRESSIBILITY_RATIO: f64 = 4.0;
+
+/// Effective number of independent samples in `series`,
+/// N / τ, clamped to [1, N].
+///
+/// τ = 1 + 2 Σ ρ(k) is the integrated autocorrelation time, with ρ(k)
+/// the lag-k sample autocorrelation (normalized by the lag-0
+/// autocovariance, biased 1/N estimator). The sum is truncated with
+/// Geyer's initial positive sequence: lags are taken in pairs
+/// ρ(2m) + ρ(2m+1), stopping at the first pair that is not positive,
+/// since beyond that point the estimates are noise. Anti-correlated
+/// series (τ < 1) are credited no more than N samples. A constant
+/// series has no measurable correlation and counts as N.
+pub fn effective_sample_size(series: &[f64]) -> f64 {
+    let n = series.len();
+    if n < 2 {
+        return n as f64;
+    }
+    let mean = series.iter().sum::<f64>() / n as f64;
+    let centered: Vec<f64> = series.iter().map(|x| x - mean).collect();
+    let autocovariance = |lag: usize| {
+        centered.iter().zip(&centered[lag..]).map(|(a, b)| a * b).sum::<f64>() / n as f64
+    };
+    let variance = autocovariance(0);
+    if variance.is_nan() || variance <= 0.0 {
+        return n as f64;
+    }
+
+    let mut tau = -1.0;
+    let mut lag = 0;
+    while lag + 1 < n / 2 {
+        let pair = (autocovariance(lag) + autocovariance(lag + 1)) / variance;
+        if pair.is_nan() || pair <= 0.0 {
+            break;
+        }
+        tau += 2.0 * pair;
+        lag += 2;
+    }
+    (n as f64 / tau.max(1.0)).clamp(1.0, n as f64)
+}
+
+/// `effective_sample_size` of a displacement series, in breadcrumbs:
+/// the chain it came from is one longer than the series.
+pub(crate) fn effective_chain_length(displacements: &[f64]) -> f64 {
+    let n = displacements.len() + 1;
+    let ratio = effective_sample_size(displacements) / displacements.len().max(1) as f64;
+    (n as f64 * ratio).clamp(1.0, n as f64)
+}
+
+/// Minimum expected interval between breadcrumbs (seconds), per spec.
+pub const MIN_BREADCRUMB_INTERVAL_SECS: u64 = 600; // 10 minutes
+
+/// Maximum expected interval between breadcrumbs (seconds), per spec.
+pub const MAX_BREADCRUMB_INTERVAL_SECS: u64 = 86400; // 24 hours
+
+/// Summary of a chain's sampling cadence against the spec range.
+#[derive(Debug, Clone)]
+pub struct CadenceReport {
+    /// Median interval between consecutive breadcrumbs (seconds)
+    pub median_interval_seconds: f64,
+    /// Intervals shorter than MIN_BREADCRUMB_INTERVAL_SECS
+    pub too_short: usize,
+    /// Intervals longer than MAX_BREADCRUMB_INTERVAL_SECS
+    pub too_long: usize,
+    /// Total number of intervals
+    pub total: usize,
+}
+
+impl CadenceReport {
+    /// Does the median interval fall within the spec range?
+    pub fn cadence_in_spec(&self) -> bool {
+        self.median_interval_seconds >= MIN_BREADCRUMB_INTERVAL_SECS as f64
+            && self.median_interval_seconds <= MAX_BREADCRUMB_INTERVAL_SECS as f64
+    }
+
+    /// A few out-of-range intervals in an otherwise in-spec chain.
+    pub fn has_isolated_outliers(&self) -> bool {
+        self.cadence_in_spec() && self.too_short + self.too_long > 0
+    }
+}
+
+/// Intervals at least this long (seconds) count as long dwells.
+pub const LONG_DWELL_SECS: u64 = 6 * 3600;
+
+/// Share of a chain's intervals that may be stationary long dwells
+/// before it is flagged as low-liveness.
+pub const MAX_STATIONARY_DWELL_SHARE: f64 = 0.5;
+
+/// How much of a chain is spent sitting still for hours at a time.
+///
+/// A person sleeps in one cell every night, so some long stationary
+/// intervals are normal. A phone left on a charger produces little
+/// else: breadcrumb after breadcrumb, hours apart, in the same cell.
+#[derive(Debug, Clone)]
+pub struct DwellReport {
+    /// Intervals of at least LONG_DWELL_SECS
+    pub long_intervals: usize,
+    /// Long intervals with no cell change
+    pub stationary: usize,
+    /// Total number of intervals
+    pub total: usize,
+}
+
+impl DwellReport {
+    /// Summarize long stationary dwells from a displacement series and
+    /// its intervals; a zero displacement counts as no cell change.
+    pub fn from_series(displacement_km: &[f64], interval_seconds: &[f64]) -> Self {
+        let long: Vec<f64> = displacement_km.iter().zip(interval_seconds)
+            .filter(|&(_, &dt)| dt >= LONG_DWELL_SECS as f64)
+            .map(|(&d, _)| d)
+            .collect();
+
+        Self {
+            long_intervals: long.len(),
+            stationary: long.iter().filter(|&&d| d == 0.0).count(),
+            total: displacement_km.len(),
+        }
+    }
+
+    /// Fraction of long intervals with no cell change [0, 1]
+    pub fn dwell_ratio(&self) -> f64 {
+        if self.long_intervals == 0 {
+            return 0.0;
+        }
+        self.stationary as f64 / self.long_intervals as f64
+    }
+
+    /// Stationary long dwells as a fraction of all intervals [0, 1]
+    pub fn stationary_share(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.stationary as f64 / self.total as f64
+    }
+
+    /// Is the chain dominated by stationary long dwells?
+    pub fn is_low_liveness(&self) -> bool {
+        self.stationary_share() > MAX_STATIONARY_DWELL_SHARE
+    }
+}
+
+/// Share of consecutive breadcrumbs with an unchanged context digest
+/// above which a chain is flagged as low-liveness.
+pub const MAX_CONTEXT_REPEAT_SHARE: f64 = 0.5;
+
+/// Distinct context digests per breadcrumb below which a chain is
+/// flagged as low-liveness.
+pub const MIN_CONTEXT_UNIQUENESS: f64 = 0.25;
+
+/// How often the sensor context behind a chain actually changes.
+///
+/// A real device's context (IMU, Wi-Fi, cellular) drifts constantly,
+/// so its digests are almost all distinct; a still night can repeat a
+/// few. A bot replaying one captured sensor context repeats the same
+/// digest breadcrumb after breadcrumb. The contextual Hamiltonian
+/// catches a single move without a context change; this looks at the
+/// chain as a whole.
+#[derive(Debug, Clone)]
+pub struct ContextReport {
+    /// Breadcrumbs whose digest equals the previous one's
+    pub repeated: usize,
+    /// Distinct digests in the chain
+    pub unique: usize,
+    /// Total number of breadcrumbs
+    pub total: usize,
+}
+
+impl ContextReport {
+    /// Consecutive repeats as a fraction of consecutive pairs [0, 1]
+    pub fn repeat_share(&self) -> f64 {
+        if self.total < 2 {
+            return 0.0;
+        }
+        self.repeated as f64 / (self.total - 1) as f64
+    }
+
+    /// Distinct digests as a fraction of breadcrumbs [0, 1]
+    pub fn uniqueness_ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.unique as f64 / self.total as f64
+    }
+
+    /// Is the context implausibly repetitive for a live device?
+    pub fn is_low_liveness(&self) -> bool {
+        self.repeat_share() > MAX_CONTEXT_REPEAT_SHARE
+            || self.uniqueness_ratio() < MIN_CONTEXT_UNIQUENESS
+    }
+}
+
+/// A dense group of visited places found by `spatial_clusters`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    /// Mean position of the cluster's breadcrumbs (degrees)
+    pub centroid: (f64, f64),
+    /// Breadcrumbs in the cluster
+    pub size: usize,
+    /// Distinct cells in the cluster
+    pub cells: usize,
+}
+
+/// Share of a chain's breadcrumbs outside every cluster [0, 1].
+pub fn noise_fraction(clusters: &[Cluster], chain_length: usize) -> f64 {
+    if chain_length == 0 {
+        return 0.0;
+    }
+    let clustered: usize = clusters.iter().map(|c| c.size).sum();
+    1.0 - clustered as f64 / chain_length as f64
+}
+
+/// How many of a chain's fixes report a poor GPS accuracy.
+///
+/// `MetaFlags::accuracy` is the fix's reported error radius in meters.
+/// A fix without one is counted as unknown, not poor: older Attesters
+/// never recorded it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AccuracyReport {
+    /// Breadcrumbs reporting an accuracy worse than the threshold
+    pub poor: usize,
+    /// Breadcrumbs reporting no accuracy
+    pub unknown: usize,
+    /// Total number of breadcrumbs
+    pub total: usize,
+}
+
+impl AccuracyReport {
+    /// Poor fixes as a fraction of all breadcrumbs [0, 1]
+    pub fn poor_fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.poor as f64 / self.total as f64
+    }
+
+    /// Fixes without a reported accuracy as a fraction [0, 1]
+    pub fn unknown_fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.unknown as f64 / self.total as f64
+    }
+}
+
+/// Does `breadcrumb` report an accuracy worse than `max_accuracy_m`?
+/// False when it reports none.
+pub fn is_poor_accuracy(breadcrumb: &Breadcrumb, max_accuracy_m: f64) -> bool {
+    breadcrumb.meta_flags.accuracy.is_some_and(|a| a > max_accuracy_m)
+}
+
+/// Moves (non-zero displacements) needed before
+/// `directional_persistence` is measured.
+pub const MIN_PERSISTENCE_MOVES: usize = 10;
+
+/// Mean resultant length of `bearings` (radians) [0, 1]: 1 when every
+/// move heads the same way, near 0 when headings cancel out, as they
+/// do for a person going out and coming back. None with fewer than
+/// `MIN_PERSISTENCE_MOVES` bearings.
+pub fn directional_persistence(bearings: &[f64]) -> Option<f64> {
+    if bearings.len() < MIN_PERSISTENCE_MOVES {
+        return None;
+    }
+    let n = bearings.len() as f64;
+    let (s, c) = bearings.iter().fold((0.0, 0.0), |(s, c), &b| (s + b.sin(), c + b.cos()));
+    Some((s / n).hypot(c / n))
+}
+
+/// Share of zero (same-cell) displacements above which the chain's
+/// H3 resolution may be too coarse for its movement.
+pub const MAX_ZERO_DISPLACEMENT_SHARE: f64 = 0.5;
+
+/// Whether the chain's H3 resolution can see how its user moves.
+///
+/// Moves shorter than a cell read as zero. At resolution 7 (~1.2 km
+/// edge) a bot pacing inside one cell and a person running errands
+/// around the block both look stationary. The chain is too coarse
+/// when most displacements are zero and the moves that do register
+/// are mostly single hops to a neighbouring cell.
+#[derive(Debug, Clone)]
+pub struct ResolutionReport {
+    /// Most common H3 resolution in the chain
+    pub resolution: u8,
+    /// Fraction of displacements that stay in the same cell [0, 1]
+    pub zero_share: f64,
+    /// Median non-zero displacement (km); 0.0 if there are none
+    pub median_move_km: f64,
+    /// Distance between neighbouring cell centers (km)
+    pub cell_pitch_km: f64,
+}
+
+impl ResolutionReport {
+    /// Is the resolution too coarse for the observed movement scale?
+    pub fn is_too_coarse(&self) -> bool {
+        self.zero_share > MAX_ZERO_DISPLACEMENT_SHARE
+            && self.median_move_km < 2.0 * self.cell_pitch_km
+    }
+
+    /// A finer resolution to collect at, if this one is too coarse.
+    /// Each step is ~2.6× finer, so two turn a one-cell hop into
+    /// several cells.
+    pub fn recommended_resolution(&self) -> Option<u8> {
+        self.is_too_coarse().then(|| (self.resolution + 2).min(15))
+    }
+}
+
+/// How chain construction treats missing breadcrumb indices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GapTolerance {
+    /// Every index from genesis on must be present
+    #[default]
+    Strict,
+    /// Up to `max_missing` indices in total may be absent, provided
+    /// each breadcrumb after a gap carries a `skip_hash` equal to the
+    /// block hash of the breadcrumb before it
+    Lenient { max_missing: u64 },
+}
+
+/// A run of missing indices tolerated in lenient mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexGap {
+    /// Index of the last breadcrumb before the gap
+    pub after: u64,
+    /// Number of missing indices
+    pub missing: u64,
+}
+
+/// A privacy-safe, reproducible copy of a chain, as produced by
+/// `BreadcrumbChain::to_anonymized_vector`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestVector {
+    /// Human-readable note on where the vector came from
+    pub description: String,
+    /// Re-signed breadcrumbs under a throwaway identity
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
 
 /// A verified breadcrumb chain from a single identity.
 pub struct BreadcrumbChain {
@@ -18,13 +384,24 @@ pub struct BreadcrumbChain {
     pub breadcrumbs: Vec<Breadcrumb>,
     pub displacements: Vec<Displacement>,
     pub chain_verified: bool,
+    /// Gaps tolerated under `GapTolerance::Lenient` (empty when strict)
+    pub gaps: Vec<IndexGap>,
 }
 
 impl BreadcrumbChain {
     /// Parse and verify a breadcrumb chain from JSON.
     /// Performs structural validation but NOT Ed25519 signature
-    /// verification (that requires the full crypto stack).
-    pub fn from_breadcrumbs(mut breadcrumbs: Vec<Breadcrumb>) -> Result<Self> {
+    /// verification (see `verify_signatures`).
+    pub fn from_breadcrumbs(breadcrumbs: Vec<Breadcrumb>) -> Result<Self> {
+        Self::from_breadcrumbs_with_tolerance(breadcrumbs, GapTolerance::Strict)
+    }
+
+    /// Like `from_breadcrumbs`, but with a configurable tolerance for
+    /// missing indices.
+    pub fn from_breadcrumbs_with_tolerance(
+        mut breadcrumbs: Vec<Breadcrumb>,
+        tolerance: GapTolerance,
+    ) -> Result<Self> {
         if breadcrumbs.is_empty() {
             return Err(TripError::InsufficientBreadcrumbs { got: 0, need: 1 });
         }
@@ -43,18 +420,36 @@ impl BreadcrumbChain {
             }
         }
 
+        // Duplicates (a common export bug) would otherwise surface
+        // as a confusing gap further down the chain
+        if let Some(pair) = breadcrumbs.windows(2).find(|pair| pair[0].index == pair[1].index) {
+            return Err(TripError::ChainIntegrity(
+                format!("duplicate index {}", pair[1].index)
+            ));
+        }
+
         // Verify index sequence
-        for (i, b) in breadcrumbs.iter().enumerate() {
-            if b.index != i as u64 {
-                return Err(TripError::ChainIntegrity(
-                    format!("Index gap: expected {}, got {} at position {}", i, b.index, i)
-                ));
+        let gaps = match tolerance {
+            GapTolerance::Strict => {
+                for (i, b) in breadcrumbs.iter().enumerate() {
+                    if b.index != i as u64 {
+                        return Err(TripError::ChainIntegrity(
+                            format!("Index gap: expected {}, got {} at position {}", i, b.index, i)
+                        ));
+                    }
+                }
+                Vec::new()
             }
-        }
+            GapTolerance::Lenient { max_missing } => Self::find_gaps(&breadcrumbs, max_missing)?,
+        };
 
-        // Verify monotonic timestamps
+        // Index order and time order must agree; then no two
+        // breadcrumbs may share a timestamp
+        Self::verify_order_consistency(&breadcrumbs)?;
+
+        // Verify monotonic timestamps, at the signed (millisecond) precision
         for pair in breadcrumbs.windows(2) {
-            if pair[1].timestamp <= pair[0].timestamp {
+            if pair[1].timestamp_millis() <= pair[0].timestamp_millis() {
                 return Err(TripError::ChainIntegrity(
                     format!(
                         "Non-monotonic timestamp at index {}: {} <= {}",
@@ -68,16 +463,62 @@ impl BreadcrumbChain {
         Self::verify_hash_chain(&breadcrumbs)?;
 
         // Compute displacements
-        let displacements = compute_displacements(&breadcrumbs);
+        let displacements = compute_displacements(&breadcrumbs)?;
 
         Ok(Self {
             identity,
             breadcrumbs,
             displacements,
             chain_verified: true,
+            gaps,
         })
     }
 
+    /// Collect index gaps, failing if the genesis breadcrumb is missing
+    /// or more than `max_missing` indices are absent in total.
+    fn find_gaps(breadcrumbs: &[Breadcrumb], max_missing: u64) -> Result<Vec<IndexGap>> {
+        if breadcrumbs[0].index != 0 {
+            return Err(TripError::ChainIntegrity(
+                format!("Index gap: expected 0, got {} at position 0", breadcrumbs[0].index)
+            ));
+        }
+
+        let gaps: Vec<IndexGap> = breadcrumbs.windows(2)
+            .filter(|pair| pair[1].index > pair[0].index + 1)
+            .map(|pair| IndexGap {
+                after: pair[0].index,
+                missing: pair[1].index - pair[0].index - 1,
+            })
+            .collect();
+
+        let missing: u64 = gaps.iter().map(|g| g.missing).sum();
+        if missing > max_missing {
+            return Err(TripError::ChainIntegrity(
+                format!("{} missing indices exceed tolerance of {}", missing, max_missing)
+            ));
+        }
+        Ok(gaps)
+    }
+
+    /// Check that sorting by index also sorts by time, naming the
+    /// first inversion (a later index with an older timestamp) and how
+    /// many there are. Compared at the signed (millisecond) precision.
+    fn verify_order_consistency(breadcrumbs: &[Breadcrumb]) -> Result<()> {
+        let mut inversions = breadcrumbs.windows(2)
+            .filter(|pair| pair[1].timestamp_millis() < pair[0].timestamp_millis());
+        let Some(first) = inversions.next() else {
+            return Ok(());
+        };
+        Err(TripError::ChainIntegrity(format!(
+            "Index and timestamp order disagree: index {} ({}) is older than index {} ({}); {} inversion(s)",
+            first[1].index,
+            first[1].timestamp,
+            first[0].index,
+            first[0].timestamp,
+            1 + inversions.count(),
+        )))
+    }
+
     /// Verify the hash chain: each breadcrumb's previous_hash
     /// must equal the prior breadcrumb's block_hash.
     fn verify_hash_chain(breadcrumbs: &[Breadcrumb]) -> Result<()> {
@@ -88,8 +529,19 @@ impl BreadcrumbChain {
             ));
         }
 
-        // Each subsequent block must reference the previous
+        // Each subsequent block must reference the previous. Across a
+        // tolerated gap, the skip reference must link to the block
+        // before the gap instead.
         for pair in breadcrumbs.windows(2) {
+            if pair[1].index != pair[0].index + 1 {
+                if pair[1].skip_hash.as_deref() != Some(pair[0].block_hash.as_str()) {
+                    return Err(TripError::ChainIntegrity(
+                        format!("Gap before index {} has no valid skip reference", pair[1].index)
+                    ));
+                }
+                continue;
+            }
+
             match &pair[1].previous_hash {
                 Some(prev) if prev == &pair[0].block_hash => {},
                 Some(prev) => {
@@ -113,32 +565,26 @@ impl BreadcrumbChain {
         Ok(())
     }
 
+    /// Verify the Ed25519 signature of every breadcrumb.
+    /// Fails on the first invalid signature.
+    pub fn verify_signatures(&self) -> Result<()> {
+        for b in &self.breadcrumbs {
+            b.verify_signature()?;
+        }
+        Ok(())
+    }
+
     /// Recompute and verify block hashes.
     /// Matches the Flutter BreadcrumbBlock.computeHash() algorithm:
     /// SHA-256(dataToSign + ":" + signature)
     pub fn verify_block_hashes(&self) -> Result<()> {
         for b in &self.breadcrumbs {
-            let data_to_sign = serde_json::json!({
-                "index": b.index,
-                "identity": b.identity_public_key,
-                "timestamp": b.timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
-                "loc_cell": b.location_cell,
-                "loc_res": b.location_resolution,
-                "context": b.context_digest,
-                "prev_hash": b.previous_hash.as_deref().unwrap_or("genesis"),
-                "meta": b.meta_flags,
-            });
-
-            let content = format!("{}:{}", data_to_sign, b.signature);
-            let mut hasher = Sha256::new();
-            hasher.update(content.as_bytes());
-            let hash = hex::encode(hasher.finalize());
-
+            let hash = b.compute_block_hash();
             if hash != b.block_hash {
                 return Err(TripError::ChainIntegrity(
                     format!(
                         "Block hash mismatch at index {}: computed {}, stored {}",
-                        b.index, &hash[..8], &b.block_hash[..8]
+                        b.index, &hash[..8], b.block_hash.get(..8).unwrap_or(&b.block_hash)
                     )
                 ));
             }
@@ -159,9 +605,9 @@ impl BreadcrumbChain {
         if self.breadcrumbs.len() < 2 {
             return 0.0;
         }
-        let first = self.breadcrumbs.first().unwrap().unix_seconds();
-        let last = self.breadcrumbs.last().unwrap().unix_seconds();
-        last - first
+        let first = self.breadcrumbs.first().unwrap().timestamp;
+        let last = self.breadcrumbs.last().unwrap().timestamp;
+        last.signed_duration_since(first).num_milliseconds() as f64 / 1000.0
     }
 
     /// Number of unique H3 cells visited
@@ -179,15 +625,974 @@ impl BreadcrumbChain {
         self.displacements.iter().map(|d| d.distance_km).collect()
     }
 
+    /// Extract displacement magnitudes under a given distance mode (km)
+    pub fn displacement_series_with_mode(&self, mode: DistanceMode) -> Vec<f64> {
+        self.displacements.iter().map(|d| mode.distance_km(d)).collect()
+    }
+
+    /// Position along the chain's principal axis (km from the
+    /// centroid) at the end of each displacement, aligned with
+    /// `interval_series`: a 1-D embedding of the path for
+    /// `psd::path_cross_check`.
+    ///
+    /// Cell centers are placed on a local tangent plane and projected
+    /// onto the direction of greatest spread. Latitude alone would
+    /// flatten an east-west path, and a space-filling-curve index
+    /// jumps between neighbouring cells that sit on either side of a
+    /// curve fold; the principal axis keeps the largest share of the
+    /// movement with no jumps.
+    pub fn path_series(&self) -> Vec<f64> {
+        let centers: Vec<(f64, f64)> = self.displacements.iter()
+            .filter_map(|d| h3_cell_to_latlon(&d.to_cell).ok())
+            .collect();
+        let Some(&(lat0, lon0)) = centers.first() else {
+            return Vec::new();
+        };
+
+        // Local tangent plane (km), longitudes wrapped around lon0
+        let km_per_degree = 111.32;
+        let points: Vec<(f64, f64)> = centers.iter()
+            .map(|&(lat, lon)| {
+                let dlon = (lon - lon0 + 540.0).rem_euclid(360.0) - 180.0;
+                (dlon * km_per_degree * lat0.to_radians().cos(), (lat - lat0) * km_per_degree)
+            })
+            .collect();
+
+        let n = points.len() as f64;
+        let (mx, my) = points.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x / n, sy + y / n));
+        let (mut cxx, mut cyy, mut cxy) = (0.0, 0.0, 0.0);
+        for &(x, y) in &points {
+            cxx += (x - mx).powi(2);
+            cyy += (y - my).powi(2);
+            cxy += (x - mx) * (y - my);
+        }
+        let theta = 0.5 * (2.0 * cxy).atan2(cxx - cyy);
+        let (sin, cos) = theta.sin_cos();
+
+        points.iter().map(|&(x, y)| (x - mx) * cos + (y - my) * sin).collect()
+    }
+
+    /// Initial great-circle bearing of each move between cell
+    /// centers, in radians clockwise from north [0, 2π). Same-cell
+    /// displacements have no direction and are skipped.
+    pub fn bearing_series(&self) -> Vec<f64> {
+        self.displacements.iter()
+            .filter(|d| d.from_cell != d.to_cell)
+            .filter_map(|d| {
+                let (lat1, lon1) = h3_cell_to_latlon(&d.from_cell).ok()?;
+                let (lat2, lon2) = h3_cell_to_latlon(&d.to_cell).ok()?;
+                let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+                let dlon = (lon2 - lon1).to_radians();
+                let y = dlon.sin() * phi2.cos();
+                let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlon.cos();
+                Some(y.atan2(x).rem_euclid(std::f64::consts::TAU))
+            })
+            .collect()
+    }
+
+    /// How consistently the chain keeps one heading (see
+    /// `directional_persistence`). A script following waypoints along
+    /// a route scores near 1; people reverse and revisit.
+    pub fn directional_persistence(&self) -> Option<f64> {
+        directional_persistence(&self.bearing_series())
+    }
+
+    /// Median quantization uncertainty of the displacements (km).
+    /// Displacements below this are dominated by cell size.
+    pub fn quantization_km(&self) -> f64 {
+        let mut bands: Vec<f64> = self.displacements.iter().map(|d| d.uncertainty_km).collect();
+        if bands.is_empty() {
+            return 0.0;
+        }
+        bands.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        bands[bands.len() / 2]
+    }
+
     /// Extract time intervals as a series (seconds)
     pub fn interval_series(&self) -> Vec<f64> {
         self.displacements.iter().map(|d| d.dt_seconds).collect()
     }
 
+    /// Summarize the sampling cadence of the chain.
+    pub fn sampling_cadence(&self) -> CadenceReport {
+        let mut intervals = self.interval_series();
+        intervals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let median_interval_seconds = if intervals.is_empty() {
+            0.0
+        } else {
+            intervals[intervals.len() / 2]
+        };
+
+        CadenceReport {
+            median_interval_seconds,
+            too_short: intervals.iter()
+                .filter(|&&dt| dt < MIN_BREADCRUMB_INTERVAL_SECS as f64)
+                .count(),
+            too_long: intervals.iter()
+                .filter(|&&dt| dt > MAX_BREADCRUMB_INTERVAL_SECS as f64)
+                .count(),
+            total: intervals.len(),
+        }
+    }
+
+    /// Summarize long stationary dwells (see `DwellReport`).
+    pub fn dwell_report(&self) -> DwellReport {
+        let long: Vec<&Displacement> = self.displacements.iter()
+            .filter(|d| d.dt_seconds >= LONG_DWELL_SECS as f64)
+            .collect();
+
+        DwellReport {
+            long_intervals: long.len(),
+            stationary: long.iter().filter(|d| d.from_cell == d.to_cell).count(),
+            total: self.displacements.len(),
+        }
+    }
+
+    /// Dense groups of visited places: DBSCAN over the breadcrumbs'
+    /// cell centers, largest first.
+    ///
+    /// A cell is a core point when at least `min_points` breadcrumbs
+    /// lie within `eps_km` of its center (its own visits included);
+    /// clusters are the core cells linked within `eps_km`, plus the
+    /// non-core cells within reach of one. Breadcrumbs in no cluster
+    /// are noise (see `noise_fraction`). Repeat visits to one cell are
+    /// counted once per cell, so the cost grows with the number of
+    /// distinct cells, not the chain length.
+    pub fn spatial_clusters(&self, eps_km: f64, min_points: usize) -> Vec<Cluster> {
+        let mut visits: HashMap<&str, usize> = HashMap::new();
+        for b in &self.breadcrumbs {
+            *visits.entry(b.location_cell.as_str()).or_default() += 1;
+        }
+        let mut cells: Vec<(&str, usize)> = visits.into_iter().collect();
+        cells.sort_unstable();
+        let points: Vec<((f64, f64), usize)> = cells.iter()
+            .filter_map(|&(cell, count)| h3_cell_to_latlon(cell).ok().map(|p| (p, count)))
+            .collect();
+
+        let neighbours: Vec<Vec<usize>> = points.iter()
+            .map(|&((lat, lon), _)| {
+                (0..points.len())
+                    .filter(|&j| haversine_km(lat, lon, points[j].0.0, points[j].0.1) <= eps_km)
+                    .collect()
+            })
+            .collect();
+        let is_core = |i: usize| neighbours[i].iter().map(|&j| points[j].1).sum::<usize>() >= min_points;
+
+        let mut label: Vec<Option<usize>> = vec![None; points.len()];
+        let mut clusters = Vec::new();
+        for seed in 0..points.len() {
+            if label[seed].is_some() || !is_core(seed) {
+                continue;
+            }
+            let id = clusters.len();
+            let mut members = Vec::new();
+            let mut frontier = vec![seed];
+            label[seed] = Some(id);
+            while let Some(i) = frontier.pop() {
+                members.push(i);
+                if !is_core(i) {
+                    continue;
+                }
+                for &j in &neighbours[i] {
+                    if label[j].is_none() {
+                        label[j] = Some(id);
+                        frontier.push(j);
+                    }
+                }
+            }
+
+            let size: usize = members.iter().map(|&i| points[i].1).sum();
+            let (lat, lon) = members.iter().fold((0.0, 0.0), |(lat, lon), &i| {
+                let ((p_lat, p_lon), count) = points[i];
+                (lat + p_lat * count as f64, lon + p_lon * count as f64)
+            });
+            clusters.push(Cluster {
+                centroid: (lat / size as f64, lon / size as f64),
+                size,
+                cells: members.len(),
+            });
+        }
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.size));
+        clusters
+    }
+
+    /// The chain without breadcrumbs the user added by hand
+    /// (`MetaFlags::manual`), displacements recomputed between the
+    /// automatically sampled ones that remain.
+    ///
+    /// The result is derived from this already-verified chain: the
+    /// hash links across removed breadcrumbs are not re-checked.
+    pub fn without_manual_breadcrumbs(&self) -> Result<BreadcrumbChain> {
+        self.retain_breadcrumbs(|b| !b.meta_flags.manual)
+    }
+
+    /// The chain with only the breadcrumbs `keep` accepts,
+    /// displacements recomputed between them. Like
+    /// `without_manual_breadcrumbs`, hash links across removed
+    /// breadcrumbs are not re-checked.
+    pub fn retain_breadcrumbs(&self, keep: impl Fn(&Breadcrumb) -> bool) -> Result<BreadcrumbChain> {
+        let breadcrumbs: Vec<Breadcrumb> = self.breadcrumbs.iter()
+            .filter(|b| keep(b))
+            .cloned()
+            .collect();
+        let displacements = compute_displacements(&breadcrumbs)?;
+        Ok(BreadcrumbChain {
+            identity: self.identity.clone(),
+            breadcrumbs,
+            displacements,
+            chain_verified: self.chain_verified,
+            gaps: self.gaps.clone(),
+        })
+    }
+
+    /// Summarize how often the context digest changes (see
+    /// `ContextReport`).
+    pub fn context_report(&self) -> ContextReport {
+        let digests: Vec<&str> = self.breadcrumbs.iter().map(|b| b.context_digest.as_str()).collect();
+        let mut unique = digests.clone();
+        unique.sort_unstable();
+        unique.dedup();
+
+        ContextReport {
+            repeated: digests.windows(2).filter(|pair| pair[0] == pair[1]).count(),
+            unique: unique.len(),
+            total: digests.len(),
+        }
+    }
+
+    /// Count fixes reporting an accuracy worse than `max_accuracy_m`
+    /// meters (see `AccuracyReport`).
+    pub fn accuracy_report(&self, max_accuracy_m: f64) -> AccuracyReport {
+        AccuracyReport {
+            poor: self.breadcrumbs.iter().filter(|b| is_poor_accuracy(b, max_accuracy_m)).count(),
+            unknown: self.breadcrumbs.iter().filter(|b| b.meta_flags.accuracy.is_none()).count(),
+            total: self.breadcrumbs.len(),
+        }
+    }
+
+    /// Compare the chain's H3 resolution with its movement scale
+    /// (see `ResolutionReport`).
+    pub fn resolution_report(&self) -> ResolutionReport {
+        let mut counts = [0usize; 16];
+        for b in &self.breadcrumbs {
+            counts[b.location_resolution.min(15) as usize] += 1;
+        }
+        let resolution = (0..16u8).max_by_key(|&r| counts[r as usize]).unwrap_or(0);
+
+        let mut moves: Vec<f64> = self.displacements.iter()
+            .filter(|d| d.from_cell != d.to_cell)
+            .map(|d| d.distance_km)
+            .collect();
+        moves.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let total = self.displacements.len();
+        ResolutionReport {
+            resolution,
+            zero_share: if total == 0 { 0.0 } else { (total - moves.len()) as f64 / total as f64 },
+            median_move_km: moves.get(moves.len() / 2).copied().unwrap_or(0.0),
+            cell_pitch_km: h3o::Resolution::try_from(resolution)
+                .map_or(0.0, |r| 3f64.sqrt() * r.edge_length_km()),
+        }
+    }
+
+    /// Verify the chain's overall cadence is plausible for a human
+    /// device: the median interval must lie within
+    /// [MIN_BREADCRUMB_INTERVAL_SECS, MAX_BREADCRUMB_INTERVAL_SECS].
+    /// Isolated out-of-range intervals are tolerated and reported.
+    pub fn verify_sampling_cadence(&self) -> Result<CadenceReport> {
+        let report = self.sampling_cadence();
+        if report.total > 0 && !report.cadence_in_spec() {
+            return Err(TripError::ChainIntegrity(
+                format!(
+                    "Sampling cadence out of spec: median interval {:.0}s not in [{}, {}]",
+                    report.median_interval_seconds,
+                    MIN_BREADCRUMB_INTERVAL_SECS,
+                    MAX_BREADCRUMB_INTERVAL_SECS,
+                )
+            ));
+        }
+        Ok(report)
+    }
+
+    /// Radius of gyration in km (González et al. 2008): RMS distance
+    /// of the visited cell centers from their center of mass.
+    ///
+    /// The center of mass is the normalized mean of the cells' unit
+    /// vectors, so it is well-defined across the antimeridian.
+    /// Unparseable cells are skipped; returns 0.0 without any.
+    pub fn radius_of_gyration(&self) -> f64 {
+        let points: Vec<(f64, f64)> = self.breadcrumbs.iter()
+            .filter_map(|b| h3_cell_to_latlon(&b.location_cell).ok())
+            .collect();
+        if points.is_empty() {
+            return 0.0;
+        }
+
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        for &(lat, lon) in &points {
+            let (lat, lon) = (lat.to_radians(), lon.to_radians());
+            x += lat.cos() * lon.cos();
+            y += lat.cos() * lon.sin();
+            z += lat.sin();
+        }
+        let center_lat = z.atan2((x * x + y * y).sqrt()).to_degrees();
+        let center_lon = y.atan2(x).to_degrees();
+
+        let mean_sq = points.iter()
+            .map(|&(lat, lon)| haversine_km(center_lat, center_lon, lat, lon).powi(2))
+            .sum::<f64>() / points.len() as f64;
+        mean_sq.sqrt()
+    }
+
+    /// SHA-256 over the chain's movement alone: each displacement in
+    /// whole meters and each interval in whole seconds, in order.
+    ///
+    /// Keys, signatures, context digests and absolute times are left
+    /// out, so the same trajectory re-signed under another identity,
+    /// or replayed a day later, has the same signature. Any change of
+    /// path or timing gives a different one; for near-copies use
+    /// `similarity::trajectory_similarity`.
+    pub fn mobility_signature(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"TRIP-MOBILITY-v1");
+        hasher.update((self.displacements.len() as u64).to_be_bytes());
+        for d in &self.displacements {
+            hasher.update(((d.distance_km * 1000.0).round() as u64).to_be_bytes());
+            hasher.update((d.dt_seconds.round() as u64).to_be_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    /// How well the chain's cell and interval sequence compresses:
+    /// raw size over DEFLATE size, so higher means more compressible.
+    ///
+    /// A heuristic randomness check, complementary to the statistical
+    /// tests rather than part of the verdict. Organic movement has
+    /// high algorithmic complexity; a cheaply generated chain (a loop
+    /// of cells, a fixed clock, an arithmetic sequence) is mostly
+    /// repetition and compresses well. Each breadcrumb is serialized
+    /// as its 64-bit cell index and the milliseconds since the
+    /// previous one. Ratios are comparable only between chains of
+    /// similar length, as DEFLATE finds more to reuse in longer input;
+    /// see `HIGH_COMPRESSIBILITY_RATIO`.
+    pub fn compressibility_ratio(&self) -> f64 {
+        let mut raw = Vec::with_capacity(self.breadcrumbs.len() * 12);
+        let mut previous = None;
+        for b in &self.breadcrumbs {
+            match u64::from_str_radix(&b.location_cell, 16) {
+                Ok(cell) => raw.extend_from_slice(&cell.to_be_bytes()),
+                Err(_) => raw.extend_from_slice(b.location_cell.as_bytes()),
+            }
+            let interval_ms = previous.map_or(0, |t: DateTime<Utc>| {
+                b.timestamp.signed_duration_since(t).num_milliseconds()
+            });
+            raw.extend_from_slice(&(interval_ms.clamp(0, u32::MAX as i64) as u32).to_be_bytes());
+            previous = Some(b.timestamp);
+        }
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&raw).expect("writing to a Vec cannot fail");
+        let compressed = encoder.finish().expect("writing to a Vec cannot fail");
+        raw.len() as f64 / compressed.len() as f64
+    }
+
+    /// Number of independent observations the chain is worth: its
+    /// length divided by the integrated autocorrelation time of the
+    /// displacement series (see `effective_sample_size`), in [1, len].
+    /// Movement that keeps going the same way for many breadcrumbs
+    /// carries less evidence than its length suggests.
+    pub fn effective_sample_size(&self) -> f64 {
+        match self.breadcrumbs.len() {
+            n @ 0..=1 => n as f64,
+            _ => effective_chain_length(&self.displacement_series()),
+        }
+    }
+
     /// Chain head hash (most recent breadcrumb's block_hash)
     pub fn head_hash(&self) -> &str {
         self.breadcrumbs.last()
             .map(|b| b.block_hash.as_str())
             .unwrap_or("")
     }
+
+    /// Privacy-safe copy of this chain for reproducing a verdict.
+    ///
+    /// The cells are moved to a random place on the globe with their
+    /// H3 grid offsets from the first cell kept (see `relocate_cells`),
+    /// so a revisited cell is still revisited and every displacement
+    /// keeps its length up to a near-uniform scale factor, which
+    /// neither α nor β depends on. Timestamps and meta flags are kept,
+    /// context digests are re-hashed with a random salt (equal digests
+    /// stay equal), and the result is re-signed under a fresh identity
+    /// key so it verifies like the original. Unparseable cells carry
+    /// no position and are kept as is.
+    pub fn to_anonymized_vector(&self) -> TestVector {
+        self.to_anonymized_vector_with_rng(&mut rand::rngs::OsRng)
+    }
+
+    /// Like `to_anonymized_vector`, drawing the placement, salt and
+    /// identity key from `rng`; a seeded one gives a reproducible
+    /// vector.
+    pub fn to_anonymized_vector_with_rng(&self, rng: &mut (impl Rng + rand::CryptoRng)) -> TestVector {
+        use ed25519_dalek::Signer;
+
+        let key = ed25519_dalek::SigningKey::generate(rng);
+        let identity = hex::encode(key.verifying_key().to_bytes());
+        let salt: [u8; 32] = rng.gen();
+        let cells = relocate_cells(&self.breadcrumbs, rng);
+
+        let mut breadcrumbs: Vec<Breadcrumb> = Vec::with_capacity(self.breadcrumbs.len());
+        for original in &self.breadcrumbs {
+            let mut b = original.clone();
+            b.identity_public_key = identity.clone();
+            if let Some(cell) = cells.get(original.location_cell.as_str()) {
+                b.location_cell = cell.clone();
+            }
+            b.context_digest = hex::encode(
+                Sha256::new().chain_update(salt).chain_update(&original.context_digest).finalize()
+            );
+
+            // Across a gap, the previous hash names a block that isn't
+            // in the vector, so it can only be replaced with noise
+            match breadcrumbs.last() {
+                None => b.previous_hash = None,
+                Some(prev) if b.index == prev.index + 1 => {
+                    b.previous_hash = Some(prev.block_hash.clone());
+                }
+                Some(prev) => {
+                    b.previous_hash = Some(hex::encode(rng.gen::<[u8; 32]>()));
+                    b.skip_hash = Some(prev.block_hash.clone());
+                }
+            }
+
+            b.signature = hex::encode(key.sign(b.data_to_sign().as_bytes()).to_bytes());
+            b.block_hash = b.compute_block_hash();
+            breadcrumbs.push(b);
+        }
+
+        TestVector {
+            description: format!("Anonymized chain of {} breadcrumbs", breadcrumbs.len()),
+            breadcrumbs,
+        }
+    }
+}
+
+/// Random placements tried before giving up on a grid-exact shift.
+const RELOCATION_ATTEMPTS: usize = 16;
+
+/// Map each distinct cell of `breadcrumbs` to its anonymized cell.
+///
+/// The first cell is moved by a random rotation of the globe and the
+/// others are placed at the same local IJ offset from it, so the grid
+/// layout is reproduced exactly. That fails for chains mixing
+/// resolutions or landing across a pentagon; after a few placements,
+/// each cell is rotated on its own instead, which keeps great-circle
+/// distances but adds up to a cell of quantization error.
+fn relocate_cells(breadcrumbs: &[Breadcrumb], rng: &mut impl Rng) -> HashMap<String, String> {
+    let mut cells: Vec<(&str, h3o::CellIndex)> = Vec::new();
+    for b in breadcrumbs {
+        if let Ok(cell) = parse_cell(&b.location_cell) {
+            if !cells.iter().any(|&(_, c)| c == cell) {
+                cells.push((&b.location_cell, cell));
+            }
+        }
+    }
+
+    for _ in 0..RELOCATION_ATTEMPTS {
+        if let Some(relocated) = shift_on_grid(&cells, &random_rotation(rng)) {
+            return relocated;
+        }
+    }
+    let rotation = random_rotation(rng);
+    cells.iter()
+        .filter_map(|&(hex_str, cell)| Some((hex_str.to_string(), cell_hex(rotate_cell(cell, &rotation)?))))
+        .collect()
+}
+
+/// Move the first cell by `rotation` and the rest by the same grid
+/// offset, or None if any of them can't be placed.
+fn shift_on_grid(
+    cells: &[(&str, h3o::CellIndex)],
+    rotation: &Rotation3<f64>,
+) -> Option<HashMap<String, String>> {
+    let anchor = cells.first()?.1;
+    let image = rotate_cell(anchor, rotation)?;
+    let origin = anchor.to_local_ij(anchor).ok()?.coord;
+    let target = image.to_local_ij(image).ok()?.coord;
+
+    cells.iter()
+        .map(|&(hex_str, cell)| {
+            let offset = cell.to_local_ij(anchor).ok()?.coord;
+            let coord = h3o::CoordIJ::new(target.i + offset.i - origin.i, target.j + offset.j - origin.j);
+            let moved = h3o::CellIndex::try_from(h3o::LocalIJ::new(image, coord)).ok()?;
+            Some((hex_str.to_string(), cell_hex(moved)))
+        })
+        .collect()
+}
+
+fn random_rotation(rng: &mut impl Rng) -> Rotation3<f64> {
+    use std::f64::consts::PI;
+    Rotation3::from_euler_angles(rng.gen_range(-PI..PI), rng.gen_range(-PI..PI), rng.gen_range(-PI..PI))
+}
+
+/// Move a cell's center by `rotation` and re-index it at the same
+/// resolution.
+fn rotate_cell(cell: h3o::CellIndex, rotation: &Rotation3<f64>) -> Option<h3o::CellIndex> {
+    let center = h3o::LatLng::from(cell);
+    let (lat, lng) = (center.lat_radians(), center.lng_radians());
+    let p = rotation * Vector3::new(lat.cos() * lng.cos(), lat.cos() * lng.sin(), lat.sin());
+
+    let rotated = h3o::LatLng::from_radians(p.z.clamp(-1.0, 1.0).asin(), p.y.atan2(p.x)).ok()?;
+    Some(rotated.to_cell(cell.resolution()))
+}
+
+fn cell_hex(cell: h3o::CellIndex) -> String {
+    format!("{:x}", u64::from(cell))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::breadcrumb::MetaFlags;
+    use chrono::{TimeZone, Utc};
+
+    /// Build a hash-linked chain of breadcrumbs at the given
+    /// (unix seconds, H3 cell) samples. Signatures are not valid.
+    pub(crate) fn synthetic_breadcrumbs(samples: &[(i64, &str)]) -> Vec<Breadcrumb> {
+        let mut out: Vec<Breadcrumb> = Vec::with_capacity(samples.len());
+        for (i, &(t, cell)) in samples.iter().enumerate() {
+            out.push(Breadcrumb {
+                index: i as u64,
+                identity_public_key: "ab".repeat(32),
+                timestamp: Utc.timestamp_opt(t, 0).unwrap(),
+                location_cell: cell.to_string(),
+                location_resolution: 10,
+                context_digest: format!("{:064x}", i),
+                previous_hash: out.last().map(|b| b.block_hash.clone()),
+                meta_flags: MetaFlags {
+                    battery: None,
+                    sampling: "normal".to_string(),
+                    state: "unknown".to_string(),
+                    network: "unknown".to_string(),
+                    accuracy: None,
+                    manual: false,
+                },
+                signature: "00".repeat(64),
+                block_hash: format!("{:064x}", i + 1),
+                beacon_digest: None,
+                skip_hash: None,
+            });
+        }
+        out
+    }
+
+    /// Re-key and sign breadcrumbs with `key`, recomputing block hashes
+    /// and links so that signatures and block hashes verify.
+    pub(crate) fn sign_breadcrumbs(breadcrumbs: &mut [Breadcrumb], key: &ed25519_dalek::SigningKey) {
+        use ed25519_dalek::Signer;
+
+        let identity = hex::encode(key.verifying_key().to_bytes());
+        let mut previous: Option<String> = None;
+        for b in breadcrumbs.iter_mut() {
+            b.identity_public_key = identity.clone();
+            b.previous_hash = previous.take();
+            b.signature = hex::encode(key.sign(b.data_to_sign().as_bytes()).to_bytes());
+            b.block_hash = b.compute_block_hash();
+            previous = Some(b.block_hash.clone());
+        }
+    }
+
+    /// Lévy-like random walk around Rome at H3 resolution 10,
+    /// with irregular 10-60 minute sampling.
+    pub(crate) fn random_walk_breadcrumbs(n: usize, seed: u64) -> Vec<Breadcrumb> {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (mut lat, mut lng) = (41.9028f64, 12.4964f64);
+        let mut t = 1_700_000_000i64;
+        let mut samples: Vec<(i64, String)> = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let cell = h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten);
+            samples.push((t, format!("{:x}", u64::from(cell))));
+
+            let u: f64 = rng.gen_range(0.05..1.0);
+            let step_km = (0.05 / u).min(20.0);
+            let heading: f64 = rng.gen_range(0.0..std::f64::consts::TAU);
+            lat += step_km / 111.0 * heading.cos();
+            lng += step_km / (111.0 * lat.to_radians().cos()) * heading.sin();
+            t += rng.gen_range(600..3600);
+        }
+
+        let refs: Vec<(i64, &str)> = samples.iter().map(|(t, c)| (*t, c.as_str())).collect();
+        synthetic_breadcrumbs(&refs)
+    }
+
+    fn chain_with_interval(n: usize, dt: i64) -> BreadcrumbChain {
+        let samples: Vec<(i64, &str)> = (0..n)
+            .map(|i| (1_700_000_000 + i as i64 * dt, "8a1e8052da1ffff"))
+            .collect();
+        BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap()
+    }
+
+    #[test]
+    fn test_duplicate_index_reported() {
+        let samples: Vec<(i64, &str)> = (0..10)
+            .map(|i| (1_700_000_000 + i as i64 * 900, "8a1e8052da1ffff"))
+            .collect();
+        let mut breadcrumbs = synthetic_breadcrumbs(&samples);
+        breadcrumbs[6].index = 5;
+
+        match BreadcrumbChain::from_breadcrumbs(breadcrumbs) {
+            Err(TripError::ChainIntegrity(msg)) => assert_eq!(msg, "duplicate index 5"),
+            Err(e) => panic!("expected duplicate index error, got {e}"),
+            Ok(_) => panic!("duplicate index accepted"),
+        }
+    }
+
+    #[test]
+    fn test_cadence_one_second_rejected() {
+        let chain = chain_with_interval(50, 1);
+        let report = chain.sampling_cadence();
+        assert_eq!(report.too_short, 49);
+        assert!(!report.cadence_in_spec());
+        assert!(chain.verify_sampling_cadence().is_err());
+    }
+
+    #[test]
+    fn test_cadence_in_spec_with_isolated_outlier() {
+        let chain = chain_with_interval(50, 1800);
+        let report = chain.verify_sampling_cadence().unwrap();
+        assert!(report.cadence_in_spec());
+        assert!(!report.has_isolated_outliers());
+
+        // One 30-second gap doesn't make the cadence wrong
+        let mut samples: Vec<(i64, &str)> = (0..50)
+            .map(|i| (1_700_000_000 + i as i64 * 1800, "8a1e8052da1ffff"))
+            .collect();
+        samples[10].0 = samples[9].0 + 30;
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+        let report = chain.verify_sampling_cadence().unwrap();
+        assert_eq!(report.too_short, 1);
+        assert!(report.has_isolated_outliers());
+    }
+
+    /// 20 breadcrumbs with the given indices dropped, as a sync
+    /// failure would leave them. Survivors after a gap link back
+    /// across it with a skip reference when `link` is set.
+    fn chain_with_dropped(dropped: &[u64], link: bool) -> Vec<Breadcrumb> {
+        let samples: Vec<(i64, &str)> = (0..20)
+            .map(|i| (1_700_000_000 + i as i64 * 900, "8a1e8052da1ffff"))
+            .collect();
+        let mut breadcrumbs = synthetic_breadcrumbs(&samples);
+        breadcrumbs.retain(|b| !dropped.contains(&b.index));
+
+        if link {
+            for i in 1..breadcrumbs.len() {
+                if breadcrumbs[i].index != breadcrumbs[i - 1].index + 1 {
+                    breadcrumbs[i].skip_hash = Some(breadcrumbs[i - 1].block_hash.clone());
+                }
+            }
+        }
+        breadcrumbs
+    }
+
+    #[test]
+    fn test_half_second_spacing_distinguished() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let mut breadcrumbs = random_walk_breadcrumbs(3, 2);
+        breadcrumbs[1].timestamp = breadcrumbs[0].timestamp + chrono::Duration::milliseconds(500);
+        breadcrumbs[2].timestamp = breadcrumbs[1].timestamp + chrono::Duration::milliseconds(500);
+        sign_breadcrumbs(&mut breadcrumbs, &key);
+
+        assert_ne!(breadcrumbs[0].unix_seconds(), breadcrumbs[1].unix_seconds());
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs.clone()).unwrap();
+        assert_eq!(chain.interval_series(), vec![0.5, 0.5]);
+        assert_eq!(chain.duration_seconds(), 1.0);
+
+        // Sub-millisecond differences aren't signed, so they don't count
+        breadcrumbs[1].timestamp = breadcrumbs[0].timestamp + chrono::Duration::microseconds(400);
+        breadcrumbs[2].timestamp = breadcrumbs[1].timestamp + chrono::Duration::milliseconds(500);
+        sign_breadcrumbs(&mut breadcrumbs, &key);
+        assert!(matches!(
+            BreadcrumbChain::from_breadcrumbs(breadcrumbs),
+            Err(TripError::ChainIntegrity(msg)) if msg.contains("Non-monotonic")
+        ));
+    }
+
+    #[test]
+    fn test_index_and_timestamp_order_disagree() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let mut breadcrumbs = random_walk_breadcrumbs(10, 2);
+
+        // Index 5 is stamped an hour before index 4, and properly signed
+        breadcrumbs[5].timestamp = breadcrumbs[4].timestamp - chrono::Duration::hours(1);
+        sign_breadcrumbs(&mut breadcrumbs, &key);
+
+        // Shuffled input is sorted by index first, so the result is the same
+        breadcrumbs.reverse();
+        let Err(TripError::ChainIntegrity(msg)) = BreadcrumbChain::from_breadcrumbs(breadcrumbs) else {
+            panic!("expected ChainIntegrity");
+        };
+        assert!(msg.contains("index 5"), "{msg}");
+        assert!(msg.contains("older than index 4"), "{msg}");
+        assert!(msg.contains("1 inversion(s)"), "{msg}");
+    }
+
+    #[test]
+    fn test_computed_block_hash_verifies() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let mut breadcrumbs = random_walk_breadcrumbs(10, 2);
+        sign_breadcrumbs(&mut breadcrumbs, &key);
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs.clone()).unwrap();
+        assert!(chain.verify_block_hashes().is_ok());
+
+        // A stale hash after re-signing is caught
+        let mut stale = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        stale.breadcrumbs[4].meta_flags.battery = Some(3);
+        stale.breadcrumbs[4].signature = hex::encode(
+            ed25519_dalek::Signer::sign(&key, stale.breadcrumbs[4].data_to_sign().as_bytes()).to_bytes(),
+        );
+        assert!(stale.verify_block_hashes().is_err());
+        stale.breadcrumbs[4].block_hash = stale.breadcrumbs[4].compute_block_hash();
+        assert!(stale.verify_block_hashes().is_ok());
+    }
+
+    #[test]
+    fn test_lenient_tolerates_single_gap() {
+        let lenient = GapTolerance::Lenient { max_missing: 2 };
+
+        // Strict mode still rejects the gap
+        assert!(BreadcrumbChain::from_breadcrumbs(chain_with_dropped(&[7], true)).is_err());
+
+        let chain = BreadcrumbChain::from_breadcrumbs_with_tolerance(
+            chain_with_dropped(&[7], true), lenient,
+        ).unwrap();
+        assert_eq!(chain.len(), 19);
+        assert_eq!(chain.gaps, vec![IndexGap { after: 6, missing: 1 }]);
+
+        // A gap without a skip reference doesn't link
+        assert!(BreadcrumbChain::from_breadcrumbs_with_tolerance(
+            chain_with_dropped(&[7], false), lenient,
+        ).is_err());
+
+        let strict = BreadcrumbChain::from_breadcrumbs(chain_with_dropped(&[], false)).unwrap();
+        assert!(strict.gaps.is_empty());
+    }
+
+    #[test]
+    fn test_lenient_rejects_too_many_gaps() {
+        let lenient = GapTolerance::Lenient { max_missing: 2 };
+        match BreadcrumbChain::from_breadcrumbs_with_tolerance(
+            chain_with_dropped(&[3, 9, 10], true), lenient,
+        ) {
+            Err(TripError::ChainIntegrity(msg)) => assert!(msg.contains("exceed tolerance"), "{msg}"),
+            Err(e) => panic!("expected tolerance error, got {e}"),
+            Ok(_) => panic!("3 missing indices accepted with max_missing = 2"),
+        }
+
+        // A missing genesis is never tolerated
+        assert!(BreadcrumbChain::from_breadcrumbs_with_tolerance(
+            chain_with_dropped(&[0], true), lenient,
+        ).is_err());
+    }
+
+    #[test]
+    fn test_radius_of_gyration() {
+        // Two cells in central Rome, a few hundred meters apart
+        let local: Vec<(i64, &str)> = (0..40)
+            .map(|i| (1_700_000_000 + i * 900, if i % 2 == 0 { "8a1e8052da1ffff" } else { "8a1e8052d85ffff" }))
+            .collect();
+        let local = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&local)).unwrap();
+        let local_rg = local.radius_of_gyration();
+        assert!(local_rg > 0.0 && local_rg < 1.0, "rg = {local_rg}");
+
+        // Rome <-> Naples is about 190 km, so rg is about half that
+        let rome = h3o::LatLng::new(41.9028, 12.4964).unwrap().to_cell(h3o::Resolution::Ten);
+        let naples = h3o::LatLng::new(40.8518, 14.2681).unwrap().to_cell(h3o::Resolution::Ten);
+        let (rome, naples) = (format!("{:x}", u64::from(rome)), format!("{:x}", u64::from(naples)));
+        let travel: Vec<(i64, &str)> = (0..40)
+            .map(|i| (1_700_000_000 + i * 900, if i % 2 == 0 { rome.as_str() } else { naples.as_str() }))
+            .collect();
+        let travel = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&travel)).unwrap();
+        let travel_rg = travel.radius_of_gyration();
+        assert!((travel_rg - 94.0).abs() < 5.0, "rg = {travel_rg}");
+    }
+
+    #[test]
+    fn test_dwell_ratio_charger_vs_active() {
+        // Active human: moves every 30 minutes by day, sleeps 8 hours at home
+        let (home, work) = ("8a1e8052da1ffff", "8a1e8052d85ffff");
+        let mut samples: Vec<(i64, &str)> = Vec::new();
+        let mut t = 1_700_000_000i64;
+        for _day in 0..7 {
+            for i in 0..32 {
+                samples.push((t, if i % 4 == 0 { home } else { work }));
+                t += 1800;
+            }
+            t += 8 * 3600;
+        }
+        let active = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+        let report = active.dwell_report();
+        assert_eq!(report.long_intervals, 6);
+        assert!(!report.is_low_liveness(), "share = {}", report.stationary_share());
+
+        // Charger-bound phone: one breadcrumb a day, never moves
+        let samples: Vec<(i64, &str)> = (0..30).map(|i| (1_700_000_000 + i * 86_400, home)).collect();
+        let charger = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+        let report = charger.dwell_report();
+        assert_eq!(report.dwell_ratio(), 1.0);
+        assert!(report.is_low_liveness());
+    }
+
+    #[test]
+    fn test_context_report_identical_vs_unique() {
+        use crate::criticality::CriticalityEngine;
+
+        let live = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 3)).unwrap();
+        let report = live.context_report();
+        assert_eq!((report.repeated, report.unique, report.total), (0, 200, 200));
+        assert!(!report.is_low_liveness());
+
+        // One captured sensor context, replayed on every breadcrumb
+        let mut breadcrumbs = random_walk_breadcrumbs(200, 3);
+        for b in &mut breadcrumbs {
+            b.context_digest = "cd".repeat(32);
+        }
+        let scripted = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        let report = scripted.context_report();
+        assert_eq!(report.repeat_share(), 1.0);
+        assert_eq!(report.uniqueness_ratio(), 1.0 / 200.0);
+        assert!(report.is_low_liveness());
+
+        let engine = CriticalityEngine::with_defaults();
+        assert!(engine.evaluate(&live).unwrap().verdict.liveness_pass);
+        let result = engine.evaluate(&scripted).unwrap();
+        assert!(!result.verdict.liveness_pass);
+        assert!(!result.is_human);
+        assert!(result.verdict.summary.contains("repetitive context"), "{}", result.verdict.summary);
+    }
+
+    /// `n` breadcrumbs at 10-60 minute intervals, positions from `place`.
+    fn placed_chain(n: usize, seed: u64, place: impl Fn(&mut rand::rngs::StdRng) -> (f64, f64)) -> BreadcrumbChain {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut t = 1_700_000_000i64;
+        let samples: Vec<(i64, String)> = (0..n)
+            .map(|_| {
+                let (lat, lng) = place(&mut rng);
+                let cell = h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten);
+                t += rng.gen_range(600..3600);
+                (t, format!("{:x}", u64::from(cell)))
+            })
+            .collect();
+        let refs: Vec<(i64, &str)> = samples.iter().map(|(t, c)| (*t, c.as_str())).collect();
+        BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&refs)).unwrap()
+    }
+
+    #[test]
+    fn test_spatial_clusters_clustered_vs_uniform() {
+        use crate::criticality::CriticalityEngine;
+        use rand::Rng;
+
+        // Home, work and gym, each visited with ~200 m of GPS jitter
+        let anchors = [(41.9028, 12.4964), (41.8902, 12.4922), (41.9109, 12.4818)];
+        let clustered = placed_chain(300, 5, |rng| {
+            let (lat, lng) = anchors[rng.gen_range(0..anchors.len())];
+            (lat + rng.gen_range(-0.0015..0.0015), lng + rng.gen_range(-0.002..0.002))
+        });
+        let clusters = clustered.spatial_clusters(0.5, 5);
+        assert_eq!(clusters.len(), 3, "{clusters:?}");
+        assert_eq!(clusters.iter().map(|c| c.size).sum::<usize>(), 300);
+        assert!(clusters.windows(2).all(|w| w[0].size >= w[1].size));
+        for &(lat, lng) in &anchors {
+            assert!(clusters.iter().any(|c| {
+                (c.centroid.0 - lat).abs() < 0.001 && (c.centroid.1 - lng).abs() < 0.001
+            }));
+        }
+        assert_eq!(noise_fraction(&clusters, clustered.len()), 0.0);
+
+        // Uniform over a 30 × 30 km box: nothing dense enough to cluster
+        let uniform = placed_chain(300, 5, |rng| {
+            (41.77 + rng.gen_range(0.0..0.27), 12.32 + rng.gen_range(0.0..0.36))
+        });
+        let clusters = uniform.spatial_clusters(0.5, 5);
+        assert!(noise_fraction(&clusters, uniform.len()) > 0.95, "{clusters:?}");
+
+        let engine = CriticalityEngine::with_defaults();
+        assert!(engine.evaluate(&clustered).unwrap().verdict.cluster_structure_pass);
+        let result = engine.evaluate(&uniform).unwrap();
+        assert!(!result.verdict.cluster_structure_pass);
+        assert!(result.verdict.summary.contains("no place clusters"), "{}", result.verdict.summary);
+    }
+
+    #[test]
+    fn test_anonymized_vector_keeps_psd_classification() {
+        use crate::psd::compute_psd_from_chain;
+
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(256, 11)).unwrap();
+        let vector = chain.to_anonymized_vector_with_rng(&mut StdRng::seed_from_u64(3));
+
+        assert_ne!(vector.breadcrumbs[0].identity_public_key, chain.identity);
+        assert!(vector.breadcrumbs.iter().zip(&chain.breadcrumbs)
+            .all(|(a, b)| a.location_cell != b.location_cell));
+
+        let anonymized = BreadcrumbChain::from_breadcrumbs(vector.breadcrumbs).unwrap();
+        anonymized.verify_signatures().unwrap();
+        anonymized.verify_block_hashes().unwrap();
+
+        let original = compute_psd_from_chain(&chain.displacement_series(), &chain.interval_series()).unwrap();
+        let copy = compute_psd_from_chain(&anonymized.displacement_series(), &anonymized.interval_series()).unwrap();
+        assert_eq!(copy.classification, original.classification);
+        assert!((copy.alpha - original.alpha).abs() < 0.02, "{} vs {}", copy.alpha, original.alpha);
+    }
+
+    #[test]
+    fn test_compressibility_patterned_vs_organic() {
+        let organic = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+
+        // A commute loop through six cells on a fixed 15 minute clock
+        let mut walk = random_walk_breadcrumbs(6, 5);
+        let cells: Vec<String> = walk.drain(..).map(|b| b.location_cell).collect();
+        let samples: Vec<(i64, &str)> = (0..300)
+            .map(|i| (1_700_000_000 + 900 * i as i64, cells[i % cells.len()].as_str()))
+            .collect();
+        let patterned = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        let (organic, patterned) = (organic.compressibility_ratio(), patterned.compressibility_ratio());
+        assert!(organic < HIGH_COMPRESSIBILITY_RATIO, "organic ratio {organic}");
+        assert!(patterned > HIGH_COMPRESSIBILITY_RATIO, "patterned ratio {patterned}");
+        assert!(patterned > 5.0 * organic, "patterned {patterned} vs organic {organic}");
+    }
+
+    #[test]
+    fn test_mobility_signature_ignores_identity() {
+        let chain = |breadcrumbs| BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        let walk = random_walk_breadcrumbs(100, 5);
+
+        let mut alice = walk.clone();
+        sign_breadcrumbs(&mut alice, &ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]));
+        let mut mallory = walk.clone();
+        for b in &mut mallory {
+            b.timestamp += chrono::Duration::days(1);
+        }
+        sign_breadcrumbs(&mut mallory, &ed25519_dalek::SigningKey::from_bytes(&[2u8; 32]));
+        let (alice, mallory) = (chain(alice), chain(mallory));
+        assert_ne!(alice.identity, mallory.identity);
+        assert_eq!(alice.mobility_signature(), mallory.mobility_signature());
+
+        let other = chain(random_walk_breadcrumbs(100, 6));
+        assert_ne!(other.mobility_signature(), alice.mobility_signature());
+
+        // One interval a minute longer is a different trajectory
+        let mut late = walk;
+        for b in &mut late[50..] {
+            b.timestamp += chrono::Duration::minutes(1);
+        }
+        assert_ne!(chain(late).mobility_signature(), alice.mobility_signature());
+    }
 }
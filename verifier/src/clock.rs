@@ -0,0 +1,57 @@
+// trip-verifier/src/clock.rs
+//
+// Time Source
+// ===========
+//
+// Certificate issuance, challenge deadlines and validity checks read
+// the time through a `Clock` instead of calling `Utc::now()` directly.
+// The default `SystemClock` is the wall clock; a `FixedClock` pins
+// time so tests can step across an expiry boundary deterministically,
+// and so a historical verification can be replayed at the time it
+// originally ran.
+
+use std::sync::Mutex;
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+
+    /// Jump to `now`.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Move forward (or back, for a negative `by`).
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,433 @@
+// trip-verifier/src/compact.rs
+//
+// Compact Binary PoH Certificate
+// ================================
+//
+// A fixed-layout, signed encoding of a PoH Certificate small
+// enough for a QR code (target: under ~300 bytes), for offline
+// human-to-human trust sharing.
+//
+// Size reductions versus the CBOR form:
+// - Identity and Verifier keys are replaced by their 16-byte HITs
+//   (SHA-256(key)[0:16]); the reader resolves the Verifier key
+//   from its own key index.
+// - Floats are stored as fixed-point integers.
+//
+// Integers and issued_at are carried exactly: a value the layout
+// can't hold (issued_at before 1970, a u64 count above u32::MAX) is
+// an error rather than clamped. The validity is held to the same
+// `ValidityPolicy` cap as the CBOR form, both when signing and when
+// verifying.
+//
+// Because quantization changes the values, the compact blob is
+// signed separately by the Verifier over its own bytes.
+//
+// Layout (big-endian):
+//   version            u8      (0x01)
+//   flags              u8      (bit 0: nonce, bit 1: chain_head_hash)
+//   identity_hit       [16]
+//   verifier_hit       [16]
+//   alpha              i16     (× 1/1000)
+//   beta               i16     (× 1/1000)
+//   kappa              u32     (meters)
+//   trust_score        u8
+//   confidence         u16     (× 1/10000)
+//   chain_length       u32
+//   unique_cells       u32
+//   mean_hamiltonian   u16     (× 1/10000)
+//   issued_at          u64     (Unix seconds)
+//   valid_seconds      u32
+//   nonce              [16]    (if flag bit 0)
+//   chain_head_hash    [32]    (if flag bit 1)
+//   signature          [64]    (Ed25519 over all preceding bytes)
+
+use chrono::{DateTime, TimeZone, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use crate::certificate::{
+    DEFAULT_CLOCK_SKEW_SECONDS, PoHCertificate, ValidityPolicy, decode_hex_field, expiry, within_validity,
+};
+use crate::error::{TripError, Result};
+
+/// Compact format version byte.
+pub const COMPACT_VERSION: u8 = 0x01;
+
+/// Size budget for a compact certificate (bytes).
+pub const COMPACT_SIZE_BUDGET: usize = 300;
+
+const FLAG_NONCE: u8 = 0x01;
+const FLAG_CHAIN_HEAD: u8 = 0x02;
+
+/// Decoded compact certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactCertificate {
+    pub identity_hit: [u8; 16],
+    pub verifier_hit: [u8; 16],
+    pub alpha: f64,
+    pub beta: f64,
+    pub kappa: f64,
+    pub trust_score: u8,
+    pub confidence: f64,
+    pub chain_length: u32,
+    pub unique_cells: u32,
+    pub mean_hamiltonian: f64,
+    pub issued_at: DateTime<Utc>,
+    pub valid_seconds: u32,
+    pub nonce: Option<[u8; 16]>,
+    pub chain_head_hash: Option<[u8; 32]>,
+    pub signature: [u8; 64],
+    /// The signed bytes (everything before the signature)
+    signed_bytes: Vec<u8>,
+}
+
+impl PoHCertificate {
+    /// Encode to the compact binary form, signed with the Verifier key.
+    ///
+    /// The signing key must match `verifier_key`, and `valid_seconds`
+    /// must be within `DEFAULT_MAX_VALID_SECONDS`, as for `sign`.
+    pub fn to_compact_binary(&self, verifier_signing_key: &SigningKey) -> Result<Vec<u8>> {
+        self.to_compact_binary_with_policy(verifier_signing_key, &ValidityPolicy::default())
+    }
+
+    /// Like `to_compact_binary`, with `valid_seconds` within `policy`'s
+    /// `max_valid_seconds`.
+    pub fn to_compact_binary_with_policy(
+        &self,
+        verifier_signing_key: &SigningKey,
+        policy: &ValidityPolicy,
+    ) -> Result<Vec<u8>> {
+        policy.check(self.valid_seconds)?;
+        let verifier_key = decode_hex_field(&self.verifier_key, "verifier_key")?;
+        if verifier_signing_key.verifying_key().to_bytes() != verifier_key {
+            return Err(TripError::CertificateError(
+                "Signing key does not match verifier_key".to_string()
+            ));
+        }
+        let identity_key = decode_hex_field(&self.identity_key, "identity_key")?;
+
+        let mut flags = 0u8;
+        if self.nonce.is_some() {
+            flags |= FLAG_NONCE;
+        }
+        if self.chain_head_hash.is_some() {
+            flags |= FLAG_CHAIN_HEAD;
+        }
+
+        let mut buf = Vec::with_capacity(COMPACT_SIZE_BUDGET);
+        buf.push(COMPACT_VERSION);
+        buf.push(flags);
+        buf.extend_from_slice(&hit(&identity_key));
+        buf.extend_from_slice(&hit(&verifier_key));
+        buf.extend_from_slice(&to_fixed_i16(self.alpha, 1000.0).to_be_bytes());
+        buf.extend_from_slice(&to_fixed_i16(self.beta, 1000.0).to_be_bytes());
+        buf.extend_from_slice(&to_fixed_u32(self.kappa, 1000.0).to_be_bytes());
+        buf.push(self.trust_score.clamp(0.0, 100.0) as u8);
+        buf.extend_from_slice(&to_fixed_u16(self.confidence, 10_000.0).to_be_bytes());
+        buf.extend_from_slice(&encodable::<u32, _>(self.chain_length, "chain_length")?.to_be_bytes());
+        buf.extend_from_slice(&encodable::<u32, _>(self.unique_cells, "unique_cells")?.to_be_bytes());
+        buf.extend_from_slice(&to_fixed_u16(self.mean_hamiltonian, 10_000.0).to_be_bytes());
+        buf.extend_from_slice(&encodable::<u64, _>(self.issued_at.timestamp(), "issued_at")?.to_be_bytes());
+        buf.extend_from_slice(&encodable::<u32, _>(self.valid_seconds, "valid_seconds")?.to_be_bytes());
+
+        if let Some(ref nonce) = self.nonce {
+            let nonce: [u8; 16] = nonce.as_slice().try_into()
+                .map_err(|_| TripError::CertificateError("Nonce must be 16 bytes".to_string()))?;
+            buf.extend_from_slice(&nonce);
+        }
+        if let Some(ref hash) = self.chain_head_hash {
+            let hash: [u8; 32] = decode_hex_field(hash, "chain_head_hash")?;
+            buf.extend_from_slice(&hash);
+        }
+
+        let signature = verifier_signing_key.sign(&buf);
+        buf.extend_from_slice(&signature.to_bytes());
+        Ok(buf)
+    }
+}
+
+impl CompactCertificate {
+    /// Decode a compact binary certificate. Does not verify the signature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader { bytes, pos: 0 };
+
+        let version = r.u8()?;
+        if version != COMPACT_VERSION {
+            return Err(TripError::CertificateError(
+                format!("Unsupported compact version: {version:#04x}")
+            ));
+        }
+        let flags = r.u8()?;
+
+        let identity_hit = r.array::<16>()?;
+        let verifier_hit = r.array::<16>()?;
+        let alpha = i16::from_be_bytes(r.array()?) as f64 / 1000.0;
+        let beta = i16::from_be_bytes(r.array()?) as f64 / 1000.0;
+        let kappa = u32::from_be_bytes(r.array()?) as f64 / 1000.0;
+        let trust_score = r.u8()?;
+        let confidence = u16::from_be_bytes(r.array()?) as f64 / 10_000.0;
+        let chain_length = u32::from_be_bytes(r.array()?);
+        let unique_cells = u32::from_be_bytes(r.array()?);
+        let mean_hamiltonian = u16::from_be_bytes(r.array()?) as f64 / 10_000.0;
+        let issued_secs = u64::from_be_bytes(r.array()?);
+        let valid_seconds = u32::from_be_bytes(r.array()?);
+
+        let nonce = if flags & FLAG_NONCE != 0 { Some(r.array::<16>()?) } else { None };
+        let chain_head_hash = if flags & FLAG_CHAIN_HEAD != 0 { Some(r.array::<32>()?) } else { None };
+
+        let signed_len = r.pos;
+        let signature = r.array::<64>()?;
+        if r.pos != bytes.len() {
+            return Err(TripError::CertificateError(
+                format!("Trailing bytes in compact certificate: {}", bytes.len() - r.pos)
+            ));
+        }
+
+        let issued_at = i64::try_from(issued_secs).ok()
+            .and_then(|s| Utc.timestamp_opt(s, 0).single())
+            .ok_or_else(|| TripError::CertificateError("Invalid issued_at".to_string()))?;
+
+        Ok(Self {
+            identity_hit,
+            verifier_hit,
+            alpha,
+            beta,
+            kappa,
+            trust_score,
+            confidence,
+            chain_length,
+            unique_cells,
+            mean_hamiltonian,
+            issued_at,
+            valid_seconds,
+            nonce,
+            chain_head_hash,
+            signature,
+            signed_bytes: bytes[..signed_len].to_vec(),
+        })
+    }
+
+    /// Check the Verifier signature and validity now, within the
+    /// default clock skew and validity cap. The key is resolved by the
+    /// caller (e.g. from `verifier_hit`) and must hash to that HIT.
+    pub fn verify(&self, verifier_key: &VerifyingKey) -> Result<()> {
+        self.verify_at(verifier_key, Utc::now(), DEFAULT_CLOCK_SKEW_SECONDS)
+    }
+
+    /// Check the Verifier signature and validity at `now`, within
+    /// `clock_skew_seconds`. A validity over `DEFAULT_MAX_VALID_SECONDS`
+    /// fails too.
+    pub fn verify_at(&self, verifier_key: &VerifyingKey, now: DateTime<Utc>, clock_skew_seconds: u64) -> Result<()> {
+        self.verify_at_with_policy(verifier_key, now, clock_skew_seconds, &ValidityPolicy::default())
+    }
+
+    /// Like `verify_at`, accepting validities up to `policy`'s
+    /// `max_valid_seconds`.
+    pub fn verify_at_with_policy(
+        &self,
+        verifier_key: &VerifyingKey,
+        now: DateTime<Utc>,
+        clock_skew_seconds: u64,
+        policy: &ValidityPolicy,
+    ) -> Result<()> {
+        self.verify_signature(verifier_key)?;
+        policy.check(self.valid_seconds as u64)?;
+        if !within_validity(self.issued_at, self.valid_seconds as u64, now, clock_skew_seconds) {
+            return Err(TripError::CertificateError(format!(
+                "Certificate not valid at {now}: valid {} to {}",
+                self.issued_at, self.expires_at()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Expiry timestamp (issued_at + valid_seconds).
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        expiry(self.issued_at, self.valid_seconds as u64)
+    }
+
+    /// Check the Verifier signature only. The key must hash to
+    /// `verifier_hit`.
+    pub fn verify_signature(&self, verifier_key: &VerifyingKey) -> Result<()> {
+        if hit(&verifier_key.to_bytes()) != self.verifier_hit {
+            return Err(TripError::CertificateError(
+                "Verifier key does not match verifier_hit".to_string()
+            ));
+        }
+        let signature = Signature::from_bytes(&self.signature);
+        verifier_key.verify(&self.signed_bytes, &signature)
+            .map_err(|_| TripError::CertificateError("Invalid compact signature".to_string()))
+    }
+
+    /// Does this certificate belong to the given identity key?
+    pub fn matches_identity(&self, identity_key: &[u8; 32]) -> bool {
+        hit(identity_key) == self.identity_hit
+    }
+}
+
+// ========================================================================
+// Internal helpers
+// ========================================================================
+
+/// HIT = SHA-256(key)[0:16]
+pub(crate) fn hit(key: &[u8; 32]) -> [u8; 16] {
+    let hash = Sha256::digest(key);
+    let mut out = [0u8; 16];
+    out.copy_from_slice(&hash[..16]);
+    out
+}
+
+/// `value` as the layout's integer type; the signed fields are
+/// carried exactly or not at all.
+fn encodable<T: TryFrom<V>, V: Copy + std::fmt::Display>(value: V, field: &str) -> Result<T> {
+    T::try_from(value).map_err(|_| TripError::CertificateError(
+        format!("{field} {value} out of range for the compact format")
+    ))
+}
+
+fn to_fixed_i16(x: f64, scale: f64) -> i16 {
+    (x * scale).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+fn to_fixed_u16(x: f64, scale: f64) -> u16 {
+    (x * scale).round().clamp(0.0, u16::MAX as f64) as u16
+}
+
+fn to_fixed_u32(x: f64, scale: f64) -> u32 {
+    (x * scale).round().clamp(0.0, u32::MAX as f64) as u32
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let end = self.pos + N;
+        let slice = self.bytes.get(self.pos..end).ok_or_else(|| {
+            TripError::CertificateError("Truncated compact certificate".to_string())
+        })?;
+        self.pos = end;
+        Ok(slice.try_into().expect("slice length checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certificate::tests::test_cert;
+
+    fn signed_compact() -> (Vec<u8>, SigningKey, PoHCertificate) {
+        let identity = SigningKey::from_bytes(&[1u8; 32]);
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let cert = PoHCertificate {
+            identity_key: hex::encode(identity.verifying_key().to_bytes()),
+            alpha: 0.5512,
+            beta: 1.0347,
+            kappa: 42.125,
+            confidence: 0.9213,
+            chain_length: 512,
+            unique_cells: 87,
+            mean_hamiltonian: 0.1834,
+            verifier_key: hex::encode(verifier.verifying_key().to_bytes()),
+            issued_at: Utc.timestamp_opt(1_750_000_000, 0).unwrap(),
+            nonce: Some(vec![9u8; 16]),
+            chain_head_hash: Some("cd".repeat(32)),
+            ..test_cert(78.4)
+        };
+        let bytes = cert.to_compact_binary(&verifier).unwrap();
+        (bytes, verifier, cert)
+    }
+
+    #[test]
+    fn test_compact_roundtrip_verifies_within_budget() {
+        let (bytes, verifier, cert) = signed_compact();
+        assert!(bytes.len() < COMPACT_SIZE_BUDGET, "compact size {}", bytes.len());
+
+        let compact = CompactCertificate::from_bytes(&bytes).unwrap();
+        compact.verify_at(&verifier.verifying_key(), cert.issued_at, DEFAULT_CLOCK_SKEW_SECONDS).unwrap();
+
+        let identity_key: [u8; 32] = hex::decode(&cert.identity_key).unwrap().try_into().unwrap();
+        assert!(compact.matches_identity(&identity_key));
+        assert!((compact.alpha - cert.alpha).abs() < 1e-3);
+        assert!((compact.beta - cert.beta).abs() < 1e-3);
+        assert!((compact.kappa - cert.kappa).abs() < 1e-3);
+        assert_eq!(compact.trust_score, 78);
+        assert_eq!(compact.chain_length, 512);
+        assert_eq!(compact.issued_at, cert.issued_at);
+        assert_eq!(compact.nonce, Some([9u8; 16]));
+    }
+
+    #[test]
+    fn test_compact_tamper_detected() {
+        let (mut bytes, verifier, _) = signed_compact();
+        bytes[34] ^= 0x01; // inside alpha
+        let compact = CompactCertificate::from_bytes(&bytes).unwrap();
+        assert!(compact.verify_signature(&verifier.verifying_key()).is_err());
+
+        let other = SigningKey::from_bytes(&[3u8; 32]);
+        let (bytes, _, _) = signed_compact();
+        let compact = CompactCertificate::from_bytes(&bytes).unwrap();
+        assert!(compact.verify_signature(&other.verifying_key()).is_err());
+
+        assert!(CompactCertificate::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_compact_rejects_unencodable_values() {
+        let (_, verifier, cert) = signed_compact();
+        // Past the validity cap's reach, so the layout is what fails
+        let uncapped = ValidityPolicy { max_valid_seconds: u64::MAX, ..ValidityPolicy::default() };
+        let out_of_range = |cert: PoHCertificate, field: &str| matches!(
+            cert.to_compact_binary_with_policy(&verifier, &uncapped),
+            Err(TripError::CertificateError(msg)) if msg.starts_with(field)
+        );
+
+        let before_epoch = Utc.timestamp_opt(-1, 0).unwrap();
+        assert!(out_of_range(PoHCertificate { issued_at: before_epoch, ..cert.clone() }, "issued_at"));
+        let too_long = u32::MAX as u64 + 1;
+        assert!(out_of_range(PoHCertificate { valid_seconds: too_long, ..cert.clone() }, "valid_seconds"));
+        assert!(out_of_range(PoHCertificate { chain_length: too_long, ..cert.clone() }, "chain_length"));
+        assert!(out_of_range(PoHCertificate { unique_cells: too_long, ..cert }, "unique_cells"));
+    }
+
+    #[test]
+    fn test_compact_validity_capped_and_checked() {
+        let (bytes, verifier, cert) = signed_compact();
+        let key = verifier.verifying_key();
+        let over_cap = |r: Result<Vec<u8>>| matches!(
+            r, Err(TripError::CertificateError(msg)) if msg.contains("exceeds")
+        );
+
+        // Ten years is never signed, compact or not
+        let ten_years = PoHCertificate { valid_seconds: 10 * 365 * 86_400, ..cert.clone() };
+        assert!(over_cap(ten_years.to_compact_binary(&verifier)));
+        assert!(over_cap(ten_years.to_compact_binary_with_policy(&verifier, &ValidityPolicy::default())));
+
+        // A longer cap signs, but only a reader sharing it accepts
+        let policy = ValidityPolicy { max_valid_seconds: 30 * 86_400, ..ValidityPolicy::default() };
+        let month = PoHCertificate { valid_seconds: 30 * 86_400, ..cert.clone() };
+        assert!(over_cap(month.to_compact_binary(&verifier)));
+        let long = CompactCertificate::from_bytes(&month.to_compact_binary_with_policy(&verifier, &policy).unwrap()).unwrap();
+        let issued = cert.issued_at;
+        assert!(matches!(
+            long.verify_at(&key, issued, DEFAULT_CLOCK_SKEW_SECONDS),
+            Err(TripError::CertificateError(msg)) if msg.contains("exceeds")
+        ));
+        long.verify_at_with_policy(&key, issued, DEFAULT_CLOCK_SKEW_SECONDS, &policy).unwrap();
+
+        // Expiry, within the clock skew
+        let compact = CompactCertificate::from_bytes(&bytes).unwrap();
+        let secs = chrono::Duration::seconds;
+        assert_eq!(compact.expires_at(), issued + secs(3600));
+        compact.verify_at(&key, compact.expires_at() + secs(10), DEFAULT_CLOCK_SKEW_SECONDS).unwrap();
+        assert!(compact.verify_at(&key, compact.expires_at(), 0).is_err());
+        assert!(compact.verify_at(&key, issued - secs(60), DEFAULT_CLOCK_SKEW_SECONDS).is_err());
+        // Issued in 2025 for an hour: long expired now
+        assert!(compact.verify(&key).is_err());
+    }
+}
@@ -10,14 +10,33 @@
 //
 // This is the RATS Verifier logic.
 
-use crate::chain::BreadcrumbChain;
-use crate::psd::{self, PsdResult};
+use crate::breadcrumb::{DistanceMode, compute_displacements};
+use crate::calibration::CalibrationTable;
+use crate::chain::{
+    AccuracyReport, BreadcrumbChain, DwellReport, MIN_BREADCRUMB_INTERVAL_SECS, effective_chain_length,
+    is_poor_accuracy, noise_fraction,
+};
+use crate::psd::{
+    self, PathPsdResult, PsdClassification, PsdConfig, PsdResult, DEFAULT_MIN_PATH_ALPHA_EXCESS,
+};
 use crate::levy::{self, LevyResult};
+use crate::replay::{self, ReplayResult};
+use crate::diurnal;
+use crate::waiting::{self, WaitingTimeResult};
 use crate::hamiltonian::{
-    self, BehavioralProfile, ChainHamiltonianResult,
-    HamiltonianWeights,
+    self, AnomalyScorer, BehavioralProfile, ChainHamiltonianResult,
+    HamiltonianWeights, ProfileConfig,
 };
-use crate::error::{TripError, Result};
+use crate::privacy::PrivacyReport;
+use crate::clock::{Clock, SystemClock};
+use crate::liveness::{LivenessPolicy, LivenessReport};
+use crate::verification::FailureReason;
+use crate::error::{TripError, Result, ensure_finite};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use rand::{SeedableRng, rngs::StdRng};
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
 
 /// Minimum breadcrumbs required for meaningful analysis.
 /// Per TRIP spec Section 6.4 (Convergence Analysis):
@@ -26,37 +45,557 @@ use crate::error::{TripError, Result};
 pub const MIN_BREADCRUMBS_PSD: usize = 64;
 pub const MIN_BREADCRUMBS_CONFIDENT: usize = 200;
 
+/// Largest coefficient of variation (std / mean) of the step lengths
+/// at which a chain counts as stepping a constant distance. GPS jitter
+/// and H3 quantization spread a fixed step by a few percent; human
+/// step lengths, heavy-tailed, vary by more than their mean.
+pub const CONSTANT_STEP_MAX_CV: f64 = 0.1;
+
+/// Three-way classification of a chain.
+///
+/// `Inconclusive` means "not enough data yet", not "not human":
+/// applications should ask the user to keep collecting breadcrumbs
+/// rather than reject them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Human,
+    NotHuman,
+    /// Confidence below `min_confidence`, or a required analysis
+    /// (PSD, Lévy fit) had too little data to run.
+    Inconclusive,
+}
+
+impl Outcome {
+    fn from_flags(is_human: bool, is_inconclusive: bool) -> Self {
+        if is_human {
+            Self::Human
+        } else if is_inconclusive {
+            Self::Inconclusive
+        } else {
+            Self::NotHuman
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Human => "HUMAN",
+            Self::NotHuman => "NOT VERIFIED",
+            Self::Inconclusive => "INCONCLUSIVE",
+        }
+    }
+}
+
+/// How to treat a PSD α just above the pink-noise band
+/// (`PsdClassification::StrongCorrelation`).
+///
+/// Highly routine people — the same commute every day — can produce
+/// stronger-than-pink correlations. Accepting them trades a lower
+/// false-rejection rate for a higher false-acceptance rate: scripted
+/// movers and lightly-noised replays also land in this band, so
+/// accepted chains get no PSD credit in the trust score and a
+/// reduced confidence.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum StrongCorrelationPolicy {
+    /// α above `alpha_max` fails the PSD check.
+    #[default]
+    Reject,
+    /// α ∈ (`alpha_max`, `alpha_ceiling`] passes the PSD check,
+    /// with confidence scaled by `confidence_factor`.
+    AcceptReduced {
+        alpha_ceiling: f64,
+        confidence_factor: f64,
+    },
+}
+
+impl StrongCorrelationPolicy {
+    /// Accept the whole StrongCorrelation band (α ≤ 1.5) at 75% confidence.
+    pub fn widened() -> Self {
+        Self::AcceptReduced { alpha_ceiling: 1.50, confidence_factor: 0.75 }
+    }
+}
+
+/// How to treat a chain that (almost) never leaves one cell.
+///
+/// A homebound person produces all-zero displacements: the Lévy fit
+/// has nothing above x_min and the PSD sees a flat, white-noise-like
+/// signal, so the mobility analyses would call a real human a bot.
+/// Under `Inconclusive` they are skipped and reported as
+/// `NotMeasured`. The verdict then rests on liveness (long stationary
+/// dwells don't dominate, see `DwellReport`), the day/night cycle and
+/// the Hamiltonian energy: a chain passing all three is Inconclusive
+/// rather than NotHuman, with trust capped at `trust_ceiling`, and
+/// failing any of them is NotHuman as before. A static bot sampling
+/// around the clock has no day/night cycle, so the policy never
+/// softens its verdict. Without mobility evidence a stationary chain
+/// is never Human.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StationaryPolicy {
+    /// Run the mobility analyses regardless
+    Analyze,
+    /// Skip them when at most `max_moving_share` of displacements
+    /// are non-zero
+    Inconclusive {
+        max_moving_share: f64,
+        trust_ceiling: f64,
+    },
+}
+
+impl Default for StationaryPolicy {
+    fn default() -> Self {
+        Self::Inconclusive { max_moving_share: 0.05, trust_ceiling: 30.0 }
+    }
+}
+
+impl StationaryPolicy {
+    /// Trust ceiling if `displacements` count as stationary under
+    /// this policy.
+    fn trust_ceiling(&self, displacements: &[f64]) -> Option<f64> {
+        let Self::Inconclusive { max_moving_share, trust_ceiling } = *self else {
+            return None;
+        };
+        let moving = displacements.iter().filter(|&&d| d > 0.0).count();
+        (moving as f64 <= max_moving_share * displacements.len() as f64).then_some(trust_ceiling)
+    }
+}
+
+/// Default characteristic convergence length τ (breadcrumbs).
+pub const DEFAULT_CONFIDENCE_TAU: f64 = 200.0;
+
+/// Confidence as a function of chain length, modelling the
+/// convergence of the statistical estimators.
+///
+/// Choosing τ: it is the chain length at which confidence reaches
+/// 1 − 1/e ≈ 0.63 (0.95 at 3τ). The default of 200 was set for the
+/// spec's typical cadence of one breadcrumb every 10–60 minutes,
+/// where 200 samples span several days of routine. What converges is
+/// a mix of sample count (PSD and Lévy precision, see
+/// `psd::samples_for_alpha_precision`) and days observed (routine,
+/// day/night cycle):
+/// - Dense cadences (every 10 min) pack many correlated samples into
+///   one day; scale τ up so τ samples still cover about three days,
+///   e.g. τ ≈ 430 at 10 minutes.
+/// - Sparse cadences (every 24 h) cover a day per sample, but the
+///   estimators still need the samples: keep 3τ at or above the
+///   `samples_for_alpha_precision` of the α precision you require
+///   rather than shrinking τ with the interval.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ConfidenceModel {
+    /// c(n) = 1 − exp(−n/τ)
+    Exponential { tau: f64 },
+    /// c(n) = n / (n + half_length): reaches 0.5 at `half_length`
+    /// and saturates more slowly than the exponential, for
+    /// deployments that want long chains to keep earning confidence
+    Saturating { half_length: f64 },
+}
+
+impl Default for ConfidenceModel {
+    fn default() -> Self {
+        Self::Exponential { tau: DEFAULT_CONFIDENCE_TAU }
+    }
+}
+
+impl ConfidenceModel {
+    /// Confidence [0, 1] for a chain of `chain_length` breadcrumbs.
+    pub fn confidence(&self, chain_length: usize) -> f64 {
+        self.confidence_for(chain_length as f64)
+    }
+
+    /// Confidence [0, 1] for `n` independent samples, e.g. a chain's
+    /// `BreadcrumbChain::effective_sample_size`.
+    pub fn confidence_for(&self, n: f64) -> f64 {
+        match *self {
+            Self::Exponential { tau } => 1.0 - (-n / tau).exp(),
+            Self::Saturating { half_length } => n / (n + half_length),
+        }
+    }
+}
+
+/// Whether to cross-check the displacement PSD against the PSD of the
+/// path itself (see `psd::path_cross_check`).
+///
+/// The check catches replays that keep a human-like displacement
+/// sequence while retracing the same cells. A mismatch is flagged on
+/// `CriticalityResult::path_psd`; like replay periodicity it does not
+/// change the outcome by itself. Needs cells, so displacement-only
+/// evaluation never runs it.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PathPsdPolicy {
+    /// Only the displacement PSD is computed
+    #[default]
+    Skip,
+    /// Also compute the path PSD; flag α(path) − α(displacement)
+    /// below `min_alpha_excess`
+    CrossCheck { min_alpha_excess: f64 },
+}
+
+impl PathPsdPolicy {
+    /// Cross-check with `DEFAULT_MIN_PATH_ALPHA_EXCESS`.
+    pub fn cross_check() -> Self {
+        Self::CrossCheck { min_alpha_excess: DEFAULT_MIN_PATH_ALPHA_EXCESS }
+    }
+}
+
+/// Place-cluster check (see `BreadcrumbChain::spatial_clusters`).
+///
+/// People organize their movement around a few places, so most of a
+/// human chain falls in dense clusters; a bot wandering at random
+/// leaves most of its breadcrumbs as noise. A chain whose noise
+/// fraction exceeds `max_noise_fraction` is flagged on
+/// `Verdict::cluster_structure_pass`; like replay periodicity it does
+/// not change the outcome by itself. Needs cells, so displacement-only
+/// evaluation never runs it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClusterPolicy {
+    /// Neighbourhood radius (km)
+    pub eps_km: f64,
+    /// Breadcrumbs within `eps_km` that make a place dense
+    pub min_points: usize,
+    /// Largest share of breadcrumbs outside every cluster
+    pub max_noise_fraction: f64,
+}
+
+impl Default for ClusterPolicy {
+    fn default() -> Self {
+        Self { eps_km: 0.5, min_points: 5, max_noise_fraction: 0.8 }
+    }
+}
+
+/// How `evaluate` treats breadcrumbs the user added by hand
+/// (`MetaFlags::manual`).
+///
+/// A manual breadcrumb is strong evidence of a live user, but it lands
+/// off the automatic sampling rhythm: an extra short interval and a
+/// displacement the PSD and Hamiltonian did not expect. `Exclude` runs
+/// the mobility analyses (PSD, Lévy, waiting times, Hamiltonian) on the
+/// automatic breadcrumbs only, while liveness, diurnal, replay and
+/// place-cluster checks still see every breadcrumb. The default,
+/// `Include`, keeps the historical behaviour. There is no
+/// down-weighting: the spectral and Lévy fits weigh every sample
+/// equally, so dropping a breadcrumb is the only clean discount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ManualBreadcrumbPolicy {
+    /// Analyze manual breadcrumbs like any other
+    #[default]
+    Include,
+    /// Leave them out of the mobility analyses
+    Exclude,
+}
+
+/// What `evaluate` does with fixes whose reported accuracy is worse
+/// than `AccuracyPolicy::max_accuracy_m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PoorAccuracyHandling {
+    /// Analyze them, but scale confidence by the share of fixes that
+    /// aren't poor and judge sufficiency on those fixes alone
+    #[default]
+    DownWeight,
+    /// Leave them out of the mobility analyses, like
+    /// `ManualBreadcrumbPolicy::Exclude`
+    Exclude,
+}
+
+/// Floor on the GPS accuracy (`MetaFlags::accuracy`) a fix needs to
+/// count as full evidence.
+///
+/// A 5 km cell-tower fix snaps to an H3 cell the user may never have
+/// been near, so its displacements are noise at the scales the PSD and
+/// Lévy fits measure. A chain made mostly of such fixes can still look
+/// statistically human and shouldn't ground a high-trust verdict. Fixes
+/// that report no accuracy are unknown rather than poor and are always
+/// analyzed; `CriticalityResult::accuracy` reports both shares.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AccuracyPolicy {
+    /// Reported accuracy (meters) above which a fix is poor
+    pub max_accuracy_m: f64,
+    pub handling: PoorAccuracyHandling,
+}
+
+impl Default for AccuracyPolicy {
+    fn default() -> Self {
+        Self { max_accuracy_m: 500.0, handling: PoorAccuracyHandling::DownWeight }
+    }
+}
+
+/// Precision-vs-speed preset for the whole engine.
+///
+/// - `Fast`, for real-time ingest: 200 integration steps in the Lévy
+///   normalization (KS statistic and κ are coarser, β is unchanged),
+///   no path-PSD cross-check even if configured, no waiting-time fit
+/// - `Balanced`: 1000 integration steps, cross-checks as configured
+/// - `Thorough`, for appeals and audits: 4000 integration steps,
+///   bootstrap confidence intervals on α and β (1000 resamples, see
+///   `ExponentIntervals`), and the path-PSD cross-check at
+///   `DEFAULT_MIN_PATH_ALPHA_EXCESS` when `path_psd` is `Skip`
+///
+/// Only `Thorough` adds intervals. The verdict rules are the same in
+/// every mode; `Fast` can move a KS statistic near its 0.15 cutoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Fidelity {
+    Fast,
+    #[default]
+    Balanced,
+    Thorough,
+}
+
+impl Fidelity {
+    /// Trapezoid steps per Lévy normalization integral.
+    pub fn integration_steps(&self) -> usize {
+        match self {
+            Self::Fast => 200,
+            Self::Balanced => levy::DEFAULT_INTEGRATION_STEPS,
+            Self::Thorough => 4000,
+        }
+    }
+
+    /// Bootstrap resamples behind `ExponentIntervals`; 0 for none.
+    pub fn bootstrap_iterations(&self) -> usize {
+        match self {
+            Self::Fast | Self::Balanced => 0,
+            Self::Thorough => 1000,
+        }
+    }
+
+    /// The path-PSD policy to run under `configured`.
+    pub fn path_psd(&self, configured: PathPsdPolicy) -> PathPsdPolicy {
+        match (self, configured) {
+            (Self::Fast, _) => PathPsdPolicy::Skip,
+            (Self::Thorough, PathPsdPolicy::Skip) => PathPsdPolicy::cross_check(),
+            (_, policy) => policy,
+        }
+    }
+
+    /// Whether the waiting-time fit runs.
+    pub fn fits_waiting_times(&self) -> bool {
+        *self != Self::Fast
+    }
+}
+
 /// Configuration for the Criticality Engine.
-#[derive(Debug, Clone)]
+///
+/// Serializable so a Verifier can publish the exact parameters behind
+/// its verdicts; certificates carry the matching `digest()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CriticalityConfig {
     /// Hamiltonian component weights
     pub weights: HamiltonianWeights,
-    /// Minimum displacement threshold for Lévy fitting (km)
+    /// PSD analysis options (resampling of irregular intervals)
+    pub psd: PsdConfig,
+    /// Behavioral profile options (genesis handling)
+    pub profile: ProfileConfig,
+    /// How displacement lengths are derived from H3 cells
+    pub distance: DistanceMode,
+    /// Minimum displacement threshold for Lévy fitting, in km like
+    /// every displacement (0.01 = 10 m). Under `DistanceMode::Quantized`
+    /// it is raised to at least the chain's median quantization band.
+    /// Set it in meters with `with_levy_x_min_meters`: a value meant as
+    /// meters written here leaves almost nothing above the threshold.
     pub levy_x_min: f64,
     /// Alpha range for biological classification
     pub alpha_min: f64,
     pub alpha_max: f64,
+    /// Whether α just above `alpha_max` can still pass
+    pub strong_correlation: StrongCorrelationPolicy,
+    /// Minimum R² of the PSD log-log fit for α to count at all; a
+    /// noisier spectrum has no slope worth reading
+    pub min_psd_r_squared: f64,
     /// Beta range for human Lévy flight
     pub beta_min: f64,
     pub beta_max: f64,
+    /// Convergence confidence below which the outcome is Inconclusive
+    pub min_confidence: f64,
+    /// Minimum Hamiltonian energy variance. The profile is learned from
+    /// the chain it scores, so a synthetic, perfectly consistent chain
+    /// looks normal everywhere; natural chains sit around 1e-3, while
+    /// scripted back-and-forth commutes fall near 1e-5.
+    pub min_energy_variance: f64,
+    /// Minimum diurnal strength (24 h share of activity power). Only
+    /// checked on chains spanning `MIN_DIURNAL_DAYS` or more; uniform
+    /// round-the-clock activity scores around 2/(24·days).
+    pub min_diurnal_strength: f64,
+    /// Directional persistence (mean resultant length of move
+    /// bearings) above which the chain is flagged as possible scripted
+    /// waypoint following. A random walk sits near 1/√moves and a
+    /// commute cancels itself out; a straight route scores near 1, but
+    /// so does a genuine relocation or road trip, so the flag lowers
+    /// confidence rather than deciding the outcome.
+    pub max_directional_persistence: f64,
+    /// Confidence multiplier when the chain is flagged for a
+    /// persistent heading (see `max_directional_persistence`)
+    pub persistent_heading_confidence_factor: f64,
+    /// Confidence multiplier when the chain's H3 resolution is too
+    /// coarse to resolve its movement (see `ResolutionReport`)
+    pub coarse_resolution_confidence_factor: f64,
+    /// Shortest waiting time between moves included in the
+    /// waiting-time fit (seconds)
+    pub waiting_t_min_seconds: f64,
+    /// Handling of chains that never leave one cell
+    pub stationary: StationaryPolicy,
+    /// Optional path-PSD cross-check
+    pub path_psd: PathPsdPolicy,
+    /// Place-cluster structure check
+    pub clusters: ClusterPolicy,
+    /// Handling of hand-added breadcrumbs
+    pub manual: ManualBreadcrumbPolicy,
+    /// Handling of fixes with poor reported accuracy
+    pub accuracy: AccuracyPolicy,
+    /// Liveness score parameters (see `LivenessReport`)
+    pub liveness: LivenessPolicy,
+    /// Confidence as a function of chain length
+    pub confidence: ConfidenceModel,
+    /// Precision-vs-speed preset
+    pub fidelity: Fidelity,
 }
 
 impl Default for CriticalityConfig {
     fn default() -> Self {
         Self {
             weights: HamiltonianWeights::default(),
+            psd: PsdConfig::default(),
+            profile: ProfileConfig::default(),
+            distance: DistanceMode::CellCenter,
             levy_x_min: 0.01,  // 10 meters
             alpha_min: 0.30,
             alpha_max: 0.80,
+            strong_correlation: StrongCorrelationPolicy::Reject,
+            min_psd_r_squared: 0.5,
             beta_min: 0.80,
             beta_max: 1.20,
+            min_confidence: 0.5,
+            min_energy_variance: 1e-4,
+            min_diurnal_strength: 0.05,
+            max_directional_persistence: 0.8,
+            persistent_heading_confidence_factor: 0.5,
+            coarse_resolution_confidence_factor: 0.5,
+            waiting_t_min_seconds: MIN_BREADCRUMB_INTERVAL_SECS as f64,
+            stationary: StationaryPolicy::default(),
+            path_psd: PathPsdPolicy::Skip,
+            clusters: ClusterPolicy::default(),
+            manual: ManualBreadcrumbPolicy::Include,
+            accuracy: AccuracyPolicy::default(),
+            liveness: LivenessPolicy::default(),
+            confidence: ConfidenceModel::default(),
+            fidelity: Fidelity::Balanced,
+        }
+    }
+}
+
+impl CriticalityConfig {
+    /// Set `levy_x_min` from a value in meters.
+    pub fn with_levy_x_min_meters(mut self, meters: f64) -> Self {
+        self.levy_x_min = meters / 1000.0;
+        self
+    }
+
+    /// `levy_x_min` in meters.
+    pub fn levy_x_min_meters(&self) -> f64 {
+        self.levy_x_min * 1000.0
+    }
+
+    /// Canonical JSON form, as published by the Verifier.
+    pub fn to_published_json(&self) -> Result<String> {
+        serde_json::to_string(self)
+            .map_err(|e| TripError::CertificateError(format!("Config encode error: {e}")))
+    }
+
+    /// SHA-256 of the published JSON, hex.
+    pub fn digest(&self) -> Result<String> {
+        Ok(hex::encode(Sha256::digest(self.to_published_json()?.as_bytes())))
+    }
+
+    /// Load a published config, checking it matches the digest a
+    /// certificate was issued under.
+    pub fn from_published_json(json: &str, expected_digest: &str) -> Result<Self> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| TripError::DeserializeError(format!("Config decode error: {e}")))?;
+        let digest = config.digest()?;
+        if digest != expected_digest {
+            return Err(TripError::CertificateError(
+                format!("Config digest mismatch: published {}, expected {}", &digest[..8], expected_digest)
+            ));
         }
+        Ok(config)
+    }
+}
+
+/// Progress of a streamed evaluation (see `evaluate_streaming`).
+#[derive(Debug)]
+pub enum Progress {
+    /// Breadcrumb signatures checked so far
+    VerifyingSignatures { done: usize, total: usize },
+    ComputingPsd,
+    FittingLevy,
+    ScoringHamiltonian,
+    /// Final event of a successful evaluation
+    Done(Box<CriticalityResult>),
+    /// Final event of a failed evaluation
+    Failed(TripError),
+}
+
+/// Verdict on breadcrumbs `start..end` of a chain
+/// (see `CriticalityEngine::evaluate_windows`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowResult {
+    pub start: usize,
+    pub end: usize,
+    /// PSD exponent, None when the analysis did not run
+    pub alpha: Option<f64>,
+    pub trust_score: f64,
+    pub outcome: Outcome,
+}
+
+/// One breadcrumb's share of a chain's trust score
+/// (see `CriticalityEngine::explain`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreadcrumbContribution {
+    pub index: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub cell: String,
+    /// Trust points this breadcrumb adds (positive) or costs
+    /// (negative): `exponent_points + energy_points`
+    pub contribution: f64,
+    /// PSD and Lévy points lost if the breadcrumb were removed
+    pub exponent_points: f64,
+    /// Hamiltonian points lost if the breadcrumb were removed
+    pub energy_points: f64,
+    /// Change in α and β if the breadcrumb were removed; 0.0 when
+    /// not measured
+    pub alpha_shift: f64,
+    pub beta_shift: f64,
+    /// Hamiltonian energy H; None when not scored (excluded genesis)
+    pub energy: Option<f64>,
+}
+
+/// Coverage of the bootstrap intervals in `ExponentIntervals`.
+pub const EXPONENT_INTERVAL_LEVEL: f64 = 0.95;
+
+/// Fixed seed for the bootstrap, so a verdict is reproducible.
+const BOOTSTRAP_SEED: u64 = 0x7472_6970;
+
+/// Bootstrap percentile intervals (`EXPONENT_INTERVAL_LEVEL`) on the
+/// fitted exponents, computed under `Fidelity::Thorough`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExponentIntervals {
+    /// From refits of the log-log regression (`psd::bootstrap_alpha`)
+    pub alpha: (f64, f64),
+    /// From resampled displacements (`levy::bootstrap_beta`)
+    pub beta: (f64, f64),
+    pub iterations: usize,
+}
+
+/// Central `EXPONENT_INTERVAL_LEVEL` interval of bootstrap replicates.
+fn percentile_interval(mut replicates: Vec<f64>) -> Option<(f64, f64)> {
+    if replicates.is_empty() {
+        return None;
     }
+    replicates.sort_by(f64::total_cmp);
+    let tail = (1.0 - EXPONENT_INTERVAL_LEVEL) / 2.0;
+    let at = |q: f64| replicates[((replicates.len() - 1) as f64 * q).round() as usize];
+    Some((at(tail), at(1.0 - tail)))
 }
 
 /// Complete result from the Criticality Engine.
 /// This contains everything needed for PoH Certificate generation.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CriticalityResult {
     /// PSD scaling exponent analysis
     pub psd: PsdResult,
@@ -67,6 +606,13 @@ pub struct CriticalityResult {
     /// Per-breadcrumb Hamiltonian scoring
     pub hamiltonian: ChainHamiltonianResult,
 
+    /// Looped-replay detection on the spatial path
+    pub replay: ReplayResult,
+
+    /// Path-PSD cross-check; None unless enabled by `PathPsdPolicy`
+    /// and the chain moves
+    pub path_psd: Option<PathPsdResult>,
+
     /// Overall trust score [0, 100]
     pub trust_score: f64,
 
@@ -76,196 +622,2468 @@ pub struct CriticalityResult {
     /// Number of breadcrumbs analyzed
     pub chain_length: usize,
 
+    /// Number of unique H3 cells in the analyzed chain
+    pub unique_cells: usize,
+
+    /// Radius of gyration (km): how far the user ranges around their
+    /// center of mass. Should be broadly consistent with Lévy κ.
+    pub radius_of_gyration_km: f64,
+
+    /// Share of activity power at the 24 h period [0, 1]; None if the
+    /// chain is too short or has no timestamps (location-blind mode)
+    pub diurnal_strength: Option<f64>,
+
+    /// How consistently the moves keep one heading [0, 1] (see
+    /// `BreadcrumbChain::directional_persistence`); None with too few
+    /// moves or without cells (location-blind mode)
+    pub directional_persistence: Option<f64>,
+
+    /// Fit of the waiting times between moves (heavy-tailed for
+    /// humans, exponential for constant-rate bots); None if the chain
+    /// has too few moves or under `Fidelity::Fast`. Reported alongside
+    /// the verdict, not part of it.
+    pub waiting_times: Option<WaitingTimeResult>,
+
+    /// Confidence intervals on α and β; None unless
+    /// `Fidelity::Thorough` and both exponents were measured
+    pub intervals: Option<ExponentIntervals>,
+
     /// Is this identity classified as human?
     pub is_human: bool,
 
+    /// Too little data to decide either way (see `Outcome`)
+    pub is_inconclusive: bool,
+
+    /// Whether the identity is active now, judged at evaluation time.
+    /// Independent of `trust_score` and the verdict, which ask whether
+    /// the movement is human (see `crate::liveness`).
+    pub liveness: LivenessReport,
+
+    /// Fixes with poor or missing reported accuracy (see
+    /// `AccuracyPolicy`); empty for displacement-only evaluation
+    pub accuracy: AccuracyReport,
+
     /// Summary of what contributed to the decision
     pub verdict: Verdict,
+
+    /// False-accept calibration of the engine that produced this result
+    pub calibration: Option<Arc<CalibrationTable>>,
+
+    /// The behavioral profile the Hamiltonian scored against, for
+    /// inspecting a verdict. Only kept by an engine built
+    /// `with_profile_export`: it holds visited cells (see `PrivacyReport`).
+    pub profile: Option<BehavioralProfile>,
+}
+
+impl CriticalityResult {
+    pub fn outcome(&self) -> Outcome {
+        Outcome::from_flags(self.is_human, self.is_inconclusive)
+    }
+
+    /// Empirical probability that a non-human chain with this trust
+    /// score and length is accepted, from the engine's calibration
+    /// table. Without a table this is 1.0: nothing is known, so no
+    /// risk is ruled out.
+    pub fn estimated_false_accept_rate(&self) -> f64 {
+        match &self.calibration {
+            Some(table) => table.false_accept_rate(self.trust_score, self.chain_length),
+            None => 1.0,
+        }
+    }
+
+    /// Liveness score [0, 1] (see `LivenessReport::score`).
+    pub fn liveness_score(&self) -> f64 {
+        self.liveness.score()
+    }
+
+    /// Fold in the outcome of an Active Verification challenge run
+    /// for this identity.
+    pub fn record_active_response(&mut self, passed: bool) {
+        self.liveness.active_response = Some(passed);
+    }
 }
 
 /// Human-readable verdict breakdown.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Verdict {
     pub psd_pass: bool,
+    /// PSD passed only via `StrongCorrelationPolicy::AcceptReduced`
+    pub psd_reduced: bool,
+    /// Why the PSD check failed: `PoorSpectralFit` when α was in an
+    /// accepted band but R² was below `min_psd_r_squared`, otherwise
+    /// `AlphaOutOfRange`. None when it passed or wasn't measured.
+    pub psd_failure: Option<FailureReason>,
     pub levy_pass: bool,
     pub hamiltonian_pass: bool,
+    /// Energy variance below `min_energy_variance`, or a degenerate
+    /// displacement/interval spread (fails hamiltonian_pass)
+    pub over_smooth: bool,
+    /// Directional persistence at or below
+    /// `max_directional_persistence` (true when not measured). A flag
+    /// that lowers confidence, not part of the outcome.
+    pub directional_pass: bool,
+    /// Diurnal strength at or above `min_diurnal_strength` (true when
+    /// not measurable)
+    pub diurnal_pass: bool,
+    /// A finer H3 resolution to collect at, when the chain's own is too
+    /// coarse to resolve its movement (lowers confidence)
+    pub recommended_resolution: Option<u8>,
+    /// Stationary chain: PSD and Lévy were not measured (see
+    /// `StationaryPolicy`)
+    pub stationary: bool,
+    /// Every displacement the same non-zero length: PSD and Lévy have
+    /// no spread to fit and were not measured. Real movement is never
+    /// this uniform, so the chain fails.
+    pub constant_step: bool,
+    /// No long stationary dwells dominating the chain (see
+    /// `DwellReport`, stationary chains only) and no implausibly
+    /// repetitive sensor context (see `ContextReport`)
+    pub liveness_pass: bool,
+    /// Most breadcrumbs fall in place clusters (see `ClusterPolicy`;
+    /// true when not measured). A flag, not part of the outcome.
+    pub cluster_structure_pass: bool,
     pub confidence_sufficient: bool,
     pub summary: String,
 }
 
+/// Verdict inputs that need breadcrumbs rather than displacements;
+/// the default is what displacement-only evaluation can say.
+#[derive(Debug, Clone, Copy, Default)]
+struct ChainChecks {
+    diurnal_strength: Option<f64>,
+    recommended_resolution: Option<u8>,
+    /// Sensor context implausibly repetitive (see `ContextReport`)
+    low_liveness: bool,
+    /// Most breadcrumbs outside any place cluster (see `ClusterPolicy`)
+    low_cluster_structure: bool,
+    /// Share of analyzed fixes with poor accuracy, to down-weight (see
+    /// `AccuracyPolicy`)
+    poor_accuracy_share: f64,
+    directional_persistence: Option<f64>,
+}
+
+/// Everything `evaluate` computes before the verdict (see
+/// `CriticalityEngine::analyze`), for judging one chain under many
+/// configs in a threshold sweep.
+///
+/// The analyses depend on most of the config; the verdict applies only
+/// these, which `verdict_for_config` lets vary: `alpha_min`,
+/// `alpha_max`, `strong_correlation`, `min_psd_r_squared`, `beta_min`,
+/// `beta_max`, `min_confidence`, `min_energy_variance`,
+/// `min_diurnal_strength`, `max_directional_persistence`,
+/// `persistent_heading_confidence_factor`,
+/// `coarse_resolution_confidence_factor`, `confidence` and
+/// `clusters.max_noise_fraction`.
+#[derive(Debug, Clone)]
+pub struct RawAnalysis {
+    /// `analysis_digest` of the config the analyses ran under
+    analysis_digest: String,
+    psd: PsdResult,
+    levy: LevyResult,
+    hamiltonian: ChainHamiltonianResult,
+    replay: ReplayResult,
+    path_psd: Option<PathPsdResult>,
+    waiting_times: Option<WaitingTimeResult>,
+    intervals: Option<ExponentIntervals>,
+    /// Displacements the mobility analyses saw (km)
+    displacements: Vec<f64>,
+    /// `low_cluster_structure` is decided per config from `cluster_noise`
+    checks: ChainChecks,
+    dwell_low_liveness: bool,
+    cluster_noise: f64,
+    chain_length: usize,
+    unique_cells: usize,
+    radius_of_gyration_km: f64,
+    liveness: LivenessReport,
+    accuracy: AccuracyReport,
+    profile: BehavioralProfile,
+}
+
+/// Digest of an engine running `config` with `scorer` and
+/// `calibration`: the config digest alone when both are unset, so a
+/// default engine's certificates check against the published config.
+fn engine_digest(
+    config: &CriticalityConfig,
+    scorer: Option<&dyn AnomalyScorer>,
+    calibration: Option<&CalibrationTable>,
+) -> Result<String> {
+    if scorer.is_none() && calibration.is_none() {
+        return config.digest();
+    }
+    let engine = serde_json::json!({
+        "config": config,
+        "scorer": scorer.map(|s| s.fingerprint()),
+        "calibration": calibration,
+    });
+    Ok(hex::encode(Sha256::digest(engine.to_string().as_bytes())))
+}
+
+/// Digest of `config` with the verdict thresholds (see `RawAnalysis`)
+/// reset to their defaults, and of `scorer`: equal for any two
+/// engines whose analyses agree.
+fn analysis_digest(config: &CriticalityConfig, scorer: Option<&dyn AnomalyScorer>) -> Result<String> {
+    let defaults = CriticalityConfig::default();
+    let analyzed = CriticalityConfig {
+        alpha_min: defaults.alpha_min,
+        alpha_max: defaults.alpha_max,
+        strong_correlation: defaults.strong_correlation,
+        min_psd_r_squared: defaults.min_psd_r_squared,
+        beta_min: defaults.beta_min,
+        beta_max: defaults.beta_max,
+        min_confidence: defaults.min_confidence,
+        min_energy_variance: defaults.min_energy_variance,
+        min_diurnal_strength: defaults.min_diurnal_strength,
+        max_directional_persistence: defaults.max_directional_persistence,
+        persistent_heading_confidence_factor: defaults.persistent_heading_confidence_factor,
+        coarse_resolution_confidence_factor: defaults.coarse_resolution_confidence_factor,
+        confidence: defaults.confidence,
+        clusters: ClusterPolicy { max_noise_fraction: defaults.clusters.max_noise_fraction, ..config.clusters },
+        ..config.clone()
+    };
+    engine_digest(&analyzed, scorer, None)
+}
+
 /// The Criticality Engine.
 pub struct CriticalityEngine {
     config: CriticalityConfig,
+    /// Custom anomaly scorer; None uses the six-component Hamiltonian
+    scorer: Option<Box<dyn AnomalyScorer>>,
+    /// False-accept calibration attached to every result
+    calibration: Option<Arc<CalibrationTable>>,
+    /// Keep the behavioral profile on each result
+    export_profile: bool,
+    /// Time source for the liveness score
+    clock: Arc<dyn Clock>,
 }
 
 impl CriticalityEngine {
     pub fn new(config: CriticalityConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            scorer: None,
+            calibration: None,
+            export_profile: false,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Judge liveness at `clock`'s time instead of the wall clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Replace the six-component Hamiltonian with a custom scorer.
+    /// The verdict's energy checks then apply to its output.
+    pub fn with_scorer(mut self, scorer: Box<dyn AnomalyScorer>) -> Self {
+        self.scorer = Some(scorer);
+        self
+    }
+
+    /// Attach a false-accept calibration table, enabling
+    /// `CriticalityResult::estimated_false_accept_rate`.
+    pub fn with_calibration(mut self, table: CalibrationTable) -> Self {
+        self.calibration = Some(Arc::new(table));
+        self
+    }
+
+    /// Keep the learned behavioral profile on each result
+    /// (`CriticalityResult::profile`), e.g. to debug a verdict.
+    pub fn with_profile_export(mut self) -> Self {
+        self.export_profile = true;
+        self
     }
 
     pub fn with_defaults() -> Self {
         Self::new(CriticalityConfig::default())
     }
 
+    /// The configuration verdicts are computed with.
+    pub fn config(&self) -> &CriticalityConfig {
+        &self.config
+    }
+
+    /// Digest of everything the results depend on: the config, the
+    /// custom scorer's fingerprint and the calibration table. Equal to
+    /// `CriticalityConfig::digest` for an engine with neither; this is
+    /// what certificates carry as `config_digest`.
+    pub fn digest(&self) -> Result<String> {
+        engine_digest(&self.config, self.scorer.as_deref(), self.calibration.as_deref())
+    }
+
     /// Evaluate a breadcrumb chain and produce a CriticalityResult.
     ///
     /// This is the main entry point for the Verifier.
     pub fn evaluate(&self, chain: &BreadcrumbChain) -> Result<CriticalityResult> {
-        if chain.len() < MIN_BREADCRUMBS_PSD {
-            return Err(TripError::InsufficientBreadcrumbs {
-                got: chain.len(),
-                need: MIN_BREADCRUMBS_PSD,
+        self.evaluate_with_profile(chain, &mut |_| {}).map(|(result, _)| result)
+    }
+
+    /// Verify signatures and evaluate a chain, sending `Progress`
+    /// events as each stage starts, for clients rendering progress.
+    ///
+    /// The last event is always `Done` or `Failed`. Signature progress
+    /// is reported about every 1% of the chain. Events are dropped if
+    /// the receiver hangs up; `evaluate` is the simple, silent path.
+    pub fn evaluate_streaming(&self, chain: &BreadcrumbChain, events: Sender<Progress>) {
+        let mut send = |event: Progress| {
+            let _ = events.send(event);
+        };
+
+        let outcome = Self::verify_signatures_with_progress(chain, &mut send)
+            .and_then(|()| self.evaluate_with_profile(chain, &mut send));
+        send(match outcome {
+            Ok((result, _)) => Progress::Done(Box::new(result)),
+            Err(e) => Progress::Failed(e),
+        });
+    }
+
+    fn verify_signatures_with_progress(
+        chain: &BreadcrumbChain,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<()> {
+        let total = chain.len();
+        let step = (total / 100).max(1);
+        progress(Progress::VerifyingSignatures { done: 0, total });
+        for (i, b) in chain.breadcrumbs.iter().enumerate() {
+            b.verify_signature()?;
+            let done = i + 1;
+            if done % step == 0 || done == total {
+                progress(Progress::VerifyingSignatures { done, total });
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate a chain down to a three-way Outcome.
+    ///
+    /// Errors caused by too little data (chain shorter than
+    /// MIN_BREADCRUMBS_PSD, too few segments for the PSD, too few
+    /// displacements for the Lévy fit) become `Outcome::Inconclusive`.
+    /// Integrity and other errors are still returned.
+    pub fn classify(&self, chain: &BreadcrumbChain) -> Result<Outcome> {
+        match self.evaluate(chain) {
+            Ok(result) => Ok(result.outcome()),
+            Err(TripError::InsufficientBreadcrumbs { .. })
+            | Err(TripError::PsdError(_))
+            | Err(TripError::LevyFitError(_)) => Ok(Outcome::Inconclusive),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Evaluate sliding windows of `window` breadcrumbs, advancing
+    /// `step` breadcrumbs at a time, in location-blind mode (see
+    /// `evaluate_displacements`).
+    ///
+    /// Localizes a change of regime within one chain, such as a phone
+    /// handed to a bot: a whole-chain verdict averages it away. Windows
+    /// whose analyses lack data are reported as Inconclusive with no α.
+    /// Confidence follows the window length, so windows shorter than
+    /// the `min_confidence` crossover are always Inconclusive; compare
+    /// their `alpha` instead. `window` is raised to 2 and `step` to 1.
+    pub fn evaluate_windows(
+        &self,
+        chain: &BreadcrumbChain,
+        window: usize,
+        step: usize,
+    ) -> Result<Vec<WindowResult>> {
+        let (window, step) = (window.max(2), step.max(1));
+        let displacement_km = chain.displacement_series_with_mode(self.config.distance);
+        let interval_seconds = chain.interval_series();
+
+        let mut windows = Vec::new();
+        let mut start = 0;
+        while start + window <= chain.len() {
+            let span = start..start + window - 1;
+            let result = match self.evaluate_displacements(
+                &displacement_km[span.clone()],
+                &interval_seconds[span],
+            ) {
+                Ok(result) => Some(result),
+                Err(TripError::InsufficientBreadcrumbs { .. })
+                | Err(TripError::PsdError(_))
+                | Err(TripError::LevyFitError(_)) => None,
+                Err(e) => return Err(e),
+            };
+            windows.push(WindowResult {
+                start,
+                end: start + window,
+                alpha: result.as_ref()
+                    .filter(|r| r.psd.classification != PsdClassification::NotMeasured)
+                    .map(|r| r.psd.alpha),
+                trust_score: result.as_ref().map_or(0.0, |r| r.trust_score),
+                outcome: result.as_ref().map_or(Outcome::Inconclusive, |r| r.outcome()),
             });
+            start += step;
         }
+        trace_event!(debug, windows = windows.len(), window, step, "windows evaluated");
+        Ok(windows)
+    }
 
-        // --- 1. PSD Analysis ---
-        let displacement_km = chain.displacement_series();
+    /// Attribute the trust score across breadcrumbs and return the
+    /// `top_n` with the largest effect, most impactful first: "show me
+    /// exactly what looked wrong".
+    ///
+    /// Each breadcrumb is scored by leaving it out. Its exponent points
+    /// are the PSD and Lévy points the chain would lose without it,
+    /// with its two displacements merged into one. Those points are
+    /// the in-band credit extended linearly past the band edges (where
+    /// the real credit is flat at zero), so a breadcrumb that drags α
+    /// or β out of band still registers. Its energy points
+    /// are the change in the Hamiltonian's share of the score from
+    /// dropping its energy H from the mean, taken on the passing
+    /// branch (25·(H̄ − H)/(n − 1)): below-average energy helps.
+    /// Contributions are first-order and don't add up exactly to the
+    /// trust score. Costs one PSD and Lévy fit per breadcrumb.
+    pub fn explain(
+        &self,
+        chain: &BreadcrumbChain,
+        top_n: usize,
+    ) -> Result<Vec<BreadcrumbContribution>> {
+        let result = self.evaluate(chain)?;
+        let n = chain.len();
+        let displacement_km = chain.displacement_series_with_mode(self.config.distance);
         let interval_seconds = chain.interval_series();
-        let psd_result = psd::compute_psd_from_chain(&displacement_km, &interval_seconds)?;
+        let measured = result.psd.classification != PsdClassification::NotMeasured;
+        let exponent_points = 40.0 * Self::psd_closeness(&self.config, &result.psd)
+            + 25.0 * Self::levy_closeness(&self.config, &result.levy);
 
-        // --- 2. Lévy Flight Fitting ---
-        let levy_result = levy::fit_levy(&displacement_km, self.config.levy_x_min)?;
+        let energies: std::collections::HashMap<u64, f64> = result.hamiltonian.scores.iter()
+            .map(|score| (score.index, score.h_total))
+            .collect();
+        let mean_energy = result.hamiltonian.mean_energy;
+        let scored = result.hamiltonian.scores.len();
 
-        // --- 3. Build Behavioral Profile ---
-        let profile = BehavioralProfile::from_chain(chain);
+        let mut contributions = Vec::with_capacity(n);
+        for (i, breadcrumb) in chain.breadcrumbs.iter().enumerate() {
+            let (mut displacements, mut intervals) = (displacement_km.clone(), interval_seconds.clone());
+            match i {
+                0 => {
+                    displacements.remove(0);
+                    intervals.remove(0);
+                }
+                _ if i == n - 1 => {
+                    displacements.pop();
+                    intervals.pop();
+                }
+                _ => {
+                    let merged = compute_displacements(&[
+                        chain.breadcrumbs[i - 1].clone(),
+                        chain.breadcrumbs[i + 1].clone(),
+                    ])?;
+                    displacements.splice(i - 1..=i, [self.config.distance.distance_km(&merged[0])]);
+                    intervals.splice(i - 1..=i, [merged[0].dt_seconds]);
+                }
+            }
 
-        // --- 4. Hamiltonian Evaluation ---
-        let hamiltonian_result = hamiltonian::evaluate_hamiltonian(
-            chain,
-            &profile,
+            let (mut points_without, mut alpha_shift, mut beta_shift) = (exponent_points, 0.0, 0.0);
+            if measured {
+                let psd = psd::compute_psd_from_chain_with_config(&displacements, &intervals, &self.config.psd).ok();
+                let levy = levy::fit_levy_with_steps(
+                    &displacements,
+                    self.config.levy_x_min,
+                    self.config.fidelity.integration_steps(),
+                ).ok();
+                points_without = 40.0 * psd.as_ref().map_or(0.0, |p| Self::psd_closeness(&self.config, p))
+                    + 25.0 * levy.as_ref().map_or(0.0, |l| Self::levy_closeness(&self.config, l));
+                alpha_shift = psd.map_or(0.0, |p| p.alpha - result.psd.alpha);
+                beta_shift = levy.map_or(0.0, |l| l.beta - result.levy.beta);
+            }
+
+            let energy = energies.get(&breadcrumb.index).copied();
+            let energy_points = match energy {
+                Some(h) if scored > 1 => 25.0 * (mean_energy - h) / (scored - 1) as f64,
+                _ => 0.0,
+            };
+            let exponent_points = exponent_points - points_without;
+            contributions.push(BreadcrumbContribution {
+                index: breadcrumb.index,
+                timestamp: breadcrumb.timestamp,
+                cell: breadcrumb.location_cell.clone(),
+                contribution: exponent_points + energy_points,
+                exponent_points,
+                energy_points,
+                alpha_shift,
+                beta_shift,
+                energy,
+            });
+        }
+
+        contributions.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()));
+        contributions.truncate(top_n);
+        trace_event!(debug, breadcrumbs = n, top_n, "trust attributed");
+        Ok(contributions)
+    }
+
+    /// Evaluate pre-computed displacements without any H3 geometry
+    /// (location-blind mode), for Attesters that never expose cells.
+    ///
+    /// Available signals: PSD α and Lévy β/κ (identical to the full
+    /// path), and the H_spatial and H_structure Hamiltonian components.
+    /// Unavailable: H_temporal, H_kinetic, H_contextual (need cells,
+    /// timestamps or context digests), replay periodicity,
+    /// `unique_cells` and `radius_of_gyration_km`, which are reported
+    /// as zero. A custom scorer set via `with_scorer` is not used, as
+    /// it needs breadcrumbs.
+    ///
+    /// `intervals[i]` is the time (seconds) over which `displacements[i]`
+    /// (km) occurred.
+    pub fn evaluate_displacements(
+        &self,
+        displacements: &[f64],
+        intervals: &[f64],
+    ) -> Result<CriticalityResult> {
+        let chain_length = displacements.len() + 1;
+        if chain_length < MIN_BREADCRUMBS_PSD {
+            return Err(TripError::InsufficientBreadcrumbs {
+                got: chain_length,
+                need: MIN_BREADCRUMBS_PSD,
+            });
+        }
+
+        if let Some(i) = intervals.iter().position(|&dt| !(dt > 0.0 && dt.is_finite())) {
+            return Err(TripError::ChainIntegrity(
+                format!("Non-positive interval at position {i}")
+            ));
+        }
+        ensure_finite("displacement_km", displacements)?;
+
+        let stationary_ceiling = self.config.stationary.trust_ceiling(displacements);
+        let unmeasurable = stationary_ceiling.is_some() || Self::is_constant_step(&self.config, displacements);
+        let (psd_result, levy_result) = match unmeasurable {
+            true => (PsdResult::not_measured(), LevyResult::not_measured()),
+            false => (
+                psd::compute_psd_from_chain_with_config(displacements, intervals, &self.config.psd)?,
+                levy::fit_levy_with_steps(
+                    displacements,
+                    self.config.levy_x_min,
+                    self.config.fidelity.integration_steps(),
+                )?,
+            ),
+        };
+        let exponent_intervals = self.exponent_intervals(&psd_result, displacements, self.config.levy_x_min);
+        let waiting_result = self.fit_waiting_times(displacements, intervals);
+        let hamiltonian_result = hamiltonian::evaluate_displacement_hamiltonian(
+            displacements,
+            intervals,
             &self.config.weights,
+            &self.config.profile,
         );
 
-        // --- 5. Compute Trust Score ---
-        let (trust_score, confidence, is_human, verdict) = self.compute_verdict(
-            &psd_result,
-            &levy_result,
-            &hamiltonian_result,
-            chain.len(),
-        );
+        let (trust_score, confidence, is_human, is_inconclusive, verdict) = match stationary_ceiling {
+            Some(trust_ceiling) => Self::compute_stationary_verdict(
+                &self.config,
+                &hamiltonian_result,
+                None,
+                DwellReport::from_series(displacements, intervals).is_low_liveness(),
+                chain_length,
+                trust_ceiling,
+            ),
+            None => Self::compute_verdict(
+                &self.config,
+                &psd_result,
+                &levy_result,
+                &hamiltonian_result,
+                &ChainChecks::default(),
+                displacements,
+            ),
+        };
 
         Ok(CriticalityResult {
             psd: psd_result,
             levy: levy_result,
             hamiltonian: hamiltonian_result,
+            replay: ReplayResult::default(),
+            path_psd: None,
             trust_score,
             confidence,
-            chain_length: chain.len(),
+            chain_length,
+            unique_cells: 0,
+            radius_of_gyration_km: 0.0,
+            diurnal_strength: None,
+            directional_persistence: None,
+            waiting_times: waiting_result,
+            intervals: exponent_intervals,
             is_human,
+            is_inconclusive,
+            liveness: LivenessReport::not_measured(),
+            accuracy: AccuracyReport::default(),
             verdict,
+            calibration: self.calibration.clone(),
+            profile: None,
         })
     }
 
-    /// Compute the final verdict from individual analyses.
-    fn compute_verdict(
+    /// Evaluate a chain and also produce a PrivacyReport describing
+    /// which location-derived quantities the result retains.
+    pub fn evaluate_with_privacy_report(
         &self,
-        psd: &PsdResult,
-        levy: &LevyResult,
-        hamiltonian: &ChainHamiltonianResult,
-        chain_length: usize,
-    ) -> (f64, f64, bool, Verdict) {
-        // PSD check: α in biological range?
-        let psd_pass = psd.alpha >= self.config.alpha_min
-            && psd.alpha <= self.config.alpha_max
-            && psd.r_squared >= 0.5;
+        chain: &BreadcrumbChain,
+    ) -> Result<(CriticalityResult, PrivacyReport)> {
+        let (result, profile) = self.evaluate_with_profile(chain, &mut |_| {})?;
+        let report = PrivacyReport::new(&result, &profile);
+        Ok((result, report))
+    }
 
-        // Lévy check: β in human range?
-        let levy_pass = levy.beta >= self.config.beta_min
-            && levy.beta <= self.config.beta_max
-            && levy.ks_statistic < 0.15;
+    /// Run the full analysis, returning the intermediate profile too.
+    /// `progress` is told when each stage starts.
+    fn evaluate_with_profile(
+        &self,
+        chain: &BreadcrumbChain,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<(CriticalityResult, BehavioralProfile)> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("criticality", chain_length = chain.len()).entered();
 
-        // Hamiltonian check: low mean energy, few red alerts?
-        let red_fraction = hamiltonian.alert_count.red as f64
-            / hamiltonian.scores.len().max(1) as f64;
-        let hamiltonian_pass = hamiltonian.mean_energy < 0.4
-            && red_fraction < 0.05;
+        let raw = self.analyze_with_progress(chain, progress)?;
+        let result = self.verdict_unchecked(&raw, &self.config);
+        Ok((result, raw.profile))
+    }
 
-        // Confidence: increases with chain length
-        // Per TRIP spec convergence analysis:
-        // 64 → 0.3 confidence, 200 → 0.7, 500+ → 0.95
-        let confidence = convergence_confidence(chain_length);
-        let confidence_sufficient = confidence >= 0.5;
+    /// Run the analyses (PSD, Lévy, Hamiltonian and the chain checks)
+    /// under this engine's config, stopping short of the verdict.
+    /// `verdict_for_config` then judges the result under any number of
+    /// thresholds without repeating them.
+    pub fn analyze(&self, chain: &BreadcrumbChain) -> Result<RawAnalysis> {
+        self.analyze_with_progress(chain, &mut |_| {})
+    }
 
-        // Trust score [0, 100]:
-        // 40% from PSD (scaled by how close α is to center of range)
-        // 25% from Lévy
-        // 25% from Hamiltonian
-        // 10% from chain length / confidence
-        let psd_score = if psd_pass {
-            let center = (self.config.alpha_min + self.config.alpha_max) / 2.0;
-            let range = (self.config.alpha_max - self.config.alpha_min) / 2.0;
-            let distance = (psd.alpha - center).abs() / range;
-            (1.0 - distance) * psd.r_squared
-        } else {
-            0.0
+    /// The result `evaluate` would give under `config`, from analyses
+    /// already run by `analyze`.
+    ///
+    /// `config` may differ from the one `raw` was analyzed under only
+    /// in the thresholds the verdict applies (see `RawAnalysis`);
+    /// anything else would change the analyses themselves and is a
+    /// `ConfigMismatch`. Calibration and profile export come from this
+    /// engine.
+    pub fn verdict_for_config(&self, raw: &RawAnalysis, config: &CriticalityConfig) -> Result<CriticalityResult> {
+        if analysis_digest(config, self.scorer.as_deref())? != raw.analysis_digest {
+            return Err(TripError::ConfigMismatch(
+                "config differs from the analyzed one beyond verdict thresholds".to_string()
+            ));
+        }
+        Ok(self.verdict_unchecked(raw, config))
+    }
+
+    /// Analyses for `analyze`; `progress` is told when each stage
+    /// starts.
+    fn analyze_with_progress(
+        &self,
+        chain: &BreadcrumbChain,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<RawAnalysis> {
+        // The breadcrumbs the mobility analyses see (see
+        // `ManualBreadcrumbPolicy` and `AccuracyPolicy`); the other
+        // checks use `chain`
+        let AccuracyPolicy { max_accuracy_m, handling } = self.config.accuracy;
+        let accuracy = chain.accuracy_report(max_accuracy_m);
+        let exclude_manual = self.config.manual == ManualBreadcrumbPolicy::Exclude;
+        let exclude_poor = handling == PoorAccuracyHandling::Exclude && accuracy.poor > 0;
+        let retained;
+        let mobility = match exclude_manual || exclude_poor {
+            false => chain,
+            true => {
+                retained = chain.retain_breadcrumbs(|b| {
+                    let drop_manual = exclude_manual && b.meta_flags.manual;
+                    let drop_poor = exclude_poor && is_poor_accuracy(b, max_accuracy_m);
+                    !drop_manual && !drop_poor
+                })?;
+                &retained
+            }
+        };
+        let poor_accuracy_share = match handling {
+            PoorAccuracyHandling::DownWeight => mobility.accuracy_report(max_accuracy_m).poor_fraction(),
+            PoorAccuracyHandling::Exclude => 0.0,
         };
+        if mobility.len() < MIN_BREADCRUMBS_PSD {
+            return Err(TripError::InsufficientBreadcrumbs {
+                got: mobility.len(),
+                need: MIN_BREADCRUMBS_PSD,
+            });
+        }
 
-        let levy_score = if levy_pass {
-            let center = (self.config.beta_min + self.config.beta_max) / 2.0;
-            let range = (self.config.beta_max - self.config.beta_min) / 2.0;
-            let distance = (levy.beta - center).abs() / range;
-            (1.0 - distance) * (1.0 - levy.ks_statistic)
+        let displacement_km = mobility.displacement_series_with_mode(self.config.distance);
+        let interval_seconds = mobility.interval_series();
+        let stationary_ceiling = self.config.stationary.trust_ceiling(&displacement_km);
+        let constant_step = Self::is_constant_step(&self.config, &displacement_km);
+
+        // --- 1. PSD Analysis ---
+        progress(Progress::ComputingPsd);
+        let psd_result = if stationary_ceiling.is_some() {
+            trace_event!(info, "stationary chain, mobility analyses skipped");
+            PsdResult::not_measured()
+        } else if constant_step {
+            trace_event!(warn, "constant step length, mobility analyses skipped");
+            PsdResult::not_measured()
         } else {
-            0.0
+            trace_event!(debug, samples = displacement_km.len(), "psd started");
+            let psd_result = psd::compute_psd_from_chain_with_config(
+                &displacement_km,
+                &interval_seconds,
+                &self.config.psd,
+            )?;
+            trace_event!(
+                debug,
+                alpha = psd_result.alpha,
+                r_squared = psd_result.r_squared,
+                classification = psd_result.classification.label(),
+                "psd finished"
+            );
+            psd_result
+        };
+        let path_psd = match self.config.fidelity.path_psd(self.config.path_psd) {
+            PathPsdPolicy::CrossCheck { min_alpha_excess } if stationary_ceiling.is_none() && !constant_step => {
+                self.cross_check_path(mobility, &interval_seconds, psd_result.alpha, min_alpha_excess)
+            }
+            _ => None,
         };
 
-        let ham_score = if hamiltonian_pass {
-            1.0 - hamiltonian.mean_energy
+        // --- 2. Lévy Flight Fitting ---
+        progress(Progress::FittingLevy);
+        let x_min = match self.config.distance {
+            DistanceMode::CellCenter => self.config.levy_x_min,
+            DistanceMode::Quantized => self.config.levy_x_min.max(mobility.quantization_km()),
+        };
+        let levy_result = if stationary_ceiling.is_some() || constant_step {
+            LevyResult::not_measured()
         } else {
-            (0.4 - hamiltonian.mean_energy).max(0.0) / 0.4
+            let levy_result = levy::fit_levy_with_steps(
+                &displacement_km,
+                x_min,
+                self.config.fidelity.integration_steps(),
+            )?;
+            trace_event!(
+                debug,
+                beta = levy_result.beta,
+                kappa_km = levy_result.kappa_km,
+                ks_statistic = levy_result.ks_statistic,
+                "levy fit"
+            );
+            levy_result
         };
 
-        let trust_score = (
-            40.0 * psd_score
-            + 25.0 * levy_score
-            + 25.0 * ham_score
-            + 10.0 * confidence
-        ).clamp(0.0, 100.0);
+        let exponent_intervals = self.exponent_intervals(&psd_result, &displacement_km, x_min);
 
-        let is_human = psd_pass && levy_pass && hamiltonian_pass && confidence_sufficient;
+        // --- 3. Build Behavioral Profile ---
+        progress(Progress::ScoringHamiltonian);
+        let profile = BehavioralProfile::from_chain_with_config(mobility, &self.config.profile);
 
-        let summary = format!(
-            "PSD α={:.3} ({}), Lévy β={:.3} ({}), H_mean={:.3} ({}), confidence={:.2} ({}). {}",
-            psd.alpha, if psd_pass { "PASS" } else { "FAIL" },
-            levy.beta, if levy_pass { "PASS" } else { "FAIL" },
-            hamiltonian.mean_energy, if hamiltonian_pass { "PASS" } else { "FAIL" },
-            confidence, if confidence_sufficient { "PASS" } else { "FAIL" },
-            if is_human { "HUMAN" } else { "NOT VERIFIED" },
+        // --- 4. Hamiltonian Evaluation ---
+        let hamiltonian_result = match &self.scorer {
+            Some(scorer) => hamiltonian::evaluate_with_scorer(mobility, &profile, scorer.as_ref()),
+            None => hamiltonian::evaluate_hamiltonian(mobility, &profile, &self.config.weights),
+        };
+        trace_event!(
+            debug,
+            mean_energy = hamiltonian_result.mean_energy,
+            energy_variance = hamiltonian_result.energy_variance,
+            red_alerts = hamiltonian_result.alert_count.red,
+            "hamiltonian scored"
         );
 
-        let verdict = Verdict {
+        // --- 5. Replay Periodicity ---
+        let replay_result = replay::replay_periodicity(&chain.breadcrumbs);
+        trace_event!(debug, periodicity = replay_result.replay_periodicity, "replay checked");
+
+        // --- 6. Diurnal Periodicity ---
+        let timestamps: Vec<_> = chain.breadcrumbs.iter().map(|b| b.timestamp).collect();
+        let diurnal_strength = diurnal::diurnal_strength(&timestamps).ok().map(|d| d.strength);
+
+        // --- 7. Waiting Times Between Moves ---
+        let waiting_result = self.fit_waiting_times(&displacement_km, &interval_seconds);
+
+        // --- 8. Resolution vs movement scale ---
+        let recommended_resolution = mobility.resolution_report().recommended_resolution();
+        if recommended_resolution.is_some() {
+            trace_event!(
+                warn,
+                recommended = ?recommended_resolution,
+                "H3 resolution too coarse for this chain's movement"
+            );
+        }
+
+        // --- 9. Sensor context liveness ---
+        let context_low_liveness = chain.context_report().is_low_liveness();
+        if context_low_liveness {
+            trace_event!(warn, "context digests implausibly repetitive");
+        }
+
+        // --- 10. Place clusters ---
+        let ClusterPolicy { eps_km, min_points, .. } = self.config.clusters;
+        let clusters = chain.spatial_clusters(eps_km, min_points);
+        let noise = noise_fraction(&clusters, chain.len());
+
+        // --- 11. Directional persistence ---
+        let directional_persistence = mobility.directional_persistence();
+
+        Ok(RawAnalysis {
+            analysis_digest: analysis_digest(&self.config, self.scorer.as_deref())?,
+            psd: psd_result,
+            levy: levy_result,
+            hamiltonian: hamiltonian_result,
+            replay: replay_result,
+            path_psd,
+            waiting_times: waiting_result,
+            intervals: exponent_intervals,
+            displacements: displacement_km,
+            checks: ChainChecks {
+                diurnal_strength,
+                recommended_resolution,
+                low_liveness: context_low_liveness,
+                low_cluster_structure: false,
+                poor_accuracy_share,
+                directional_persistence,
+            },
+            dwell_low_liveness: chain.dwell_report().is_low_liveness(),
+            cluster_noise: noise,
+            chain_length: chain.len(),
+            unique_cells: chain.unique_cells(),
+            radius_of_gyration_km: chain.radius_of_gyration(),
+            liveness: LivenessReport::from_timestamps(&timestamps, self.clock.now(), &self.config.liveness),
+            accuracy,
+            profile,
+        })
+    }
+
+    /// The verdict on `raw` under `config`, which must match the
+    /// config `raw` was analyzed under beyond the verdict thresholds.
+    fn verdict_unchecked(&self, raw: &RawAnalysis, config: &CriticalityConfig) -> CriticalityResult {
+        let checks = ChainChecks {
+            low_cluster_structure: raw.cluster_noise > config.clusters.max_noise_fraction,
+            ..raw.checks
+        };
+
+        // --- 12. Compute Trust Score ---
+        let (trust_score, confidence, is_human, is_inconclusive, verdict) =
+            match config.stationary.trust_ceiling(&raw.displacements) {
+                Some(trust_ceiling) => Self::compute_stationary_verdict(
+                    config,
+                    &raw.hamiltonian,
+                    checks.diurnal_strength,
+                    raw.dwell_low_liveness || checks.low_liveness,
+                    raw.chain_length,
+                    trust_ceiling,
+                ),
+                None => Self::compute_verdict(
+                    config,
+                    &raw.psd,
+                    &raw.levy,
+                    &raw.hamiltonian,
+                    &checks,
+                    &raw.displacements,
+                ),
+            };
+
+        CriticalityResult {
+            psd: raw.psd.clone(),
+            levy: raw.levy.clone(),
+            hamiltonian: raw.hamiltonian.clone(),
+            replay: raw.replay.clone(),
+            path_psd: raw.path_psd.clone(),
+            trust_score,
+            confidence,
+            chain_length: raw.chain_length,
+            unique_cells: raw.unique_cells,
+            radius_of_gyration_km: raw.radius_of_gyration_km,
+            diurnal_strength: checks.diurnal_strength,
+            directional_persistence: checks.directional_persistence,
+            waiting_times: raw.waiting_times.clone(),
+            intervals: raw.intervals.clone(),
+            is_human,
+            is_inconclusive,
+            liveness: raw.liveness.clone(),
+            accuracy: raw.accuracy.clone(),
+            verdict,
+            calibration: self.calibration.clone(),
+            profile: self.export_profile.then(|| raw.profile.clone()),
+        }
+    }
+
+    /// PSD of the chain's path checked against the displacement α, if
+    /// it can be computed.
+    fn cross_check_path(
+        &self,
+        chain: &BreadcrumbChain,
+        intervals: &[f64],
+        displacement_alpha: f64,
+        min_alpha_excess: f64,
+    ) -> Option<PathPsdResult> {
+        let result = psd::path_cross_check(
+            &chain.path_series(),
+            intervals,
+            displacement_alpha,
+            &self.config.psd,
+            min_alpha_excess,
+        ).ok()?;
+        trace_event!(
+            debug,
+            alpha = result.psd.alpha,
+            alpha_excess = result.alpha_excess,
+            suspicious = result.suspicious,
+            "path psd cross-checked"
+        );
+        Some(result)
+    }
+
+    /// Bootstrap intervals on α and β, if the fidelity asks for them
+    /// and both were measured.
+    fn exponent_intervals(
+        &self,
+        psd: &PsdResult,
+        displacements: &[f64],
+        x_min: f64,
+    ) -> Option<ExponentIntervals> {
+        let iterations = self.config.fidelity.bootstrap_iterations();
+        if iterations == 0 || psd.classification == PsdClassification::NotMeasured {
+            return None;
+        }
+        let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+        let alpha = percentile_interval(psd::bootstrap_alpha(psd, self.config.psd.fit, iterations, &mut rng))?;
+        let beta = percentile_interval(levy::bootstrap_beta(displacements, x_min, iterations, &mut rng))?;
+        trace_event!(debug, ?alpha, ?beta, iterations, "exponent intervals bootstrapped");
+        Some(ExponentIntervals { alpha, beta, iterations })
+    }
+
+    /// Fit the waiting times between moves, if there are enough.
+    fn fit_waiting_times(&self, displacements: &[f64], intervals: &[f64]) -> Option<WaitingTimeResult> {
+        if !self.config.fidelity.fits_waiting_times() {
+            return None;
+        }
+        let waits = waiting::waiting_times(displacements, intervals);
+        let result = waiting::fit_waiting_times(&waits, self.config.waiting_t_min_seconds).ok()?;
+        trace_event!(
+            debug,
+            exponent = result.exponent,
+            likelihood_ratio = result.likelihood_ratio,
+            classification = result.classification.label(),
+            "waiting times fit"
+        );
+        Some(result)
+    }
+
+    /// PSD share of the trust score [0, 1]: `psd_closeness` inside
+    /// the biological band, 0 outside.
+    fn psd_score(config: &CriticalityConfig, psd: &PsdResult) -> f64 {
+        let psd_in_band = psd.alpha >= config.alpha_min
+            && psd.alpha <= config.alpha_max
+            && psd.r_squared >= config.min_psd_r_squared;
+        if psd_in_band { Self::psd_closeness(config, psd) } else { 0.0 }
+    }
+
+    /// How close α is to the center of the biological band, weighted
+    /// by the fit's R²; negative outside the band.
+    fn psd_closeness(config: &CriticalityConfig, psd: &PsdResult) -> f64 {
+        let center = (config.alpha_min + config.alpha_max) / 2.0;
+        let range = (config.alpha_max - config.alpha_min) / 2.0;
+        let distance = (psd.alpha - center).abs() / range;
+        (1.0 - distance) * psd.r_squared
+    }
+
+    /// Lévy share of the trust score [0, 1]: `levy_closeness` inside
+    /// the human band, 0 outside.
+    fn levy_score(config: &CriticalityConfig, levy: &LevyResult) -> f64 {
+        let levy_pass = levy.beta >= config.beta_min
+            && levy.beta <= config.beta_max
+            && levy.ks_statistic < 0.15;
+        if levy_pass { Self::levy_closeness(config, levy) } else { 0.0 }
+    }
+
+    /// How close β is to the center of the human band, weighted by the
+    /// KS fit; negative outside the band.
+    fn levy_closeness(config: &CriticalityConfig, levy: &LevyResult) -> f64 {
+        let center = (config.beta_min + config.beta_max) / 2.0;
+        let range = (config.beta_max - config.beta_min) / 2.0;
+        let distance = (levy.beta - center).abs() / range;
+        (1.0 - distance) * (1.0 - levy.ks_statistic)
+    }
+
+    /// Compute the final verdict from individual analyses.
+    fn compute_verdict(
+        config: &CriticalityConfig,
+        psd: &PsdResult,
+        levy: &LevyResult,
+        hamiltonian: &ChainHamiltonianResult,
+        checks: &ChainChecks,
+        displacements: &[f64],
+    ) -> (f64, f64, bool, bool, Verdict) {
+        let ChainChecks {
+            diurnal_strength,
+            recommended_resolution,
+            low_liveness,
+            low_cluster_structure,
+            poor_accuracy_share,
+            directional_persistence,
+        } = *checks;
+        let constant_step = Self::is_constant_step(config, displacements);
+
+        // PSD check: α in biological range, from a clean enough fit?
+        let spectral_fit_pass = psd.r_squared >= config.min_psd_r_squared;
+        let alpha_in_band = psd.alpha >= config.alpha_min
+            && psd.alpha <= config.alpha_max;
+        let psd_in_band = alpha_in_band && spectral_fit_pass;
+
+        // ...or in the widened StrongCorrelation band, if allowed
+        let (alpha_reduced, psd_reduced, confidence_factor) = match config.strong_correlation {
+            StrongCorrelationPolicy::AcceptReduced { alpha_ceiling, confidence_factor } => {
+                let in_band = psd.alpha > config.alpha_max && psd.alpha <= alpha_ceiling;
+                let accepted = in_band && spectral_fit_pass;
+                (in_band, accepted, if accepted { confidence_factor } else { 1.0 })
+            }
+            StrongCorrelationPolicy::Reject => (false, false, 1.0),
+        };
+        let psd_pass = psd_in_band || psd_reduced;
+        let psd_failure = match psd_pass {
+            true => None,
+            false if constant_step => None,
+            false if alpha_in_band || alpha_reduced => Some(FailureReason::PoorSpectralFit),
+            false => Some(FailureReason::AlphaOutOfRange),
+        };
+
+        // Lévy check: β in human range?
+        let levy_pass = levy.beta >= config.beta_min
+            && levy.beta <= config.beta_max
+            && levy.ks_statistic < 0.15;
+
+        // Hamiltonian check: low mean energy, few red alerts?
+        let red_fraction = hamiltonian.alert_count.red as f64
+            / hamiltonian.scores.len().max(1) as f64;
+        // ...but not implausibly uniform?
+        let over_smooth = hamiltonian.energy_variance < config.min_energy_variance
+            || hamiltonian.degenerate_spread;
+        let hamiltonian_pass = hamiltonian.mean_energy < 0.4
+            && red_fraction < 0.05
+            && !over_smooth;
+
+        // Diurnal check: does activity follow a day/night cycle?
+        let diurnal_pass = diurnal_strength
+            .is_none_or(|strength| strength >= config.min_diurnal_strength);
+
+        // Liveness check: does the sensor context keep changing?
+        let liveness_pass = !low_liveness;
+
+        // Heading check: does the chain ever turn back? Flagged, not
+        // gating: a relocation or road trip keeps its heading too
+        let directional_pass = directional_persistence
+            .is_none_or(|persistence| persistence <= config.max_directional_persistence);
+
+        // Place clusters: flagged, not gating
+        let cluster_structure_pass = !low_cluster_structure;
+
+        // Confidence: increases with chain length
+        // Per TRIP spec convergence analysis (default model):
+        // 64 → 0.3 confidence, 200 → 0.7, 500+ → 0.95
+        // Sufficiency is judged on chain length alone; the reported
+        // confidence counts independent samples instead (see
+        // `BreadcrumbChain::effective_sample_size`), and a reduced PSD
+        // acceptance, a too-coarse resolution or a persistent heading
+        // lowers it further.
+        // Human movement is autocorrelated by nature, so gating on
+        // the effective length would leave most of it Inconclusive.
+        // Poor-accuracy fixes count for neither (see `AccuracyPolicy`).
+        let accurate_fixes = ((displacements.len() + 1) as f64 * (1.0 - poor_accuracy_share)).round() as usize;
+        let confidence_sufficient =
+            config.confidence.confidence(accurate_fixes) >= config.min_confidence;
+        let convergence = config.confidence.confidence_for(effective_chain_length(displacements));
+        let resolution_factor = if recommended_resolution.is_some() {
+            config.coarse_resolution_confidence_factor
+        } else {
+            1.0
+        };
+        let heading_factor = if directional_pass {
+            1.0
+        } else {
+            config.persistent_heading_confidence_factor
+        };
+        let confidence = convergence * confidence_factor * resolution_factor * heading_factor
+            * (1.0 - poor_accuracy_share);
+
+        // Trust score [0, 100]:
+        // 40% from PSD (scaled by how close α is to center of range)
+        // 25% from Lévy
+        // 25% from Hamiltonian
+        // 10% from chain length / confidence
+        // A reduced acceptance earns no PSD credit
+        let psd_score = Self::psd_score(config, psd);
+        let levy_score = Self::levy_score(config, levy);
+
+        let ham_score = if hamiltonian_pass {
+            1.0 - hamiltonian.mean_energy
+        } else {
+            (0.4 - hamiltonian.mean_energy).max(0.0) / 0.4
+        };
+
+        let trust_score = (
+            40.0 * psd_score
+            + 25.0 * levy_score
+            + 25.0 * ham_score
+            + 10.0 * confidence
+        ).clamp(0.0, 100.0);
+
+        let is_human = psd_pass && levy_pass && hamiltonian_pass && diurnal_pass
+            && liveness_pass && !constant_step && confidence_sufficient;
+        let is_inconclusive = !confidence_sufficient;
+        let outcome = Outcome::from_flags(is_human, is_inconclusive);
+
+        let summary = format!(
+            "PSD α={:.3} ({}), Lévy β={:.3} ({}), H_mean={:.3} ({}), diurnal={} ({}), confidence={:.2} ({}{}{}){}{}{}{}. {}",
+            psd.alpha, match (psd_pass, psd_reduced, &psd_failure) {
+                (true, false, _) => "PASS",
+                (true, true, _) => "PASS, reduced",
+                (false, _, Some(FailureReason::PoorSpectralFit)) => "FAIL, poor fit",
+                _ => "FAIL",
+            },
+            levy.beta, if levy_pass { "PASS" } else { "FAIL" },
+            hamiltonian.mean_energy, if hamiltonian_pass { "PASS" } else { "FAIL" },
+            diurnal_strength.map_or("n/a".to_string(), |d| format!("{d:.3}")),
+            if diurnal_pass { "PASS" } else { "FAIL" },
+            confidence, if confidence_sufficient { "PASS" } else { "FAIL" },
+            recommended_resolution.map_or(String::new(), |r| format!(", coarse resolution, use {r}")),
+            match poor_accuracy_share > 0.0 {
+                true => format!(", {:.0}% poor accuracy", 100.0 * poor_accuracy_share),
+                false => String::new(),
+            },
+            if liveness_pass { "" } else { ", repetitive context" },
+            if directional_pass { "" } else { ", persistent heading" },
+            if cluster_structure_pass { "" } else { ", no place clusters" },
+            if constant_step { ", constant step" } else { "" },
+            outcome.label(),
+        );
+
+        let verdict = Verdict {
             psd_pass,
+            psd_reduced,
+            psd_failure,
             levy_pass,
             hamiltonian_pass,
+            over_smooth,
+            directional_pass,
+            diurnal_pass,
+            recommended_resolution,
+            stationary: false,
+            constant_step,
+            liveness_pass,
+            cluster_structure_pass,
             confidence_sufficient,
             summary,
         };
 
-        (trust_score, confidence, is_human, verdict)
+        trace_event!(
+            info,
+            trust_score,
+            confidence,
+            outcome = outcome.label(),
+            "verdict"
+        );
+
+        (trust_score, confidence, is_human, is_inconclusive, verdict)
     }
-}
 
-/// Confidence as a function of chain length.
-/// Models the convergence of statistical estimators:
-///   c(n) = 1 - exp(-n / τ)
-/// where τ = 200 (characteristic convergence length)
-fn convergence_confidence(chain_length: usize) -> f64 {
-    let tau = 200.0;
-    1.0 - (-(chain_length as f64) / tau).exp()
+    /// Whether the displacements are all non-zero (beyond the
+    /// profile's degenerate-spread tolerance) and nearly the same
+    /// length, their coefficient of variation at most
+    /// `CONSTANT_STEP_MAX_CV`: a bot stepping a fixed distance each
+    /// interval, jittered or not. The spread is relative so that metres
+    /// of jitter on a kilometre step don't hide it. Zero steps are left
+    /// to `StationaryPolicy`.
+    fn is_constant_step(config: &CriticalityConfig, displacements: &[f64]) -> bool {
+        let tolerance = config.profile.degenerate_spread.tolerance;
+        if displacements.is_empty() || !displacements.iter().all(|&d| d > tolerance) {
+            return false;
+        }
+        let n = displacements.len() as f64;
+        let mean = displacements.iter().sum::<f64>() / n;
+        let variance = displacements.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / n;
+        variance.sqrt() <= CONSTANT_STEP_MAX_CV * mean
+    }
+
+    /// Verdict for a stationary chain (see `StationaryPolicy`), from
+    /// liveness, the day/night cycle and Hamiltonian energy only.
+    fn compute_stationary_verdict(
+        config: &CriticalityConfig,
+        hamiltonian: &ChainHamiltonianResult,
+        diurnal_strength: Option<f64>,
+        low_liveness: bool,
+        chain_length: usize,
+        trust_ceiling: f64,
+    ) -> (f64, f64, bool, bool, Verdict) {
+        let diurnal_pass = diurnal_strength
+            .is_none_or(|strength| strength >= config.min_diurnal_strength);
+        let liveness_pass = !low_liveness;
+
+        // A constant (zero) displacement spread is expected here, so
+        // only the energy itself counts
+        let red_fraction = hamiltonian.alert_count.red as f64
+            / hamiltonian.scores.len().max(1) as f64;
+        let hamiltonian_pass = hamiltonian.mean_energy < 0.4 && red_fraction < 0.05;
+        let ham_score = (0.4 - hamiltonian.mean_energy).max(0.0) / 0.4;
+
+        let confidence = config.confidence.confidence(chain_length);
+        let confidence_sufficient = confidence >= config.min_confidence;
+        let trust_score = (25.0 * ham_score + 10.0 * confidence).clamp(0.0, trust_ceiling);
+
+        let is_inconclusive = !confidence_sufficient || (diurnal_pass && liveness_pass && hamiltonian_pass);
+        let outcome = Outcome::from_flags(false, is_inconclusive);
+
+        let summary = format!(
+            "Stationary chain, mobility not measured; H_mean={:.3} ({}), diurnal={} ({}), liveness {}, confidence={:.2} ({}). {}",
+            hamiltonian.mean_energy, if hamiltonian_pass { "PASS" } else { "FAIL" },
+            diurnal_strength.map_or("n/a".to_string(), |d| format!("{d:.3}")),
+            if diurnal_pass { "PASS" } else { "FAIL" },
+            if liveness_pass { "PASS" } else { "FAIL" },
+            confidence, if confidence_sufficient { "PASS" } else { "FAIL" },
+            outcome.label(),
+        );
+
+        let verdict = Verdict {
+            psd_pass: false,
+            psd_reduced: false,
+            psd_failure: None,
+            levy_pass: false,
+            hamiltonian_pass,
+            over_smooth: false,
+            directional_pass: true,
+            diurnal_pass,
+            recommended_resolution: None,
+            stationary: true,
+            constant_step: false,
+            liveness_pass,
+            cluster_structure_pass: true,
+            confidence_sufficient,
+            summary,
+        };
+
+        trace_event!(
+            info,
+            trust_score,
+            confidence,
+            outcome = outcome.label(),
+            "verdict"
+        );
+
+        (trust_score, confidence, false, is_inconclusive, verdict)
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    struct ConstantScorer(f64);
+
+    impl AnomalyScorer for ConstantScorer {
+        fn score(
+            &self,
+            _current: &crate::breadcrumb::Breadcrumb,
+            _prev: Option<&crate::breadcrumb::Breadcrumb>,
+            _profile: &BehavioralProfile,
+        ) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_custom_scorer_drives_verdict() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 5)).unwrap();
+
+        let default = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        assert!(default.verdict.hamiltonian_pass);
+
+        let engine = CriticalityEngine::with_defaults().with_scorer(Box::new(ConstantScorer(0.9)));
+        let result = engine.evaluate(&chain).unwrap();
+        assert!((result.hamiltonian.mean_energy - 0.9).abs() < 1e-12);
+        assert_eq!(result.hamiltonian.alert_count.red, chain.len());
+        assert!(!result.verdict.hamiltonian_pass);
+        assert!(!result.is_human);
+        assert!(result.trust_score < default.trust_score);
+    }
+
+    #[test]
+    fn test_evaluate_displacements_matches_full_path() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 9)).unwrap();
+        let engine = CriticalityEngine::with_defaults();
+
+        let full = engine.evaluate(&chain).unwrap();
+        let blind = engine
+            .evaluate_displacements(&chain.displacement_series(), &chain.interval_series())
+            .unwrap();
+
+        assert_eq!(blind.chain_length, full.chain_length);
+        assert_eq!(blind.psd.alpha, full.psd.alpha);
+        assert_eq!(blind.levy.beta, full.levy.beta);
+        assert_eq!(blind.levy.kappa_km, full.levy.kappa_km);
+        assert_eq!(blind.confidence, full.confidence);
+        let (blind_waits, full_waits) = (blind.waiting_times.unwrap(), full.waiting_times.unwrap());
+        assert_eq!(blind_waits.exponent, full_waits.exponent);
+        assert_eq!(blind_waits.classification, full_waits.classification);
+
+        // Spatial and structure components agree; the rest are absent
+        assert_eq!(blind.hamiltonian.scores.len(), full.hamiltonian.scores.len() - 1);
+        for (b, f) in blind.hamiltonian.scores.iter().zip(&full.hamiltonian.scores[1..]) {
+            assert_eq!(b.index, f.index);
+            assert!((b.h_spatial - f.h_spatial).abs() < 1e-12);
+            assert!((b.h_structure - f.h_structure).abs() < 1e-12);
+            assert_eq!(b.h_temporal, 0.0);
+            assert_eq!(b.h_kinetic, 0.0);
+        }
+    }
+
+    /// Lévy-distributed displacements (β ≈ 1) ordered along a slowly
+    /// drifting 1/f² signal: a highly routine mover whose trip lengths
+    /// change little from one breadcrumb to the next.
+    fn routine_displacements(n: usize, seed: u64) -> Vec<f64> {
+        ordered_displacements(n, seed, 1.1)
+    }
+
+    /// Lévy-distributed displacements (β ≈ 1) ordered along a signal
+    /// whose k-th harmonic has amplitude k^-`decay` (a 1/f^(2·decay)
+    /// spectrum).
+    pub(crate) fn ordered_displacements(n: usize, seed: u64, decay: f64) -> Vec<f64> {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        use std::f64::consts::TAU;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let phases: Vec<f64> = (0..n / 2).map(|_| rng.gen_range(0.0..TAU)).collect();
+        let signal: Vec<f64> = (0..n)
+            .map(|t| {
+                phases.iter().enumerate()
+                    .map(|(k, phase)| {
+                        let k = (k + 1) as f64;
+                        k.powf(-decay) * (TAU * k * t as f64 / n as f64 + phase).cos()
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| signal[a].partial_cmp(&signal[b]).unwrap());
+
+        let mut displacements = vec![0.0; n];
+        for (rank, &i) in order.iter().enumerate() {
+            let u = (n - rank) as f64 / (n + 1) as f64;
+            displacements[i] = 0.01 / u;
+        }
+        displacements
+    }
+
+    #[test]
+    fn test_non_finite_displacement_rejected() {
+        let mut displacements = routine_displacements(200, 3);
+        let intervals = vec![900.0; displacements.len()];
+        displacements[17] = f64::NAN;
+        displacements[90] = f64::INFINITY;
+
+        let engine = CriticalityEngine::with_defaults();
+        match engine.evaluate_displacements(&displacements, &intervals) {
+            Err(TripError::NonFinite { series, count, first }) => {
+                assert_eq!((series, count, first), ("displacement_km", 2, 17));
+            }
+            other => panic!("expected NonFinite, got {other:?}"),
+        }
+
+        // The analyses guard their own inputs too
+        assert!(matches!(
+            psd::compute_psd_from_chain(&displacements, &intervals),
+            Err(TripError::NonFinite { count: 2, .. })
+        ));
+        assert!(matches!(levy::fit_levy(&displacements, 0.01), Err(TripError::NonFinite { first: 17, .. })));
+    }
+
+    #[test]
+    fn test_robust_dispersion_keeps_timer_sampled_chain_human() {
+        // Most intervals exactly 1800 s, as from a fixed sampling timer
+        let displacements = ordered_displacements(400, 1, 0.25);
+        let intervals: Vec<f64> = (0..displacements.len())
+            .map(|i| if i % 20 < 11 { 1800.0 } else { 1800.0 + ((i * 37) % 120) as f64 })
+            .collect();
+
+        for robust_dispersion in [false, true] {
+            let mut config = CriticalityConfig::default();
+            config.profile.robust_dispersion = robust_dispersion;
+            let result = CriticalityEngine::new(config)
+                .evaluate_displacements(&displacements, &intervals)
+                .unwrap();
+            assert!(!result.hamiltonian.degenerate_spread, "robust: {robust_dispersion}");
+            assert!(!result.verdict.over_smooth, "robust: {robust_dispersion}");
+            assert!(result.is_human, "robust: {robust_dispersion}: {}", result.verdict.summary);
+        }
+    }
+
+    #[test]
+    fn test_strong_correlation_policy() {
+        let displacements = routine_displacements(400, 1);
+        // Jittered cadence: a constant one is itself flagged as bot-like
+        let intervals: Vec<f64> = (0..displacements.len())
+            .map(|i| 1800.0 + ((i * 37) % 120) as f64)
+            .collect();
+
+        let default = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&displacements, &intervals)
+            .unwrap();
+        assert_eq!(default.psd.classification, psd::PsdClassification::StrongCorrelation);
+        assert!(default.verdict.levy_pass && default.verdict.hamiltonian_pass);
+        assert!(!default.verdict.psd_pass);
+        assert!(!default.is_human);
+
+        let widened = CriticalityEngine::new(CriticalityConfig {
+            strong_correlation: StrongCorrelationPolicy::widened(),
+            ..Default::default()
+        })
+        .evaluate_displacements(&displacements, &intervals)
+        .unwrap();
+        assert!(widened.verdict.psd_pass);
+        assert!(widened.verdict.psd_reduced);
+        assert!(widened.is_human);
+        assert!((widened.confidence - 0.75 * default.confidence).abs() < 1e-12);
+        // No PSD credit: the trust score stays below the 60-point tiers
+        assert!(widened.trust_score < 60.0, "trust: {}", widened.trust_score);
+    }
+
+    #[test]
+    fn test_estimated_false_accept_rate() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let table = CalibrationTable::new(
+            vec![0.0, 100.0],
+            vec![100, 400],
+            vec![vec![0.4, 0.02], vec![0.2, 0.01]],
+        ).unwrap();
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(250, 5)).unwrap();
+
+        let result = CriticalityEngine::with_defaults()
+            .with_calibration(table.clone())
+            .evaluate(&chain)
+            .unwrap();
+        let expected = table.false_accept_rate(result.trust_score, 250);
+        assert_eq!(result.estimated_false_accept_rate(), expected);
+        assert!(expected > 0.01 && expected < 0.4);
+
+        let uncalibrated = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        assert_eq!(uncalibrated.estimated_false_accept_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_coarse_resolution_lowers_confidence() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        // Errands within a few hundred meters, recorded at `resolution`
+        let errands = |resolution: h3o::Resolution| {
+            let mut rng = StdRng::seed_from_u64(4);
+            let (mut lat, mut lng) = (41.9028f64, 12.4964f64);
+            let mut t = 1_700_000_000i64;
+            let mut samples = Vec::new();
+            for _ in 0..300 {
+                let cell = h3o::LatLng::new(lat, lng).unwrap().to_cell(resolution);
+                samples.push((t, cell.to_string()));
+                let step_km = rng.gen_range(0.05..0.4);
+                let heading: f64 = rng.gen_range(0.0..std::f64::consts::TAU);
+                lat += step_km / 111.0 * heading.cos();
+                lng += step_km / 83.0 * heading.sin();
+                t += rng.gen_range(600..3600);
+            }
+            let refs: Vec<(i64, &str)> = samples.iter().map(|(t, c)| (*t, c.as_str())).collect();
+            let mut breadcrumbs = synthetic_breadcrumbs(&refs);
+            for b in &mut breadcrumbs {
+                b.location_resolution = u8::from(resolution);
+            }
+            BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap()
+        };
+        let engine = CriticalityEngine::with_defaults();
+
+        let coarse = errands(h3o::Resolution::Seven);
+        let report = coarse.resolution_report();
+        assert_eq!(report.resolution, 7);
+        assert!(report.zero_share > 0.5, "zero share = {}", report.zero_share);
+        assert!(report.is_too_coarse());
+        let coarse_result = engine.evaluate(&coarse).unwrap();
+        assert_eq!(coarse_result.verdict.recommended_resolution, Some(9));
+        assert!(coarse_result.verdict.summary.contains("coarse resolution, use 9"));
+
+        let fine = errands(h3o::Resolution::Ten);
+        assert!(!fine.resolution_report().is_too_coarse());
+        let fine_result = engine.evaluate(&fine).unwrap();
+        assert_eq!(fine_result.verdict.recommended_resolution, None);
+
+        // Convergence follows each chain's effective length; only the
+        // coarse one is halved
+        let model = ConfidenceModel::default();
+        let convergence = |chain: &BreadcrumbChain| model.confidence_for(chain.effective_sample_size());
+        assert!((coarse_result.confidence - 0.5 * convergence(&coarse)).abs() < 1e-12);
+        assert!((fine_result.confidence - convergence(&fine)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_inconclusive_vs_not_human() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+        let engine = CriticalityEngine::with_defaults();
+
+        // Random-walk generator over a long chain: enough data, fails PSD
+        let bot = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+        let result = engine.evaluate(&bot).unwrap();
+        assert!(!result.verdict.psd_pass);
+        assert!(!result.is_inconclusive);
+        assert_eq!(result.outcome(), Outcome::NotHuman);
+        assert_eq!(engine.classify(&bot).unwrap(), Outcome::NotHuman);
+
+        // Analyzable but below the confidence floor
+        let short = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(100, 5)).unwrap();
+        let result = engine.evaluate(&short).unwrap();
+        assert!(result.is_inconclusive);
+        assert!(!result.is_human);
+        assert_eq!(result.outcome(), Outcome::Inconclusive);
+        assert!(result.verdict.summary.ends_with("INCONCLUSIVE"));
+
+        // Too short to analyze at all
+        let tiny = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(30, 5)).unwrap();
+        assert!(engine.evaluate(&tiny).is_err());
+        assert_eq!(engine.classify(&tiny).unwrap(), Outcome::Inconclusive);
+    }
+
+    #[test]
+    fn test_constant_step_chain_flagged() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        let engine = CriticalityEngine::with_defaults();
+
+        // A bot shuttling between two cells on an irregular clock:
+        // every displacement is the same length
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let (a, b) = (cell(41.9028, 12.4964), cell(41.9100, 12.4800));
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut t = 1_700_000_000i64;
+        let samples: Vec<(i64, &str)> = (0..300)
+            .map(|i| {
+                t += rng.gen_range(600..3600);
+                (t, if i % 2 == 0 { a.as_str() } else { b.as_str() })
+            })
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        // Flagged as NotHuman, not an analysis error or Inconclusive
+        let result = engine.evaluate(&chain).unwrap();
+        assert!(result.verdict.constant_step);
+        assert!(result.verdict.over_smooth);
+        assert_eq!(result.psd.classification, PsdClassification::NotMeasured);
+        assert_eq!(result.levy.classification, crate::levy::LevyClassification::NotMeasured);
+        assert_eq!(result.outcome(), Outcome::NotHuman);
+        assert!(result.verdict.summary.contains("constant step"), "{}", result.verdict.summary);
+        assert_eq!(engine.classify(&chain).unwrap(), Outcome::NotHuman);
+
+        // Same in location-blind mode
+        let displacements = vec![0.5; 299];
+        let intervals: Vec<f64> = (0..299).map(|_| rng.gen_range(600.0..3600.0)).collect();
+        let result = engine.evaluate_displacements(&displacements, &intervals).unwrap();
+        assert!(result.verdict.constant_step);
+        assert_eq!(result.outcome(), Outcome::NotHuman);
+
+        // Jitter, even of tens of metres, or a single odd step doesn't
+        // hide a constant stride
+        for jitter in [0.001, 0.05] {
+            let jittered: Vec<f64> = displacements.iter()
+                .map(|d| d + rng.gen_range(-jitter..jitter))
+                .collect();
+            let result = engine.evaluate_displacements(&jittered, &intervals).unwrap();
+            assert!(result.verdict.constant_step, "jitter {jitter} km");
+            assert_eq!(result.outcome(), Outcome::NotHuman);
+        }
+        let mut varied = displacements.clone();
+        varied[100] = 0.8;
+        assert!(CriticalityEngine::is_constant_step(engine.config(), &varied));
+
+        // Varied steps, or any step at zero, are measured as usual
+        let lengths: Vec<f64> = (0..299).map(|i| 0.5 + 0.4 * ((i % 7) as f64 - 3.0) / 3.0).collect();
+        assert!(!CriticalityEngine::is_constant_step(engine.config(), &lengths));
+        let mut paused = displacements.clone();
+        paused[50] = 0.0;
+        assert!(!CriticalityEngine::is_constant_step(engine.config(), &paused));
+    }
+
+    #[test]
+    fn test_over_smooth_chain_flagged() {
+        use crate::chain::tests::{random_walk_breadcrumbs, synthetic_breadcrumbs};
+        let engine = CriticalityEngine::with_defaults();
+
+        // Scripted commute: home and work, every 30 minutes, forever
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let (home, work) = (cell(41.9028, 12.4964), cell(41.9100, 12.4800));
+        let samples: Vec<(i64, &str)> = (0..200)
+            .map(|i| (1_700_000_000 + i * 1800, if i % 2 == 0 { home.as_str() } else { work.as_str() }))
+            .collect();
+        let perfect = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+        let result = engine.evaluate(&perfect).unwrap();
+        assert!(result.hamiltonian.mean_energy < 0.4);
+        assert!(result.hamiltonian.degenerate_spread);
+        assert!(result.verdict.over_smooth);
+        assert!(!result.verdict.hamiltonian_pass);
+
+        let varied = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 5)).unwrap();
+        let result = engine.evaluate(&varied).unwrap();
+        assert!(result.hamiltonian.energy_variance > 1e-4, "{}", result.hamiltonian.energy_variance);
+        assert!(!result.verdict.over_smooth);
+        assert!(result.verdict.hamiltonian_pass);
+    }
+
+    #[test]
+    fn test_diurnal_check_in_verdict() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        // Round-the-clock sampling over ~7 days
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        let strength = result.diurnal_strength.unwrap();
+        assert!(strength < 0.1, "strength = {strength}");
+
+        let strict = CriticalityEngine::new(CriticalityConfig {
+            min_diurnal_strength: 0.5,
+            ..Default::default()
+        });
+        let result = strict.evaluate(&chain).unwrap();
+        assert!(!result.verdict.diurnal_pass);
+        assert!(!result.is_human);
+        assert!(result.verdict.summary.contains("diurnal="));
+
+        // Location-blind mode has no timestamps to measure
+        let result = strict
+            .evaluate_displacements(&chain.displacement_series(), &chain.interval_series())
+            .unwrap();
+        assert!(result.diurnal_strength.is_none());
+        assert!(result.verdict.diurnal_pass);
+    }
+
+    #[test]
+    fn test_flat_multi_day_bot_fails_diurnal() {
+        use crate::chain::tests::{random_walk_breadcrumbs, synthetic_breadcrumbs};
+
+        // Plausible movement, but one breadcrumb every 20 minutes for 7 days
+        let walk = random_walk_breadcrumbs(7 * 72, 5);
+        let samples: Vec<(i64, &str)> = walk.iter().enumerate()
+            .map(|(i, b)| (1_700_006_400 + i as i64 * 1200, b.location_cell.as_str()))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        assert_eq!(result.diurnal_strength, Some(0.0));
+        assert!(!result.verdict.diurnal_pass);
+        assert!(!result.is_human);
+    }
+
+    #[test]
+    fn test_path_psd_flags_retraced_path() {
+        use crate::chain::tests::{random_walk_breadcrumbs, synthetic_breadcrumbs};
+
+        let organic = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(512, 7)).unwrap();
+
+        // Replay: the organic step lengths, each walked out along one
+        // street and straight back, so the path keeps retracing itself
+        let (lat, lng) = (41.9028f64, 12.4964f64);
+        let cell = |km: f64| {
+            let lng = lng + km / (111.32 * lat.to_radians().cos());
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let cells: Vec<String> = organic.displacement_series().iter()
+            .take(organic.len() / 2)
+            .flat_map(|&d| [cell(0.0), cell(d)])
+            .collect();
+        let samples: Vec<(i64, &str)> = organic.breadcrumbs.iter().zip(&cells)
+            .map(|(b, c)| (b.timestamp.timestamp(), c.as_str()))
+            .collect();
+        let replay = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        assert!(CriticalityEngine::with_defaults().evaluate(&organic).unwrap().path_psd.is_none());
+
+        let engine = CriticalityEngine::new(CriticalityConfig {
+            path_psd: PathPsdPolicy::cross_check(),
+            ..Default::default()
+        });
+        let organic_path = engine.evaluate(&organic).unwrap().path_psd.unwrap();
+        let replay_path = engine.evaluate(&replay).unwrap().path_psd.unwrap();
+        assert!(!organic_path.suspicious, "organic excess {}", organic_path.alpha_excess);
+        assert!(replay_path.suspicious, "replay excess {}", replay_path.alpha_excess);
+        assert!(organic_path.alpha_excess - replay_path.alpha_excess > 1.0);
+    }
+
+    #[test]
+    fn test_exported_profile_anchors_most_visited_cells() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let (home, work, shop) = (cell(41.9028, 12.4964), cell(41.9100, 12.4800), cell(41.8950, 12.5100));
+        // Home 50%, work 30%, shop 10%, and a different one-off cell 10% of the time
+        let cells: Vec<String> = (0..200)
+            .map(|i| match i % 10 {
+                0..=4 => home.clone(),
+                5..=7 => work.clone(),
+                8 => shop.clone(),
+                _ => cell(41.80 + i as f64 * 0.001, 12.40),
+            })
+            .collect();
+        let samples: Vec<(i64, &str)> = cells.iter().enumerate()
+            .map(|(i, c)| (1_700_000_000 + i as i64 * 1800 + (i as i64 * 37) % 600, c.as_str()))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        assert!(CriticalityEngine::with_defaults().evaluate(&chain).unwrap().profile.is_none());
+
+        let engine = CriticalityEngine::with_defaults().with_profile_export();
+        let (result, report) = engine.evaluate_with_privacy_report(&chain).unwrap();
+        let profile = result.profile.as_ref().unwrap();
+
+        let mut anchors = profile.anchor_cells.clone();
+        anchors.sort();
+        let mut expected = vec![home.clone(), work.clone(), shop.clone()];
+        expected.sort();
+        assert_eq!(anchors, expected);
+        assert!(profile.cell_histogram[&home] > profile.cell_histogram[&work]);
+
+        // The export is an inspectable JSON artifact
+        let json = serde_json::to_string(profile).unwrap();
+        assert!(json.contains(&home) && json.contains("hourly_profile"));
+
+        assert!(report.retained.iter().any(|q| q.name == "profile"));
+        assert!(report.contains_raw_location());
+        assert!(!report.discarded.contains(&"anchor_cells"));
+    }
+
+    #[test]
+    fn test_stationary_chain_inconclusive() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        // Homebound: one cell for a week, active 07:00-23:00 only
+        let day_start = 1_700_006_400i64; // 00:00 UTC
+        let homebound: Vec<(i64, &str)> = (0..7)
+            .flat_map(|day| (0..33).map(move |k| {
+                (day_start + day * 86_400 + 7 * 3600 + k * 1800 + (k * 37) % 300, "8a1e8052da1ffff")
+            }))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&homebound)).unwrap();
+
+        let engine = CriticalityEngine::with_defaults();
+        let result = engine.evaluate(&chain).unwrap();
+        assert!(result.verdict.stationary);
+        assert!(result.verdict.diurnal_pass && result.verdict.liveness_pass);
+        assert_eq!(result.psd.classification, psd::PsdClassification::NotMeasured);
+        assert_eq!(result.levy.classification, levy::LevyClassification::NotMeasured);
+        assert_eq!(result.outcome(), Outcome::Inconclusive);
+        assert!(result.trust_score <= 30.0);
+        assert_eq!(engine.classify(&chain).unwrap(), Outcome::Inconclusive);
+
+        // Without the policy the mobility analyses can't run
+        let analyze = CriticalityEngine::new(CriticalityConfig {
+            stationary: StationaryPolicy::Analyze,
+            ..Default::default()
+        });
+        assert!(analyze.evaluate(&chain).is_err());
+
+        // A phone on a charger around the clock fails the temporal fallback
+        let charger: Vec<(i64, &str)> = (0..7 * 58)
+            .map(|k| (day_start + k * 1500, "8a1e8052da1ffff"))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&charger)).unwrap();
+        let result = engine.evaluate(&chain).unwrap();
+        assert!(result.verdict.stationary);
+        assert!(!result.verdict.diurnal_pass);
+        assert_eq!(result.outcome(), Outcome::NotHuman);
+    }
+
+    #[test]
+    fn test_static_bot_not_softened() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        // One cell every 20 minutes around the clock for a week, with
+        // and without an occasional hop to the next cell
+        let engine = CriticalityEngine::with_defaults();
+        for hop_every in [None, Some(50)] {
+            let samples: Vec<(i64, &str)> = (0..7 * 72)
+                .map(|i| {
+                    let hop = hop_every.is_some_and(|every| i % every == 7);
+                    (1_700_006_400 + i as i64 * 1200, if hop { "8a1e8052da17fff" } else { "8a1e8052da1ffff" })
+                })
+                .collect();
+            let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+            let result = engine.evaluate(&chain).unwrap();
+            assert!(result.verdict.stationary, "hop every {hop_every:?}");
+            assert_eq!(result.diurnal_strength, Some(0.0));
+            assert!(!result.verdict.diurnal_pass);
+            assert_eq!(result.outcome(), Outcome::NotHuman, "hop every {hop_every:?}");
+            assert_eq!(engine.classify(&chain).unwrap(), Outcome::NotHuman);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_stage_events_emitted() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// An event's message and field names
+        type Captured = (String, Vec<String>);
+
+        /// Records every event emitted while it is the default subscriber.
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<Captured>>>);
+
+        struct Fields<'a>(&'a mut String, &'a mut Vec<String>);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    *self.0 = format!("{value:?}");
+                } else {
+                    self.1.push(field.name().to_string());
+                }
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+            fn new_span(&self, _: &Attributes<'_>) -> Id { Id::from_u64(1) }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let (mut message, mut fields) = (String::new(), Vec::new());
+                event.record(&mut Fields(&mut message, &mut fields));
+                self.0.lock().unwrap().push((message, fields));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let chain = BreadcrumbChain::from_breadcrumbs(
+            crate::chain::tests::random_walk_breadcrumbs(200, 5)
+        ).unwrap();
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        });
+
+        let events = capture.0.lock().unwrap();
+        let messages: Vec<&str> = events.iter().map(|(m, _)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "psd started", "psd finished", "levy fit", "hamiltonian scored", "replay checked",
+                "waiting times fit", "verdict",
+            ]
+        );
+        assert!(events[1].1.contains(&"alpha".to_string()));
+        assert!(events[2].1.contains(&"beta".to_string()));
+        assert!(events[5].1.contains(&"exponent".to_string()));
+        assert!(events[6].1.contains(&"trust_score".to_string()));
+    }
+
+    #[test]
+    fn test_streaming_progress_events() {
+        use crate::chain::tests::{random_walk_breadcrumbs, sign_breadcrumbs};
+        use std::sync::mpsc::channel;
+
+        let mut breadcrumbs = random_walk_breadcrumbs(250, 5);
+        sign_breadcrumbs(&mut breadcrumbs, &ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]));
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        chain.verify_block_hashes().unwrap();
+
+        let (tx, rx) = channel();
+        CriticalityEngine::with_defaults().evaluate_streaming(&chain, tx);
+        let events: Vec<Progress> = rx.iter().collect();
+
+        let signatures: Vec<(usize, usize)> = events.iter()
+            .filter_map(|e| match e {
+                Progress::VerifyingSignatures { done, total } => Some((*done, *total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(signatures.first(), Some(&(0, 250)));
+        assert_eq!(signatures.last(), Some(&(250, 250)));
+        assert!(signatures.windows(2).all(|w| w[0].0 < w[1].0));
+
+        let stages = &events[signatures.len()..];
+        assert!(matches!(
+            stages,
+            [Progress::ComputingPsd, Progress::FittingLevy, Progress::ScoringHamiltonian, Progress::Done(_)]
+        ));
+        if let Some(Progress::Done(result)) = events.last() {
+            let direct = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+            assert_eq!(result.trust_score, direct.trust_score);
+        }
+
+        // Unsigned chain: stops at the first bad signature
+        let unsigned = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(250, 5)).unwrap();
+        let (tx, rx) = channel();
+        CriticalityEngine::with_defaults().evaluate_streaming(&unsigned, tx);
+        let events: Vec<Progress> = rx.iter().collect();
+        assert!(matches!(events.last(), Some(Progress::Failed(TripError::SignatureInvalid { index: 0 }))));
+    }
+
+    #[test]
+    fn test_config_digest_tracks_every_field() {
+        let base = CriticalityConfig::default();
+        let digest = base.digest().unwrap();
+        assert_eq!(digest, CriticalityConfig::default().digest().unwrap());
+
+        let mutations: Vec<fn(&mut CriticalityConfig)> = vec![
+            |c| c.weights.spatial += 0.01,
+            |c| c.weights.structure += 0.01,
+            |c| c.psd.resampling = psd::Resampling::Linear,
+            |c| c.psd.fit = psd::PsdFit::LogBinned,
+            |c| c.profile.genesis = hamiltonian::GenesisHandling::Exclude,
+            |c| c.profile.robust_dispersion = true,
+            |c| c.profile.novel_transition.anchor_scale_km = Some(5.0),
+            |c| c.profile.degenerate_spread.regularity_energy = 0.5,
+            |c| c.profile.max_transition_sources = 16,
+            |c| c.distance = DistanceMode::Quantized,
+            |c| c.levy_x_min = 0.02,
+            |c| c.alpha_min = 0.31,
+            |c| c.alpha_max = 0.81,
+            |c| c.strong_correlation = StrongCorrelationPolicy::widened(),
+            |c| c.min_psd_r_squared = 0.45,
+            |c| c.beta_min = 0.81,
+            |c| c.beta_max = 1.21,
+            |c| c.min_confidence = 0.6,
+            |c| c.min_energy_variance = 2e-4,
+            |c| c.min_diurnal_strength = 0.1,
+            |c| c.max_directional_persistence = 0.9,
+            |c| c.persistent_heading_confidence_factor = 0.6,
+            |c| c.coarse_resolution_confidence_factor = 0.6,
+            |c| c.waiting_t_min_seconds = 900.0,
+            |c| c.stationary = StationaryPolicy::Analyze,
+            |c| c.path_psd = PathPsdPolicy::cross_check(),
+            |c| c.clusters.eps_km = 0.6,
+            |c| c.clusters.min_points = 6,
+            |c| c.clusters.max_noise_fraction = 0.7,
+            |c| c.manual = ManualBreadcrumbPolicy::Exclude,
+            |c| c.accuracy.max_accuracy_m = 100.0,
+            |c| c.accuracy.handling = PoorAccuracyHandling::Exclude,
+            |c| c.liveness.window_seconds = 1800,
+            |c| c.liveness.max_head_age_seconds = 7200,
+            |c| c.liveness.target_breadcrumbs = 6,
+            |c| c.confidence = ConfidenceModel::Exponential { tau: 300.0 },
+            |c| c.fidelity = Fidelity::Thorough,
+        ];
+        for (i, mutate) in mutations.iter().enumerate() {
+            let mut config = base.clone();
+            mutate(&mut config);
+            assert_ne!(config.digest().unwrap(), digest, "mutation {i}");
+        }
+    }
+
+    #[test]
+    fn test_published_config_matches_certificate() {
+        let engine = CriticalityEngine::new(CriticalityConfig {
+            alpha_max: 0.85,
+            ..Default::default()
+        });
+        let digest = engine.config().digest().unwrap();
+        let published = engine.config().to_published_json().unwrap();
+
+        let restored = CriticalityConfig::from_published_json(&published, &digest).unwrap();
+        assert_eq!(restored.alpha_max, 0.85);
+
+        let other = CriticalityConfig::default().digest().unwrap();
+        assert!(CriticalityConfig::from_published_json(&published, &other).is_err());
+    }
+
     #[test]
     fn test_convergence_confidence() {
-        let c64 = convergence_confidence(64);
-        let c200 = convergence_confidence(200);
-        let c500 = convergence_confidence(500);
+        let model = ConfidenceModel::default();
+        let c64 = model.confidence(64);
+        let c200 = model.confidence(200);
+        let c500 = model.confidence(500);
 
         assert!(c64 > 0.25 && c64 < 0.40, "64 breadcrumbs: {c64}");
         assert!(c200 > 0.60 && c200 < 0.70, "200 breadcrumbs: {c200}");
         assert!(c500 > 0.90, "500 breadcrumbs: {c500}");
     }
+
+    #[test]
+    fn test_poor_spectral_fit_reason() {
+        let intervals = |n: usize| -> Vec<f64> {
+            (0..n).map(|i| 1800.0 + ((i * 37) % 120) as f64).collect()
+        };
+        let pink = ordered_displacements(400, 1, 0.3);
+        let default = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&pink, &intervals(pink.len()))
+            .unwrap();
+        assert!(default.verdict.psd_pass);
+        assert_eq!(default.verdict.psd_failure, None);
+
+        // Same in-band α, but the fit must now explain more variance
+        // than it does
+        let alpha = default.psd.alpha;
+        let r_squared = default.psd.r_squared;
+        assert!((0.30..=0.80).contains(&alpha) && r_squared < 0.8, "α={alpha}, R²={r_squared}");
+        let strict = CriticalityEngine::new(CriticalityConfig {
+            min_psd_r_squared: 0.8,
+            ..Default::default()
+        })
+        .evaluate_displacements(&pink, &intervals(pink.len()))
+        .unwrap();
+        assert_eq!(strict.psd.alpha, alpha);
+        assert!(!strict.verdict.psd_pass);
+        assert!(!strict.is_human);
+        assert_eq!(strict.verdict.psd_failure, Some(FailureReason::PoorSpectralFit));
+        assert!(strict.verdict.summary.contains("FAIL, poor fit"), "{}", strict.verdict.summary);
+
+        // α out of band is reported as such, whatever the fit
+        let flat = ordered_displacements(400, 0, 0.3);
+        let result = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&flat, &intervals(flat.len()))
+            .unwrap();
+        assert!(result.psd.alpha < 0.30, "α={}", result.psd.alpha);
+        assert_eq!(result.verdict.psd_failure, Some(FailureReason::AlphaOutOfRange));
+    }
+
+    #[test]
+    fn test_autocorrelation_lowers_confidence() {
+        use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+        use crate::chain::effective_sample_size;
+
+        // Same displacements, in routine order and shuffled
+        let routine = routine_displacements(400, 1);
+        let mut shuffled = routine.clone();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(1));
+        let n_routine = effective_sample_size(&routine);
+        let n_shuffled = effective_sample_size(&shuffled);
+        assert!(n_routine < 0.2 * routine.len() as f64, "routine N_eff = {n_routine}");
+        assert!(n_shuffled > 0.5 * shuffled.len() as f64, "shuffled N_eff = {n_shuffled}");
+        assert_eq!(effective_sample_size(&[3.0; 50]), 50.0);
+
+        let intervals: Vec<f64> = (0..routine.len())
+            .map(|i| 1800.0 + ((i * 37) % 120) as f64)
+            .collect();
+        let engine = CriticalityEngine::with_defaults();
+        let correlated = engine.evaluate_displacements(&routine, &intervals).unwrap();
+        let independent = engine.evaluate_displacements(&shuffled, &intervals).unwrap();
+        assert!(
+            correlated.confidence < 0.5 * independent.confidence,
+            "{} vs {}", correlated.confidence, independent.confidence
+        );
+        // The sufficiency gate still counts breadcrumbs
+        assert!(correlated.verdict.confidence_sufficient);
+    }
+
+    #[test]
+    fn test_confidence_model_configurable() {
+        let fast = ConfidenceModel::Exponential { tau: 80.0 };
+        for n in [64, 120, 300] {
+            assert!(fast.confidence(n) > ConfidenceModel::default().confidence(n), "n = {n}");
+        }
+        let saturating = ConfidenceModel::Saturating { half_length: 150.0 };
+        assert_eq!(saturating.confidence(150), 0.5);
+
+        // Same chain, same analyses: only the confidence (and the
+        // sufficiency gate) follow the model
+        let displacements = routine_displacements(120, 4);
+        let intervals = vec![900.0; displacements.len()];
+        let default = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&displacements, &intervals).unwrap();
+        let fast = CriticalityEngine::new(CriticalityConfig { confidence: fast, ..Default::default() })
+            .evaluate_displacements(&displacements, &intervals).unwrap();
+        assert!(fast.confidence > default.confidence);
+        assert!(!default.verdict.confidence_sufficient);
+        assert!(fast.verdict.confidence_sufficient);
+        assert_eq!(fast.psd.alpha, default.psd.alpha);
+    }
+
+    #[test]
+    fn test_explain_ranks_injected_anomaly() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        // One breadcrumb teleports to Naples, ~190 km from the walk
+        let mut breadcrumbs = random_walk_breadcrumbs(250, 5);
+        let naples = h3o::LatLng::new(40.8518, 14.2681).unwrap().to_cell(h3o::Resolution::Ten);
+        breadcrumbs[120].location_cell = naples.to_string();
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+
+        let engine = CriticalityEngine::with_defaults();
+        let top = engine.explain(&chain, 10).unwrap();
+        assert_eq!(top.len(), 10);
+        assert!(top.windows(2).all(|w| w[0].contribution.abs() >= w[1].contribution.abs()));
+
+        // The teleport alone drags α towards brown noise: it ranks
+        // first, an order of magnitude ahead of the runner-up, and no
+        // other breadcrumb moves α by more than 0.1
+        let worst = &top[0];
+        assert_eq!(worst.index, 120);
+        assert!(worst.contribution < 0.0);
+        assert!(worst.alpha_shift < -0.5, "alpha shift {}", worst.alpha_shift);
+        assert!(
+            worst.contribution.abs() > 10.0 * top[1].contribution.abs(),
+            "{} vs {}", worst.contribution, top[1].contribution
+        );
+        assert!(top[1..].iter().all(|c| c.alpha_shift.abs() < 0.1));
+        assert_eq!(worst.cell, naples.to_string());
+        assert_eq!(worst.timestamp, chain.breadcrumbs[120].timestamp);
+    }
+
+    #[test]
+    fn test_fidelity_modes() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+        let engine = |fidelity| CriticalityEngine::new(CriticalityConfig { fidelity, ..Default::default() });
+        let fast = engine(Fidelity::Fast).evaluate(&chain).unwrap();
+        let thorough = engine(Fidelity::Thorough).evaluate(&chain).unwrap();
+
+        // Cost is set by the work each mode asks for, not wall clock
+        assert_eq!(Fidelity::Fast.integration_steps(), 200);
+        assert_eq!(Fidelity::Balanced.integration_steps(), levy::DEFAULT_INTEGRATION_STEPS);
+        assert_eq!(Fidelity::Thorough.integration_steps(), 4000);
+        assert_eq!(Fidelity::Fast.bootstrap_iterations(), 0);
+        assert_eq!(Fidelity::Thorough.bootstrap_iterations(), 1000);
+        assert!(!Fidelity::Fast.fits_waiting_times());
+        assert_eq!(fast.levy.integration_steps, 200);
+        assert_eq!(thorough.levy.integration_steps, 4000);
+
+        assert!(fast.intervals.is_none());
+        assert!(fast.path_psd.is_none());
+        assert!(fast.waiting_times.is_none());
+        assert_eq!(fast.levy.beta, thorough.levy.beta);
+
+        let intervals = thorough.intervals.unwrap();
+        assert_eq!(intervals.iterations, 1000);
+        assert!(intervals.alpha.0 <= thorough.psd.alpha && thorough.psd.alpha <= intervals.alpha.1);
+        assert!(intervals.beta.0 <= thorough.levy.beta && thorough.levy.beta <= intervals.beta.1);
+        assert!(intervals.alpha.0 < intervals.alpha.1 && intervals.beta.0 < intervals.beta.1);
+        assert!(thorough.path_psd.is_some());
+
+        // Balanced is the engine as it was: no intervals, same fit
+        let balanced = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        assert!(balanced.intervals.is_none());
+        assert!(balanced.path_psd.is_none());
+    }
+
+    #[test]
+    fn test_manual_breadcrumb_policy() {
+        use crate::chain::tests::{random_walk_breadcrumbs, synthetic_breadcrumbs};
+
+        // A check-in at home, tapped 30 s after every other automatic sample
+        let automatic = random_walk_breadcrumbs(300, 5);
+        let home = automatic[0].location_cell.clone();
+        let mut samples: Vec<(i64, &str, bool)> = Vec::new();
+        for (i, b) in automatic.iter().enumerate() {
+            let t = b.timestamp.timestamp();
+            samples.push((t, b.location_cell.as_str(), false));
+            if i % 2 == 0 {
+                samples.push((t + 30, home.as_str(), true));
+            }
+        }
+        let refs: Vec<(i64, &str)> = samples.iter().map(|&(t, c, _)| (t, c)).collect();
+        let mut breadcrumbs = synthetic_breadcrumbs(&refs);
+        for (b, &(_, _, manual)) in breadcrumbs.iter_mut().zip(&samples) {
+            b.meta_flags.manual = manual;
+        }
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        let automatic = BreadcrumbChain::from_breadcrumbs(automatic).unwrap();
+
+        let engine = |manual| CriticalityEngine::new(CriticalityConfig { manual, ..Default::default() });
+        let baseline = CriticalityEngine::with_defaults().evaluate(&automatic).unwrap();
+        let included = engine(ManualBreadcrumbPolicy::Include).evaluate(&chain).unwrap();
+        let excluded = engine(ManualBreadcrumbPolicy::Exclude).evaluate(&chain).unwrap();
+
+        // Included, the check-ins drag α and β away from the walk's own
+        assert!((included.psd.alpha - baseline.psd.alpha).abs() > 0.3);
+        assert_ne!(included.levy.beta, baseline.levy.beta);
+
+        // Excluded, the mobility analyses see exactly the automatic walk,
+        // while the chain is still reported in full
+        assert_eq!(excluded.psd.alpha, baseline.psd.alpha);
+        assert_eq!(excluded.levy.beta, baseline.levy.beta);
+        assert_eq!(excluded.hamiltonian.mean_energy, baseline.hamiltonian.mean_energy);
+        assert_eq!(excluded.verdict.summary, baseline.verdict.summary);
+        assert_eq!(excluded.chain_length, 450);
+    }
+
+    #[test]
+    fn test_liveness_independent_of_humanity() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+        use crate::clock::FixedClock;
+
+        // 70 breadcrumbs, five minutes apart, the last one two minutes ago
+        let mut breadcrumbs = random_walk_breadcrumbs(70, 5);
+        let start = breadcrumbs[0].timestamp;
+        for (i, b) in breadcrumbs.iter_mut().enumerate() {
+            b.timestamp = start + chrono::Duration::minutes(5 * i as i64);
+        }
+        let fresh = BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap();
+        let now = fresh.breadcrumbs.last().unwrap().timestamp + chrono::Duration::minutes(2);
+        let engine = CriticalityEngine::with_defaults().with_clock(Arc::new(FixedClock::new(now)));
+
+        let mut result = engine.evaluate(&fresh).unwrap();
+        assert!(result.liveness_score() > 0.9, "{:?}", result.liveness);
+        assert!(result.confidence < engine.config().min_confidence);
+        assert_eq!(result.outcome(), Outcome::Inconclusive);
+
+        result.record_active_response(true);
+        assert!(result.liveness_score() > 0.95);
+        result.record_active_response(false);
+        assert_eq!(result.liveness_score(), 0.0);
+
+        // A long chain last seen a week ago: analyzable, not live
+        let old = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(400, 5)).unwrap();
+        let later = old.breadcrumbs.last().unwrap().timestamp + chrono::Duration::days(7);
+        let result = engine.with_clock(Arc::new(FixedClock::new(later))).evaluate(&old).unwrap();
+        assert!(result.verdict.confidence_sufficient);
+        assert_eq!(result.liveness_score(), 0.0);
+        assert_eq!(result.liveness.recent_breadcrumbs, 0);
+
+        // Location-blind evaluation has no timestamps to judge
+        let result = CriticalityEngine::with_defaults()
+            .evaluate_displacements(&old.displacement_series(), &old.interval_series())
+            .unwrap();
+        assert_eq!(result.liveness, LivenessReport::not_measured());
+    }
+
+    #[test]
+    fn test_poor_accuracy_caps_confidence() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let with_accuracy = |accuracy: Option<f64>| {
+            let mut breadcrumbs = random_walk_breadcrumbs(300, 5);
+            for b in &mut breadcrumbs {
+                b.meta_flags.accuracy = accuracy;
+            }
+            BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap()
+        };
+        let engine = CriticalityEngine::with_defaults();
+        let unknown = engine.evaluate(&with_accuracy(None)).unwrap();
+        let precise = engine.evaluate(&with_accuracy(Some(8.0))).unwrap();
+        let coarse = engine.evaluate(&with_accuracy(Some(3000.0))).unwrap();
+
+        // Missing accuracy is reported, but isn't held against the chain
+        assert_eq!(unknown.accuracy, AccuracyReport { poor: 0, unknown: 300, total: 300 });
+        assert_eq!(precise.accuracy.unknown_fraction(), 0.0);
+        assert_eq!(unknown.confidence, precise.confidence);
+        assert_eq!(unknown.trust_score, precise.trust_score);
+        assert!(precise.verdict.confidence_sufficient);
+
+        // Cell-tower fixes: same statistics, but no confidence to act on
+        assert_eq!(coarse.accuracy.poor_fraction(), 1.0);
+        assert_eq!(coarse.psd.alpha, precise.psd.alpha);
+        assert_eq!(coarse.confidence, 0.0);
+        assert!(!coarse.verdict.confidence_sufficient);
+        assert!(coarse.is_inconclusive && !coarse.is_human);
+        assert!(coarse.trust_score < precise.trust_score);
+        assert!(coarse.verdict.summary.contains("100% poor accuracy"));
+
+        // Excluded, nothing is left to analyze
+        let exclude = CriticalityEngine::new(CriticalityConfig {
+            accuracy: AccuracyPolicy { handling: PoorAccuracyHandling::Exclude, ..Default::default() },
+            ..Default::default()
+        });
+        assert!(matches!(
+            exclude.evaluate(&with_accuracy(Some(3000.0))),
+            Err(TripError::InsufficientBreadcrumbs { got: 0, .. })
+        ));
+        assert_eq!(exclude.evaluate(&with_accuracy(Some(8.0))).unwrap().psd.alpha, precise.psd.alpha);
+    }
+
+    #[test]
+    fn test_levy_x_min_in_meters() {
+        let displacements = routine_displacements(300, 2);
+        let intervals = vec![900.0; displacements.len()];
+
+        let km = CriticalityConfig { levy_x_min: 0.01, ..Default::default() };
+        let meters = CriticalityConfig::default().with_levy_x_min_meters(10.0);
+        assert_eq!(meters.levy_x_min, km.levy_x_min);
+        assert_eq!(meters.levy_x_min_meters(), 10.0);
+        assert_eq!(meters.digest().unwrap(), km.digest().unwrap());
+
+        let fit = |config: CriticalityConfig| {
+            CriticalityEngine::new(config).evaluate_displacements(&displacements, &intervals).unwrap().levy
+        };
+        let (by_km, by_meters) = (fit(km), fit(meters));
+        assert_eq!(by_meters.beta, by_km.beta);
+        assert_eq!(by_meters.x_min, by_km.x_min);
+        assert_eq!(by_meters.ks_statistic, by_km.ks_statistic);
+        assert_eq!(by_meters.samples, by_km.samples);
+    }
+
+    #[test]
+    fn test_directional_persistence_flags_waypoint_following() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let chain_of = |points: &[(f64, f64)], rng: &mut StdRng| {
+            let cells: Vec<String> = points.iter().map(|&(lat, lng)| cell(lat, lng)).collect();
+            let mut t = 1_700_000_000i64;
+            let samples: Vec<(i64, &str)> = cells.iter()
+                .map(|c| {
+                    t += rng.gen_range(600..3600);
+                    (t, c.as_str())
+                })
+                .collect();
+            BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap()
+        };
+        let mut rng = StdRng::seed_from_u64(11);
+
+        // A script walking a route north-east, irregular steps, never turning back
+        let mut route = vec![(41.9, 12.5)];
+        for _ in 1..300 {
+            let (lat, lng) = *route.last().unwrap();
+            let step = rng.gen_range(0.002..0.02);
+            route.push((lat + step, lng + step * rng.gen_range(0.8..1.2)));
+        }
+        let scripted = chain_of(&route, &mut rng);
+        assert!(scripted.directional_persistence().unwrap() > 0.95);
+
+        // A person going out and coming back: home, somewhere, home, ...
+        let home = (41.9028, 12.4964);
+        let commute: Vec<(f64, f64)> = (0..300)
+            .map(|i| match i % 2 {
+                0 => home,
+                _ => (home.0 + rng.gen_range(-0.05..0.05), home.1 + rng.gen_range(-0.05..0.05)),
+            })
+            .collect();
+        let human = chain_of(&commute, &mut rng);
+        assert!(human.directional_persistence().unwrap() < 0.2);
+
+        let engine = CriticalityEngine::with_defaults();
+        let result = engine.evaluate(&scripted).unwrap();
+        assert!(result.directional_persistence.unwrap() > 0.95);
+        assert!(!result.verdict.directional_pass);
+        assert!(!result.is_human);
+        assert!(result.verdict.summary.contains("persistent heading"));
+
+        let result = engine.evaluate(&human).unwrap();
+        assert!(result.verdict.directional_pass);
+        assert!(!result.verdict.summary.contains("persistent heading"));
+
+        // Human-like steps, laid out back and forth or all heading north:
+        // the northbound chain (a relocation, a road trip) is flagged and
+        // less confident, but not rejected
+        let displacements: Vec<f64> = ordered_displacements(400, 1, 0.25).iter().map(|d| 10.0 * d).collect();
+        let chain_along = |alternate: bool| {
+            let mut rng = StdRng::seed_from_u64(1);
+            let (mut lat, lng) = (41.9028f64, 12.4964f64);
+            let mut t = 1_700_035_200i64;
+            let mut samples = vec![(t, lat, lng)];
+            for (i, d) in displacements.iter().enumerate() {
+                let sign = if alternate && i % 2 == 1 { -1.0 } else { 1.0 };
+                lat += sign * d / 111.32;
+                t += rng.gen_range(1200..3600);
+                if (t / 3600) % 24 >= 22 {
+                    t += 10 * 3600;
+                }
+                samples.push((t, lat, lng));
+            }
+            let cells: Vec<String> = samples.iter()
+                .map(|&(_, lat, lng)| {
+                    h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Twelve).to_string()
+                })
+                .collect();
+            let refs: Vec<(i64, &str)> = samples.iter().zip(&cells).map(|(s, c)| (s.0, c.as_str())).collect();
+            let mut breadcrumbs = synthetic_breadcrumbs(&refs);
+            for b in &mut breadcrumbs {
+                b.location_resolution = 12;
+            }
+            BreadcrumbChain::from_breadcrumbs(breadcrumbs).unwrap()
+        };
+        let config = CriticalityConfig { levy_x_min: 0.1, ..Default::default() };
+        let evaluate = |chain: &BreadcrumbChain, config: &CriticalityConfig| {
+            CriticalityEngine::new(config.clone()).evaluate(chain).unwrap()
+        };
+
+        let back_and_forth = evaluate(&chain_along(true), &config);
+        assert!(back_and_forth.verdict.directional_pass);
+        assert!(back_and_forth.is_human);
+
+        let northbound = chain_along(false);
+        let trip = evaluate(&northbound, &config);
+        assert!(!trip.verdict.directional_pass);
+        assert!(trip.verdict.summary.contains("persistent heading"));
+        assert!(trip.is_human);
+        let unpenalized = evaluate(&northbound, &CriticalityConfig {
+            persistent_heading_confidence_factor: 1.0,
+            ..config.clone()
+        });
+        assert!((trip.confidence - 0.5 * unpenalized.confidence).abs() < 1e-12);
+
+        // Too few moves to say anything
+        assert_eq!(crate::chain::directional_persistence(&[0.5; 5]), None);
+    }
+
+    #[test]
+    fn test_verdict_for_config_matches_full_evaluate() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+        let engine = CriticalityEngine::with_defaults();
+        let raw = engine.analyze(&chain).unwrap();
+
+        let sweep = [
+            CriticalityConfig::default(),
+            CriticalityConfig { alpha_min: 0.1, alpha_max: 1.5, beta_min: 0.5, beta_max: 3.0, ..Default::default() },
+            CriticalityConfig { min_confidence: 0.99, ..Default::default() },
+            CriticalityConfig { strong_correlation: StrongCorrelationPolicy::widened(), ..Default::default() },
+            CriticalityConfig {
+                clusters: ClusterPolicy { max_noise_fraction: 0.0, ..Default::default() },
+                min_energy_variance: 0.0,
+                ..Default::default()
+            },
+        ];
+        for config in &sweep {
+            let swept = engine.verdict_for_config(&raw, config).unwrap();
+            let fresh = CriticalityEngine::new(config.clone()).evaluate(&chain).unwrap();
+            assert_eq!(swept.outcome(), fresh.outcome());
+            assert_eq!(swept.trust_score, fresh.trust_score);
+            assert_eq!(swept.confidence, fresh.confidence);
+            assert_eq!(swept.verdict.summary, fresh.verdict.summary);
+            assert_eq!(swept.psd.alpha, fresh.psd.alpha);
+        }
+        // The sweep actually moves the verdict
+        assert_ne!(
+            engine.verdict_for_config(&raw, &sweep[0]).unwrap().verdict.summary,
+            engine.verdict_for_config(&raw, &sweep[2]).unwrap().verdict.summary
+        );
+
+        // A config that changes the analyses themselves needs a new run
+        let refit = CriticalityConfig::default().with_levy_x_min_meters(50.0);
+        assert!(matches!(engine.verdict_for_config(&raw, &refit), Err(TripError::ConfigMismatch(_))));
+    }
+
+    #[test]
+    fn test_engine_digest_covers_scorer_and_calibration() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let table = CalibrationTable::new(
+            vec![0.0, 100.0],
+            vec![100, 400],
+            vec![vec![0.4, 0.02], vec![0.2, 0.01]],
+        ).unwrap();
+        let plain = CriticalityEngine::with_defaults();
+        assert_eq!(plain.digest().unwrap(), CriticalityConfig::default().digest().unwrap());
+
+        let engines = [
+            plain,
+            CriticalityEngine::with_defaults().with_scorer(Box::new(ConstantScorer(0.9))),
+            CriticalityEngine::with_defaults().with_scorer(Box::new(crate::hamiltonian::HamiltonianScorer::default())),
+            CriticalityEngine::with_defaults().with_calibration(table.clone()),
+            CriticalityEngine::with_defaults().with_scorer(Box::new(ConstantScorer(0.9))).with_calibration(table),
+        ];
+        let digests: std::collections::HashSet<String> = engines.iter().map(|e| e.digest().unwrap()).collect();
+        assert_eq!(digests.len(), engines.len());
+
+        // Analyses from one scorer are not judged by an engine with another
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 5)).unwrap();
+        let raw = engines[1].analyze(&chain).unwrap();
+        assert!(engines[1].verdict_for_config(&raw, &CriticalityConfig::default()).is_ok());
+        assert!(matches!(
+            engines[0].verdict_for_config(&raw, &CriticalityConfig::default()),
+            Err(TripError::ConfigMismatch(_))
+        ));
+    }
 }
@@ -0,0 +1,126 @@
+// trip-verifier/src/diurnal.rs
+//
+// Diurnal Periodicity
+// ====================
+//
+// People sleep. Their activity has a strong 24-hour component, while
+// a bot sampling around the clock has none. This is hard to fake
+// without also giving up half of every day's evidence.
+//
+// Implementation:
+// 1. Bin breadcrumb timestamps into hourly counts (the activity series)
+// 2. Trim to whole days, so the 24 h period falls exactly on bin k = days
+// 3. Remove the mean and take the periodogram |FFT|²
+// 4. diurnal_strength = power at 24 h / total non-DC power
+//
+// A uniformly active series spreads its power over all N/2 bins
+// (strength ≈ 2/N); a day/night cycle concentrates it at k = days.
+// Perfectly flat activity, a bot on a fixed timer, has no power at
+// all and strength 0.
+
+use chrono::{DateTime, Utc};
+use rustfft::{FftPlanner, num_complex::Complex};
+use crate::error::{TripError, Result};
+
+/// Shortest span over which a 24 h period is measured.
+pub const MIN_DIURNAL_DAYS: usize = 2;
+
+/// Result of diurnal periodicity analysis.
+#[derive(Debug, Clone)]
+pub struct DiurnalResult {
+    /// Share of activity-series power at the 24 h period [0, 1]
+    pub strength: f64,
+    /// Whole days analyzed
+    pub days: usize,
+}
+
+/// Measure the 24 h periodicity of breadcrumb activity.
+///
+/// Fails with `PsdError` if the timestamps span fewer than
+/// `MIN_DIURNAL_DAYS` whole days. Perfectly flat activity has
+/// strength 0.
+pub fn diurnal_strength(timestamps: &[DateTime<Utc>]) -> Result<DiurnalResult> {
+    let (Some(first), Some(last)) = (timestamps.iter().min(), timestamps.iter().max()) else {
+        return Err(TripError::PsdError("No timestamps for diurnal analysis".to_string()));
+    };
+
+    let days = ((*last - *first).num_seconds() / 86_400) as usize;
+    if days < MIN_DIURNAL_DAYS {
+        return Err(TripError::PsdError(
+            format!("Need at least {MIN_DIURNAL_DAYS} days for diurnal analysis, got {days}")
+        ));
+    }
+
+    // --- Step 1: Hourly activity counts over whole days ---
+    let n = days * 24;
+    let mut counts = vec![0.0f64; n];
+    for t in timestamps {
+        let hour = ((*t - *first).num_seconds() / 3600) as usize;
+        if hour < n {
+            counts[hour] += 1.0;
+        }
+    }
+
+    // --- Step 2: Periodogram of the centered series ---
+    let mean = counts.iter().sum::<f64>() / n as f64;
+    let mut buffer: Vec<Complex<f64>> = counts.iter()
+        .map(|&c| Complex::new(c - mean, 0.0))
+        .collect();
+    FftPlanner::<f64>::new().plan_fft_forward(n).process(&mut buffer);
+
+    let power: Vec<f64> = buffer[1..=n / 2].iter().map(|c| c.norm_sqr()).collect();
+    let total: f64 = power.iter().sum();
+    if total <= 0.0 {
+        return Ok(DiurnalResult { strength: 0.0, days });
+    }
+
+    // Bin k has period n/k hours; k = days is exactly 24 h
+    Ok(DiurnalResult {
+        strength: power[days - 1] / total,
+        days,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn every_minutes(days: i64, step: i64, keep: impl Fn(i64) -> bool) -> Vec<DateTime<Utc>> {
+        let start = Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap();
+        (0..days * 24 * 60 / step)
+            .map(|i| i * step)
+            .filter(|&m| keep((m / 60) % 24))
+            .map(|m| start + chrono::Duration::minutes(m))
+            .collect()
+    }
+
+    #[test]
+    fn test_diurnal_pattern_high() {
+        // Active 08:00-22:00, silent at night
+        let timestamps = every_minutes(7, 20, |hour| (8..22).contains(&hour));
+        let result = diurnal_strength(&timestamps).unwrap();
+        assert_eq!(result.days, 6);
+        assert!(result.strength > 0.5, "strength = {}", result.strength);
+    }
+
+    #[test]
+    fn test_uniform_activity_low() {
+        let timestamps = every_minutes(7, 20, |_| true);
+        let flat = diurnal_strength(&timestamps).unwrap(); // perfectly flat
+        assert_eq!(flat.strength, 0.0);
+        assert_eq!(flat.days, 6);
+
+        // Irregular round-the-clock sampling
+        let chain = crate::chain::tests::random_walk_breadcrumbs(300, 3);
+        let timestamps: Vec<_> = chain.iter().map(|b| b.timestamp).collect();
+        let result = diurnal_strength(&timestamps).unwrap();
+        assert!(result.strength < 0.1, "strength = {}", result.strength);
+    }
+
+    #[test]
+    fn test_too_short_for_diurnal() {
+        let timestamps = every_minutes(1, 20, |_| true);
+        assert!(matches!(diurnal_strength(&timestamps), Err(TripError::PsdError(_))));
+    }
+}
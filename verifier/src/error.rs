@@ -19,6 +19,12 @@ pub enum TripError {
     #[error("Lévy fit failed: {0}")]
     LevyFitError(String),
 
+    #[error("Waiting-time fit failed: {0}")]
+    WaitingTimeError(String),
+
+    #[error("Non-finite {series}: {count} NaN/Inf value(s), first at position {first}")]
+    NonFinite { series: &'static str, count: usize, first: usize },
+
     #[error("Invalid H3 cell: {0}")]
     InvalidH3Cell(String),
 
@@ -28,11 +34,36 @@ pub enum TripError {
     #[error("Verification deadline expired")]
     DeadlineExpired,
 
+    #[error("Verification request not authorized: {0}")]
+    UnauthorizedRequest(String),
+
+    #[error("Empty hex input for {0}")]
+    EmptyHex(String),
+
+    #[error("Invalid hex input: {0}")]
+    InvalidHex(String),
+
+    #[error("Invalid breadcrumb: {0}")]
+    InvalidBreadcrumb(String),
+
     #[error("Certificate encoding error: {0}")]
     CertificateError(String),
 
     #[error("Deserialization error: {0}")]
     DeserializeError(String),
+
+    #[error("Config mismatch: {0}")]
+    ConfigMismatch(String),
 }
 
 pub type Result<T> = std::result::Result<T, TripError>;
+
+/// Reject a series holding NaN or ±Inf before it reaches a mean or
+/// regression, where it would turn every result into NaN silently.
+pub fn ensure_finite(series: &'static str, values: &[f64]) -> Result<()> {
+    let mut bad = values.iter().enumerate().filter(|(_, v)| !v.is_finite());
+    match bad.next() {
+        Some((first, _)) => Err(TripError::NonFinite { series, count: 1 + bad.count(), first }),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,371 @@
+// trip-verifier/src/facet.rs
+//
+// Facet Linking
+// ==============
+//
+// A user can derive facet identities (work, home, ...) from their
+// root key, and each facet builds its own breadcrumb chain under its
+// own key. Key derivation is one-way, so a verifier can't tell on its
+// own that two keys belong to one person.
+//
+// To link a facet, the parent key signs a binding:
+//
+//   "trip-facet-binding:v1:" || facet_name || ":" || facet_key_hex
+//
+// A verifier holding valid bindings can treat the parent's and the
+// facets' certificates as one person and aggregate trust across them.
+// Linking is opt-in: unlinked facets stay unlinkable. Aggregation
+// checks each certificate's Verifier signature, expiry and Verifier
+// key revocation (see `RevocationList`), and counts only the latest
+// certificate of each identity key, so neither resubmitting one nor
+// having a chain certified again can inflate its weight.
+//
+// Linked chains can also be analyzed together. Their breadcrumbs are
+// merged in a total order (timestamp, then identity key, then index)
+// so equal timestamps across facets never make the combined series
+// depend on input order. A step from one facet's breadcrumb to
+// another's is not a movement anyone made, so only displacements
+// within each chain enter the merged series.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use crate::breadcrumb::{Breadcrumb, Displacement};
+use crate::certificate::{PoHCertificate, decode_hex_field};
+use crate::chain::BreadcrumbChain;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{TripError, Result};
+use crate::revocation::RevocationList;
+
+/// Parent-signed statement that a facet key belongs to the parent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetBinding {
+    pub parent_key: String,     // hex, 64 chars
+    pub facet_key: String,      // hex, 64 chars
+    pub facet_name: String,
+    pub signature: String,      // Ed25519 hex, by the parent key
+}
+
+impl FacetBinding {
+    /// Sign a binding for `facet_key` with the parent's key.
+    pub fn sign(parent: &SigningKey, facet_name: &str, facet_key: &VerifyingKey) -> Self {
+        let mut binding = Self {
+            parent_key: hex::encode(parent.verifying_key().to_bytes()),
+            facet_key: hex::encode(facet_key.to_bytes()),
+            facet_name: facet_name.to_string(),
+            signature: String::new(),
+        };
+        binding.signature = hex::encode(parent.sign(binding.data_to_sign().as_bytes()).to_bytes());
+        binding
+    }
+
+    /// Canonical bytes signed by the parent.
+    pub fn data_to_sign(&self) -> String {
+        format!("trip-facet-binding:v1:{}:{}", self.facet_name, self.facet_key)
+    }
+
+    /// Check the parent's signature over this binding.
+    pub fn verify(&self) -> Result<()> {
+        let invalid = || TripError::CertificateError(
+            format!("Invalid facet binding for '{}'", self.facet_name)
+        );
+
+        let key_bytes: [u8; 32] = decode_hex_field(&self.parent_key, "parent_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(&self.signature, "signature")?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| invalid())?;
+        key.verify(self.data_to_sign().as_bytes(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| invalid())
+    }
+}
+
+/// Trust aggregated over a parent identity and its linked facets.
+#[derive(Debug, Clone)]
+pub struct AggregateTrust {
+    pub parent_key: String,
+    /// Chain-length-weighted mean trust score [0, 100]
+    pub trust_score: f64,
+    /// Breadcrumbs across all linked chains
+    pub total_chain_length: u64,
+    /// Number of certificates aggregated
+    pub chains: usize,
+}
+
+/// Aggregate certificates of a parent and its facets into one trust
+/// estimate. Each certificate must verify against `revocations`
+/// (`PoHCertificate::verify_with_revocations`) and be issued to the
+/// parent itself or to a facet with a valid binding from that parent.
+/// Only the latest certificate per identity key counts: a chain
+/// re-certified many times still weighs in once.
+pub fn aggregate_facet_trust(
+    parent_key: &str,
+    bindings: &[FacetBinding],
+    certificates: &[PoHCertificate],
+    revocations: &RevocationList,
+) -> Result<AggregateTrust> {
+    aggregate_facet_trust_with_clock(parent_key, bindings, certificates, revocations, &SystemClock)
+}
+
+/// Like `aggregate_facet_trust`, checking validity at `clock`'s time.
+pub fn aggregate_facet_trust_with_clock(
+    parent_key: &str,
+    bindings: &[FacetBinding],
+    certificates: &[PoHCertificate],
+    revocations: &RevocationList,
+    clock: &dyn Clock,
+) -> Result<AggregateTrust> {
+    if certificates.is_empty() {
+        return Err(TripError::CertificateError("No certificates to aggregate".to_string()));
+    }
+
+    // Latest certificate per identity key, in order of first appearance
+    let mut latest: Vec<&PoHCertificate> = Vec::with_capacity(certificates.len());
+    let mut position: HashMap<String, usize> = HashMap::new();
+
+    for cert in certificates {
+        cert.verify_with_revocations_and_clock(revocations, clock)?;
+
+        if !cert.identity_key.eq_ignore_ascii_case(parent_key) {
+            let binding = bindings.iter()
+                .find(|b| {
+                    b.facet_key.eq_ignore_ascii_case(&cert.identity_key)
+                        && b.parent_key.eq_ignore_ascii_case(parent_key)
+                })
+                .ok_or_else(|| TripError::CertificateError(
+                    format!("No facet binding for {}", cert.identity_key.get(..8).unwrap_or(&cert.identity_key))
+                ))?;
+            binding.verify()?;
+        }
+
+        match position.entry(cert.identity_key.to_ascii_lowercase()) {
+            Entry::Occupied(slot) => {
+                let kept = &mut latest[*slot.get()];
+                if cert.issued_at > kept.issued_at {
+                    *kept = cert;
+                }
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(latest.len());
+                latest.push(cert);
+            }
+        }
+    }
+
+    let weighted: f64 = latest.iter().map(|c| c.trust_score * c.chain_length as f64).sum();
+    let total_chain_length: u64 = latest.iter().map(|c| c.chain_length).sum();
+    let trust_score = if total_chain_length > 0 {
+        weighted / total_chain_length as f64
+    } else {
+        latest.iter().map(|c| c.trust_score).sum::<f64>() / latest.len() as f64
+    };
+
+    Ok(AggregateTrust {
+        parent_key: parent_key.to_string(),
+        trust_score,
+        total_chain_length,
+        chains: latest.len(),
+    })
+}
+
+/// Total order for breadcrumbs of several chains:
+/// timestamp, then identity key, then index.
+pub fn merge_order(a: &Breadcrumb, b: &Breadcrumb) -> Ordering {
+    a.timestamp.cmp(&b.timestamp)
+        .then_with(|| a.identity_public_key.cmp(&b.identity_public_key))
+        .then_with(|| a.index.cmp(&b.index))
+}
+
+/// Displacement and interval series of several chains, merged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergedSeries {
+    pub displacement_km: Vec<f64>,
+    pub interval_seconds: Vec<f64>,
+}
+
+/// Merge the within-chain displacements of several (facet) chains
+/// into one series, ordered by `merge_order` of each step's end
+/// breadcrumb. Cross-chain displacements are excluded, and the
+/// result does not depend on the order of `chains`.
+pub fn merge_chains_for_analysis(chains: &[BreadcrumbChain]) -> MergedSeries {
+    let mut steps: Vec<(&Breadcrumb, &Displacement)> = chains.iter()
+        .flat_map(|c| c.breadcrumbs.iter().skip(1).zip(&c.displacements))
+        .collect();
+    steps.sort_by(|a, b| merge_order(a.0, b.0));
+
+    MergedSeries {
+        displacement_km: steps.iter().map(|(_, d)| d.distance_km).collect(),
+        interval_seconds: steps.iter().map(|(_, d)| d.dt_seconds).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certificate::tests::test_cert;
+    use crate::clock::FixedClock;
+
+    /// Verifier key the test certificates are signed with
+    fn verifier() -> SigningKey {
+        SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    fn cert_for(key: &VerifyingKey, trust_score: f64, chain_length: u64) -> PoHCertificate {
+        unsigned_cert_for(key, trust_score, chain_length).sign(&verifier()).unwrap()
+    }
+
+    fn unsigned_cert_for(key: &VerifyingKey, trust_score: f64, chain_length: u64) -> PoHCertificate {
+        PoHCertificate {
+            identity_key: hex::encode(key.to_bytes()),
+            chain_length,
+            verifier_key: hex::encode(verifier().verifying_key().to_bytes()),
+            chain_head_hash: None,
+            ..test_cert(trust_score)
+        }
+    }
+
+    #[test]
+    fn test_facet_binding_verifies_and_aggregates() {
+        let parent = SigningKey::from_bytes(&[1u8; 32]);
+        let work = SigningKey::from_bytes(&[2u8; 32]);
+
+        let binding = FacetBinding::sign(&parent, "work", &work.verifying_key());
+        binding.verify().unwrap();
+
+        let parent_hex = hex::encode(parent.verifying_key().to_bytes());
+        let certs = [
+            cert_for(&parent.verifying_key(), 80.0, 300),
+            cert_for(&work.verifying_key(), 60.0, 100),
+        ];
+        let bindings = std::slice::from_ref(&binding);
+        let aggregate = aggregate_facet_trust(&parent_hex, bindings, &certs, &RevocationList::new()).unwrap();
+        assert_eq!(aggregate.chains, 2);
+        assert_eq!(aggregate.total_chain_length, 400);
+        assert!((aggregate.trust_score - 75.0).abs() < 1e-9);
+
+        // Hex keys compare case-insensitively
+        let upper = aggregate_facet_trust(&parent_hex.to_uppercase(), bindings, &certs, &RevocationList::new()).unwrap();
+        assert_eq!(upper.chains, 2);
+        assert_eq!(upper.trust_score, aggregate.trust_score);
+        let mut shouting = binding.clone();
+        shouting.parent_key = shouting.parent_key.to_uppercase();
+        assert!(aggregate_facet_trust(&parent_hex, &[shouting], &certs, &RevocationList::new()).is_ok());
+    }
+
+    #[test]
+    fn test_merge_order_stable() {
+        use crate::chain::tests::{random_walk_breadcrumbs, sign_breadcrumbs};
+
+        let home = SigningKey::from_bytes(&[1u8; 32]);
+        let work = SigningKey::from_bytes(&[2u8; 32]);
+
+        // Two facets sampled at exactly the same instants
+        let mut a = random_walk_breadcrumbs(60, 5);
+        let mut b = random_walk_breadcrumbs(60, 6);
+        for (x, y) in b.iter_mut().zip(&a) {
+            x.timestamp = y.timestamp;
+        }
+        sign_breadcrumbs(&mut a, &home);
+        sign_breadcrumbs(&mut b, &work);
+
+        let chain = |v: &Vec<Breadcrumb>| BreadcrumbChain::from_breadcrumbs(v.clone()).unwrap();
+        let merged = merge_chains_for_analysis(&[chain(&a), chain(&b)]);
+        assert_eq!(merged.displacement_km.len(), 118);
+        assert_eq!(merged, merge_chains_for_analysis(&[chain(&b), chain(&a)]));
+        assert_eq!(merged, merge_chains_for_analysis(&[chain(&a), chain(&b)]));
+
+        // Ties go to the lower identity key; only within-chain steps
+        let (first, second) = if a[1].identity_public_key < b[1].identity_public_key {
+            (chain(&a), chain(&b))
+        } else {
+            (chain(&b), chain(&a))
+        };
+        assert_eq!(merge_order(&first.breadcrumbs[1], &second.breadcrumbs[1]), Ordering::Less);
+        assert_eq!(merged.displacement_km[0], first.displacements[0].distance_km);
+        assert_eq!(merged.displacement_km[1], second.displacements[0].distance_km);
+        assert_eq!(merged.interval_seconds[1], second.displacements[0].dt_seconds);
+    }
+
+    #[test]
+    fn test_forged_facet_link_rejected() {
+        let parent = SigningKey::from_bytes(&[1u8; 32]);
+        let stranger = SigningKey::from_bytes(&[3u8; 32]);
+        let parent_hex = hex::encode(parent.verifying_key().to_bytes());
+
+        // Stranger signs a binding but claims the parent as signer
+        let mut forged = FacetBinding::sign(&stranger, "work", &stranger.verifying_key());
+        forged.parent_key = parent_hex.clone();
+        assert!(forged.verify().is_err());
+
+        let certs = [cert_for(&stranger.verifying_key(), 90.0, 500)];
+        assert!(aggregate_facet_trust(&parent_hex, &[forged], &certs, &RevocationList::new()).is_err());
+
+        // No binding at all
+        assert!(aggregate_facet_trust(&parent_hex, &[], &certs, &RevocationList::new()).is_err());
+
+        // A genuine binding can't be retargeted to another facet key
+        let mut retargeted = FacetBinding::sign(&parent, "work", &parent.verifying_key());
+        retargeted.facet_key = hex::encode(stranger.verifying_key().to_bytes());
+        assert!(retargeted.verify().is_err());
+    }
+
+    #[test]
+    fn test_aggregation_checks_and_dedupes_certificates() {
+        let parent = SigningKey::from_bytes(&[1u8; 32]);
+        let work = SigningKey::from_bytes(&[2u8; 32]);
+        let binding = FacetBinding::sign(&parent, "work", &work.verifying_key());
+        let parent_hex = hex::encode(parent.verifying_key().to_bytes());
+        let none = RevocationList::new();
+
+        // The same high-scoring facet certificate submitted three times
+        let parent_cert = cert_for(&parent.verifying_key(), 50.0, 100);
+        let work_cert = cert_for(&work.verifying_key(), 90.0, 100);
+        let certs = [parent_cert.clone(), work_cert.clone(), work_cert.clone(), work_cert];
+        let aggregate = aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &certs, &none).unwrap();
+        assert_eq!(aggregate.chains, 2);
+        assert_eq!(aggregate.total_chain_length, 200);
+        assert!((aggregate.trust_score - 70.0).abs() < 1e-9);
+
+        // Re-certifying the facet replaces its earlier certificate
+        let mut earlier = unsigned_cert_for(&work.verifying_key(), 90.0, 100);
+        earlier.issued_at -= chrono::Duration::minutes(5);
+        let earlier = earlier.sign(&verifier()).unwrap();
+        let reissued = cert_for(&work.verifying_key(), 80.0, 100);
+        for certs in [
+            [parent_cert.clone(), earlier.clone(), reissued.clone()],
+            [reissued.clone(), parent_cert.clone(), earlier.clone()],
+        ] {
+            let aggregate = aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &certs, &none).unwrap();
+            assert_eq!(aggregate.chains, 2);
+            assert_eq!(aggregate.total_chain_length, 200);
+            assert!((aggregate.trust_score - 65.0).abs() < 1e-9);
+        }
+
+        // Unsigned, tampered and expired certificates are rejected
+        let unsigned = unsigned_cert_for(&work.verifying_key(), 90.0, 100);
+        assert!(aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &[unsigned], &none).is_err());
+
+        let mut tampered = parent_cert.clone();
+        tampered.trust_score = 99.0;
+        assert!(aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &[tampered], &none).is_err());
+
+        let mut stale = unsigned_cert_for(&parent.verifying_key(), 80.0, 100);
+        stale.issued_at = chrono::Utc::now() - chrono::Duration::days(2);
+        let stale = stale.sign(&verifier()).unwrap();
+        assert!(aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &[stale], &none).is_err());
+
+        // Certificates issued once their Verifier key was revoked are
+        // rejected; earlier ones count until they expire
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&parent_cert.verifier_key, parent_cert.issued_at);
+        assert!(aggregate_facet_trust(&parent_hex, std::slice::from_ref(&binding), &certs, &revocations).is_err());
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&parent_cert.verifier_key, parent_cert.issued_at + chrono::Duration::minutes(10));
+        let at = |minutes| FixedClock::new(parent_cert.issued_at + chrono::Duration::minutes(minutes));
+        let binding = std::slice::from_ref(&binding);
+        let aggregate = aggregate_facet_trust_with_clock(&parent_hex, binding, &certs, &revocations, &at(30)).unwrap();
+        assert_eq!(aggregate.chains, 2);
+        assert!(aggregate_facet_trust_with_clock(&parent_hex, binding, &certs, &revocations, &at(120)).is_err());
+    }
+}
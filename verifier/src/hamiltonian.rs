@@ -17,12 +17,18 @@
 //   spatial=0.25, temporal=0.20, kinetic=0.15,
 //   flock=0.15, contextual=0.15, structure=0.10
 
-use crate::breadcrumb::Breadcrumb;
+use crate::breadcrumb::{Breadcrumb, h3_cell_distance_km};
 use crate::chain::BreadcrumbChain;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+/// Default cap on the source cells kept in a profile's transition
+/// matrix (see `ProfileConfig::max_transition_sources`).
+pub const DEFAULT_MAX_TRANSITION_SOURCES: usize = 512;
 
 /// Component weights for the Hamiltonian.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HamiltonianWeights {
     pub spatial: f64,
     pub temporal: f64,
@@ -46,7 +52,7 @@ impl Default for HamiltonianWeights {
 }
 
 /// Result of Hamiltonian evaluation for a single breadcrumb.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HamiltonianScore {
     pub index: u64,
     pub h_spatial: f64,
@@ -60,15 +66,23 @@ pub struct HamiltonianScore {
 }
 
 /// Result of Hamiltonian evaluation for the entire chain.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ChainHamiltonianResult {
     pub scores: Vec<HamiltonianScore>,
     pub mean_energy: f64,
     pub max_energy: f64,
+    /// Population variance of per-breadcrumb energy. Real trajectories
+    /// always have some surprising moments; a near-zero value means the
+    /// chain is uniformly "normal" against its own profile.
+    pub energy_variance: f64,
     pub alert_count: AlertCounts,
+    /// The profile's displacement or interval spread is degenerate
+    /// (see `DegenerateSpread`): the chain repeats one step length or
+    /// one cadence exactly, which no person does.
+    pub degenerate_spread: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct AlertCounts {
     pub green: usize,
     pub yellow: usize,
@@ -77,7 +91,7 @@ pub struct AlertCounts {
 }
 
 /// Alert levels per TRIP spec Table 7.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum AlertLevel {
     /// H < 0.3 — Normal behavior
     Green,
@@ -100,8 +114,188 @@ impl AlertLevel {
     }
 }
 
+/// Whether the genesis breadcrumb contributes to profile statistics.
+///
+/// The genesis has no predecessor, so it has no displacement or
+/// interval (those statistics are pairwise and never include it),
+/// and its spatial/kinetic/contextual/structure energies are 0.0 by
+/// construction. With `Include`, it still counts towards the cell
+/// histogram, anchors and hourly profile, and its partial score
+/// pulls the chain's mean energy down — noticeably so for short
+/// chains. `Exclude` drops it from all of those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GenesisHandling {
+    /// Genesis counts like any other breadcrumb (original behavior)
+    #[default]
+    Include,
+    /// Genesis is left out of the profile and chain energy
+    Exclude,
+}
+
+/// H_kinetic energy for a transition the profile has never seen.
+///
+/// A flat penalty treats a walk to the shop next door the same as a
+/// jump to another continent. With `anchor_scale_km` set, the penalty
+/// grows with the distance d from the new cell to the nearest anchor:
+///   base · d / (d + anchor_scale_km)
+/// so it is half of `base` at `anchor_scale_km` and approaches `base`
+/// far from every anchor. Without anchors the flat `base` applies.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NovelTransitionPenalty {
+    pub base: f64,
+    pub anchor_scale_km: Option<f64>,
+}
+
+impl Default for NovelTransitionPenalty {
+    fn default() -> Self {
+        Self {
+            base: 0.7, // suspicious but might be exploring new area
+            anchor_scale_km: None,
+        }
+    }
+}
+
+impl NovelTransitionPenalty {
+    /// Energy for a never-seen transition into `cell`.
+    pub fn energy(&self, cell: &str, anchor_cells: &[String]) -> f64 {
+        let scale = match self.anchor_scale_km {
+            Some(scale) if scale > 0.0 => scale,
+            _ => return self.base,
+        };
+
+        let nearest = anchor_cells.iter()
+            .map(|anchor| h3_cell_distance_km(cell, anchor))
+            .fold(f64::INFINITY, f64::min);
+        if !nearest.is_finite() {
+            return self.base;
+        }
+
+        self.base * nearest / (nearest + scale)
+    }
+}
+
+/// Scoring against a profile whose displacement or interval spread
+/// is (near) zero.
+///
+/// A z-score is undefined for a zero spread, and treating it as
+/// neutral would give a perfectly regular chain zero spatial and
+/// structural energy. Real people never repeat the same step at the
+/// same cadence every time, so a degenerate spread is itself a
+/// regularity flag: samples matching the constant profile score
+/// `regularity_energy`, and a sample deviating from it by more than
+/// `tolerance` is maximally anomalous (1.0).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DegenerateSpread {
+    /// Spread below which the profile is degenerate (km or seconds)
+    pub tolerance: f64,
+    /// Energy for samples matching a degenerate profile
+    pub regularity_energy: f64,
+}
+
+impl Default for DegenerateSpread {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.001,
+            regularity_energy: 1.0,
+        }
+    }
+}
+
+impl DegenerateSpread {
+    /// Energy for `value` against a (mean, spread) pair, or None if
+    /// the spread is large enough for a z-score.
+    fn energy(&self, value: f64, mean: f64, spread: f64) -> Option<f64> {
+        if spread >= self.tolerance {
+            return None;
+        }
+        if (value - mean).abs() > self.tolerance {
+            Some(1.0)
+        } else {
+            Some(self.regularity_energy)
+        }
+    }
+}
+
+/// Options for building a BehavioralProfile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    pub genesis: GenesisHandling,
+    /// H_kinetic energy for never-seen transitions
+    pub novel_transition: NovelTransitionPenalty,
+    /// Use median absolute deviation instead of the sample standard
+    /// deviation for displacement and interval spread. A single
+    /// teleport inflates the classic std-dev and makes every other
+    /// spatial z-score too forgiving; MAD is barely affected. MAD is
+    /// zero once more than half the values agree (a fixed sampling
+    /// timer, a day at home), so a MAD below the degenerate-spread
+    /// tolerance falls back to the std-dev.
+    pub robust_dispersion: bool,
+    /// H_spatial / H_structure energy when the spread is degenerate
+    pub degenerate_spread: DegenerateSpread,
+    /// Keep transition rows for at most this many of the most
+    /// frequent source cells; the rest share one aggregate
+    /// probability (see `OtherTransitions`)
+    pub max_transition_sources: usize,
+}
+
+impl Default for ProfileConfig {
+    fn default() -> Self {
+        Self {
+            genesis: GenesisHandling::default(),
+            novel_transition: NovelTransitionPenalty::default(),
+            robust_dispersion: false,
+            degenerate_spread: DegenerateSpread::default(),
+            max_transition_sources: DEFAULT_MAX_TRANSITION_SOURCES,
+        }
+    }
+}
+
+/// Transitions out of the source cells dropped from a capped
+/// transition matrix, pooled into one "other" bucket.
+///
+/// This is an approximation: every observed transition out of a
+/// dropped source scores the same. Dropped sources are the least
+/// visited cells, whose rows hold one or two samples each and say
+/// little about the user anyway. Which transitions were observed is
+/// kept as 64-bit fingerprints, so a never-seen one still takes the
+/// novel-transition energy instead of the pooled probability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtherTransitions {
+    /// Source cells whose rows are kept in the matrix
+    pub kept_sources: HashSet<String>,
+    /// Count-weighted mean of the dropped rows' transition
+    /// probabilities
+    pub probability: f64,
+    /// `transition_fingerprint` of every pooled transition
+    #[serde(default)]
+    pub observed: HashSet<u64>,
+}
+
+impl OtherTransitions {
+    /// Was the transition `from` → `to` observed and pooled?
+    pub fn contains(&self, from: &str, to: &str) -> bool {
+        self.observed.contains(&transition_fingerprint(from, to))
+    }
+}
+
+/// Stable 64-bit fingerprint of the transition `from` → `to` (the
+/// first 8 bytes of SHA-256 over both cells).
+pub fn transition_fingerprint(from: &str, to: &str) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(from.as_bytes())
+        .chain_update([0u8])
+        .chain_update(to.as_bytes())
+        .finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 is 32 bytes"))
+}
+
 /// Behavioral profile learned from the trajectory history.
 /// Built incrementally as breadcrumbs are processed.
+///
+/// Serializable so an Attester can checkpoint a built profile and
+/// restore it after a restart instead of rebuilding it from the chain
+/// (`psd::StreamingPsd` does the same for the PSD).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BehavioralProfile {
     /// Histogram of H3 cells visited (cell_hex → visit count)
     pub cell_histogram: HashMap<String, u32>,
@@ -117,18 +311,74 @@ pub struct BehavioralProfile {
     pub mean_interval_seconds: f64,
     /// Std deviation of intervals
     pub std_interval_seconds: f64,
-    /// Transition probabilities between top cells
+    /// Transition probabilities out of the most frequent source cells
+    #[serde(with = "transition_entries")]
     pub transition_matrix: HashMap<(String, String), f64>,
+    /// Pooled transitions out of the other sources; None if the
+    /// matrix wasn't capped
+    #[serde(default)]
+    pub other_transitions: Option<OtherTransitions>,
+    /// Whether the genesis breadcrumb was included
+    pub genesis: GenesisHandling,
+    /// H_kinetic energy for never-seen transitions
+    pub novel_transition: NovelTransitionPenalty,
+    /// H_spatial / H_structure energy when the spread is degenerate
+    #[serde(default)]
+    pub degenerate_spread: DegenerateSpread,
 }
 
 impl BehavioralProfile {
+    /// Whether displacement or interval spread is below the
+    /// degenerate-spread tolerance.
+    pub fn has_degenerate_spread(&self) -> bool {
+        self.std_displacement_km < self.degenerate_spread.tolerance
+            || self.std_interval_seconds < self.degenerate_spread.tolerance
+    }
+
     /// Build a behavioral profile from a verified chain.
     pub fn from_chain(chain: &BreadcrumbChain) -> Self {
-        let n = chain.breadcrumbs.len();
+        Self::from_chain_with_config(chain, &ProfileConfig::default())
+    }
+
+    /// Build a displacement/interval-only profile (no cell data).
+    /// Cell histogram, anchors, hourly profile and transitions are empty.
+    pub fn from_series(
+        displacement_km: &[f64],
+        interval_seconds: &[f64],
+        config: &ProfileConfig,
+    ) -> Self {
+        let mean = |v: &[f64]| if v.is_empty() { 0.0 } else { v.iter().sum::<f64>() / v.len() as f64 };
+        let mean_displacement_km = mean(displacement_km);
+        let mean_interval_seconds = mean(interval_seconds);
+
+        Self {
+            cell_histogram: HashMap::new(),
+            anchor_cells: Vec::new(),
+            mean_displacement_km,
+            std_displacement_km: dispersion(displacement_km, mean_displacement_km, config),
+            hourly_profile: [0.0; 24],
+            mean_interval_seconds,
+            std_interval_seconds: dispersion(interval_seconds, mean_interval_seconds, config),
+            transition_matrix: HashMap::new(),
+            other_transitions: None,
+            genesis: GenesisHandling::Exclude,
+            novel_transition: config.novel_transition,
+            degenerate_spread: config.degenerate_spread,
+        }
+    }
+
+    /// Build a behavioral profile with explicit options.
+    pub fn from_chain_with_config(chain: &BreadcrumbChain, config: &ProfileConfig) -> Self {
+        let skip = match config.genesis {
+            GenesisHandling::Include => 0,
+            GenesisHandling::Exclude => 1.min(chain.breadcrumbs.len()),
+        };
+        let counted = &chain.breadcrumbs[skip..];
+        let n = counted.len();
 
         // Cell histogram
         let mut cell_histogram: HashMap<String, u32> = HashMap::new();
-        for b in &chain.breadcrumbs {
+        for b in counted {
             *cell_histogram.entry(b.location_cell.clone()).or_insert(0) += 1;
         }
 
@@ -146,11 +396,11 @@ impl BehavioralProfile {
         } else {
             displacements.iter().sum::<f64>() / displacements.len() as f64
         };
-        let std_displacement_km = std_dev(&displacements, mean_displacement_km);
+        let std_displacement_km = dispersion(&displacements, mean_displacement_km, config);
 
         // Hourly profile
         let mut hour_counts = [0u32; 24];
-        for b in &chain.breadcrumbs {
+        for b in counted {
             let hour = b.timestamp.hour() as usize;
             hour_counts[hour] += 1;
         }
@@ -166,7 +416,7 @@ impl BehavioralProfile {
         } else {
             intervals.iter().sum::<f64>() / intervals.len() as f64
         };
-        let std_interval_seconds = std_dev(&intervals, mean_interval_seconds);
+        let std_interval_seconds = dispersion(&intervals, mean_interval_seconds, config);
 
         // Transition matrix (cell_i → cell_j counts, normalized)
         let mut transitions: HashMap<(String, String), u32> = HashMap::new();
@@ -177,12 +427,8 @@ impl BehavioralProfile {
             *transitions.entry((from.clone(), to)).or_insert(0) += 1;
             *from_counts.entry(from).or_insert(0) += 1;
         }
-        let transition_matrix: HashMap<(String, String), f64> = transitions.into_iter()
-            .map(|((from, to), count)| {
-                let total = *from_counts.get(&from).unwrap_or(&1);
-                ((from, to), count as f64 / total as f64)
-            })
-            .collect();
+        let (transition_matrix, other_transitions) =
+            cap_transitions(transitions, &from_counts, config.max_transition_sources);
 
         Self {
             cell_histogram,
@@ -193,8 +439,153 @@ impl BehavioralProfile {
             mean_interval_seconds,
             std_interval_seconds,
             transition_matrix,
+            other_transitions,
+            genesis: config.genesis,
+            novel_transition: config.novel_transition,
+            degenerate_spread: config.degenerate_spread,
+        }
+    }
+}
+
+/// Normalize transition counts into probabilities, keeping rows for
+/// the `max_sources` most frequent sources (ties broken by cell) and
+/// pooling the rest.
+fn cap_transitions(
+    transitions: HashMap<(String, String), u32>,
+    from_counts: &HashMap<String, u32>,
+    max_sources: usize,
+) -> (HashMap<(String, String), f64>, Option<OtherTransitions>) {
+    let probability = |from: &str, count: u32| count as f64 / *from_counts.get(from).unwrap_or(&1) as f64;
+
+    if from_counts.len() <= max_sources {
+        let matrix = transitions.into_iter()
+            .map(|((from, to), count)| {
+                let p = probability(&from, count);
+                ((from, to), p)
+            })
+            .collect();
+        return (matrix, None);
+    }
+
+    let mut sources: Vec<(&String, u32)> = from_counts.iter().map(|(cell, &n)| (cell, n)).collect();
+    sources.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let kept_sources: HashSet<String> = sources.into_iter()
+        .take(max_sources)
+        .map(|(cell, _)| cell.clone())
+        .collect();
+
+    let mut matrix = HashMap::new();
+    let mut observed = HashSet::new();
+    let (mut weighted, mut dropped) = (0.0, 0u32);
+    for ((from, to), count) in transitions {
+        let p = probability(&from, count);
+        if kept_sources.contains(&from) {
+            matrix.insert((from, to), p);
+        } else {
+            observed.insert(transition_fingerprint(&from, &to));
+            weighted += count as f64 * p;
+            dropped += count;
         }
     }
+
+    let other = OtherTransitions {
+        kept_sources,
+        probability: weighted / dropped.max(1) as f64,
+        observed,
+    };
+    (matrix, Some(other))
+}
+
+/// Pluggable per-breadcrumb anomaly scorer.
+///
+/// The Criticality Engine defaults to the six-component Hamiltonian,
+/// but any scorer mapping a breadcrumb (and its predecessor) to an
+/// energy in [0, 1] can drive the verdict instead.
+pub trait AnomalyScorer: Send + Sync {
+    /// Anomaly energy for `current`; `prev` is None for the genesis.
+    fn score(
+        &self,
+        current: &Breadcrumb,
+        prev: Option<&Breadcrumb>,
+        profile: &BehavioralProfile,
+    ) -> f64;
+
+    /// Short name for diagnostics.
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    /// Identifies the scorer, parameters included, in
+    /// `CriticalityEngine::digest`. Defaults to `name`; override it
+    /// whenever parameters change the scores.
+    fn fingerprint(&self) -> String {
+        self.name().to_string()
+    }
+}
+
+/// The six-component Hamiltonian as an AnomalyScorer.
+#[derive(Debug, Clone, Default)]
+pub struct HamiltonianScorer {
+    pub weights: HamiltonianWeights,
+}
+
+impl AnomalyScorer for HamiltonianScorer {
+    fn score(
+        &self,
+        current: &Breadcrumb,
+        prev: Option<&Breadcrumb>,
+        profile: &BehavioralProfile,
+    ) -> f64 {
+        score_breadcrumb(current, prev, profile, &self.weights).h_total
+    }
+
+    fn name(&self) -> &str {
+        "hamiltonian"
+    }
+
+    fn fingerprint(&self) -> String {
+        let weights = serde_json::to_string(&self.weights).unwrap_or_default();
+        format!("hamiltonian:{weights}")
+    }
+}
+
+/// Evaluate the Hamiltonian on pre-computed displacements only
+/// (location-blind mode).
+///
+/// Only H_spatial and H_structure can be computed without cells and
+/// timestamps. H_temporal, H_kinetic and H_contextual contribute 0.0,
+/// as H_flock already does, so energies are lower-bounded relative
+/// to the full path. Score `i` belongs to breadcrumb index `i + 1`;
+/// there is no genesis score.
+pub fn evaluate_displacement_hamiltonian(
+    displacement_km: &[f64],
+    interval_seconds: &[f64],
+    weights: &HamiltonianWeights,
+    config: &ProfileConfig,
+) -> ChainHamiltonianResult {
+    let profile = BehavioralProfile::from_series(displacement_km, interval_seconds, config);
+
+    let scores = displacement_km.iter()
+        .zip(interval_seconds)
+        .enumerate()
+        .map(|(i, (&dist, &dt))| {
+            let h_spatial = spatial_energy(dist, &profile);
+            let h_structure = structure_energy(dt, &profile);
+            let h_total = weights.spatial * h_spatial + weights.structure * h_structure;
+            HamiltonianScore {
+                index: i as u64 + 1,
+                h_spatial,
+                h_temporal: 0.0,
+                h_kinetic: 0.0,
+                h_flock: 0.0,
+                h_contextual: 0.0,
+                h_structure,
+                h_total,
+                alert_level: AlertLevel::from_energy(h_total),
+            }
+        })
+        .collect();
+    summarize_scores(scores, profile.has_degenerate_spread())
 }
 
 /// Evaluate the six-component Hamiltonian for every breadcrumb
@@ -204,45 +595,97 @@ pub fn evaluate_hamiltonian(
     profile: &BehavioralProfile,
     weights: &HamiltonianWeights,
 ) -> ChainHamiltonianResult {
-    let mut scores = Vec::with_capacity(chain.len());
-    let mut alert_count = AlertCounts::default();
+    let scores = scored_breadcrumbs(chain, profile)
+        .map(|(current, prev)| score_breadcrumb(current, prev, profile, weights))
+        .collect();
+    summarize_scores(scores, profile.has_degenerate_spread())
+}
 
-    for (i, breadcrumb) in chain.breadcrumbs.iter().enumerate() {
-        let prev = if i > 0 { Some(&chain.breadcrumbs[i - 1]) } else { None };
-
-        let h_spatial = compute_h_spatial(breadcrumb, prev, profile);
-        let h_temporal = compute_h_temporal(breadcrumb, profile);
-        let h_kinetic = compute_h_kinetic(breadcrumb, prev, profile);
-        let h_flock = compute_h_flock(breadcrumb); // placeholder
-        let h_contextual = compute_h_contextual(breadcrumb, prev);
-        let h_structure = compute_h_structure(breadcrumb, prev, profile);
-
-        let h_total = weights.spatial * h_spatial
-            + weights.temporal * h_temporal
-            + weights.kinetic * h_kinetic
-            + weights.flock * h_flock
-            + weights.contextual * h_contextual
-            + weights.structure * h_structure;
-
-        let alert_level = AlertLevel::from_energy(h_total);
-        match alert_level {
+/// Evaluate a custom AnomalyScorer over the chain.
+/// Only `h_total` is populated; the component fields are 0.0.
+pub fn evaluate_with_scorer(
+    chain: &BreadcrumbChain,
+    profile: &BehavioralProfile,
+    scorer: &dyn AnomalyScorer,
+) -> ChainHamiltonianResult {
+    let scores = scored_breadcrumbs(chain, profile)
+        .map(|(current, prev)| {
+            let h_total = scorer.score(current, prev, profile);
+            HamiltonianScore {
+                index: current.index,
+                h_spatial: 0.0,
+                h_temporal: 0.0,
+                h_kinetic: 0.0,
+                h_flock: 0.0,
+                h_contextual: 0.0,
+                h_structure: 0.0,
+                h_total,
+                alert_level: AlertLevel::from_energy(h_total),
+            }
+        })
+        .collect();
+    summarize_scores(scores, profile.has_degenerate_spread())
+}
+
+/// (current, prev) pairs to score, honoring the profile's genesis handling.
+fn scored_breadcrumbs<'a>(
+    chain: &'a BreadcrumbChain,
+    profile: &BehavioralProfile,
+) -> impl Iterator<Item = (&'a Breadcrumb, Option<&'a Breadcrumb>)> {
+    let skip = match profile.genesis {
+        GenesisHandling::Include => 0,
+        GenesisHandling::Exclude => 1,
+    };
+    chain.breadcrumbs.iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(i, b)| (b, if i > 0 { Some(&chain.breadcrumbs[i - 1]) } else { None }))
+}
+
+/// Score a single breadcrumb across all six components.
+fn score_breadcrumb(
+    breadcrumb: &Breadcrumb,
+    prev: Option<&Breadcrumb>,
+    profile: &BehavioralProfile,
+    weights: &HamiltonianWeights,
+) -> HamiltonianScore {
+    let h_spatial = compute_h_spatial(breadcrumb, prev, profile);
+    let h_temporal = compute_h_temporal(breadcrumb, profile);
+    let h_kinetic = compute_h_kinetic(breadcrumb, prev, profile);
+    let h_flock = compute_h_flock(breadcrumb); // placeholder
+    let h_contextual = compute_h_contextual(breadcrumb, prev);
+    let h_structure = compute_h_structure(breadcrumb, prev, profile);
+
+    let h_total = weights.spatial * h_spatial
+        + weights.temporal * h_temporal
+        + weights.kinetic * h_kinetic
+        + weights.flock * h_flock
+        + weights.contextual * h_contextual
+        + weights.structure * h_structure;
+
+    HamiltonianScore {
+        index: breadcrumb.index,
+        h_spatial,
+        h_temporal,
+        h_kinetic,
+        h_flock,
+        h_contextual,
+        h_structure,
+        h_total,
+        alert_level: AlertLevel::from_energy(h_total),
+    }
+}
+
+/// Aggregate per-breadcrumb scores into chain statistics.
+fn summarize_scores(scores: Vec<HamiltonianScore>, degenerate_spread: bool) -> ChainHamiltonianResult {
+    let mut alert_count = AlertCounts::default();
+    for score in &scores {
+        match score.alert_level {
             AlertLevel::Green => alert_count.green += 1,
             AlertLevel::Yellow => alert_count.yellow += 1,
             AlertLevel::Orange => alert_count.orange += 1,
             AlertLevel::Red => alert_count.red += 1,
         }
-
-        scores.push(HamiltonianScore {
-            index: breadcrumb.index,
-            h_spatial,
-            h_temporal,
-            h_kinetic,
-            h_flock,
-            h_contextual,
-            h_structure,
-            h_total,
-            alert_level,
-        });
     }
 
     let mean_energy = if scores.is_empty() {
@@ -253,12 +696,19 @@ pub fn evaluate_hamiltonian(
     let max_energy = scores.iter()
         .map(|s| s.h_total)
         .fold(0.0f64, f64::max);
+    let energy_variance = if scores.is_empty() {
+        0.0
+    } else {
+        scores.iter().map(|s| (s.h_total - mean_energy).powi(2)).sum::<f64>() / scores.len() as f64
+    };
 
     ChainHamiltonianResult {
         scores,
         mean_energy,
         max_energy,
+        energy_variance,
         alert_count,
+        degenerate_spread,
     }
 }
 
@@ -268,7 +718,8 @@ pub fn evaluate_hamiltonian(
 
 /// H_spatial: Displacement anomaly.
 /// Detects teleportation / impossible jumps.
-/// Energy = normalized distance from mean displacement.
+/// Energy = normalized distance from mean displacement; a constant
+/// step length is flagged via `DegenerateSpread`.
 fn compute_h_spatial(
     current: &Breadcrumb,
     prev: Option<&Breadcrumb>,
@@ -284,8 +735,17 @@ fn compute_h_spatial(
         &current.location_cell,
     );
 
-    if profile.std_displacement_km < 0.001 {
-        return 0.0;
+    spatial_energy(dist, profile)
+}
+
+/// H_spatial for a known displacement (km).
+fn spatial_energy(dist: f64, profile: &BehavioralProfile) -> f64 {
+    if let Some(energy) = profile.degenerate_spread.energy(
+        dist,
+        profile.mean_displacement_km,
+        profile.std_displacement_km,
+    ) {
+        return energy;
     }
 
     // Z-score clamped to [0, 1]
@@ -315,6 +775,9 @@ fn compute_h_temporal(
 /// H_kinetic: Transition anomaly.
 /// Detects improbable anchor transitions.
 /// Energy based on how unlikely the cell-to-cell transition is.
+/// Observed transitions out of a source dropped from a capped matrix
+/// use the pooled "other" probability; never-seen ones are novel
+/// either way.
 fn compute_h_kinetic(
     current: &Breadcrumb,
     prev: Option<&Breadcrumb>,
@@ -325,9 +788,18 @@ fn compute_h_kinetic(
         None => return 0.0,
     };
 
-    let key = (prev.location_cell.clone(), current.location_cell.clone());
-    match profile.transition_matrix.get(&key) {
-        Some(&prob) if prob > 0.0 => {
+    let pooled = profile.other_transitions.as_ref()
+        .filter(|other| !other.kept_sources.contains(&prev.location_cell));
+    let probability = match pooled {
+        Some(other) => other.contains(&prev.location_cell, &current.location_cell)
+            .then_some(other.probability),
+        None => {
+            let key = (prev.location_cell.clone(), current.location_cell.clone());
+            profile.transition_matrix.get(&key).copied()
+        }
+    };
+    match probability {
+        Some(prob) if prob > 0.0 => {
             // Higher probability → lower energy
             // -log2(prob) normalized to [0, 1]
             let surprise = -prob.log2();
@@ -335,7 +807,7 @@ fn compute_h_kinetic(
         }
         _ => {
             // Never-before-seen transition
-            0.7 // suspicious but might be exploring new area
+            profile.novel_transition.energy(&current.location_cell, &profile.anchor_cells)
         }
     }
 }
@@ -393,18 +865,31 @@ fn compute_h_structure(
         None => return 0.0,
     };
 
-    let dt = (current.unix_seconds() - prev.unix_seconds()).max(0.001);
+    // A non-positive interval can't come from a verified chain;
+    // treat it as maximally anomalous rather than clamping.
+    let dt = match crate::breadcrumb::interval_seconds(prev, current) {
+        Ok(dt) => dt,
+        Err(_) => return 1.0,
+    };
 
-    if profile.std_interval_seconds < 0.001 {
-        return 0.0;
+    structure_energy(dt, profile)
+}
+
+/// H_structure for a known interval (seconds).
+fn structure_energy(dt: f64, profile: &BehavioralProfile) -> f64 {
+    if let Some(energy) = profile.degenerate_spread.energy(
+        dt,
+        profile.mean_interval_seconds,
+        profile.std_interval_seconds,
+    ) {
+        return energy;
     }
 
     // Z-score of interval
     let z = ((dt - profile.mean_interval_seconds) / profile.std_interval_seconds).abs();
 
-    // Extremely regular intervals (z ≈ 0 for all breadcrumbs) are
-    // themselves suspicious. But for single-breadcrumb scoring,
-    // we just flag individually outlying intervals.
+    // Perfectly regular intervals are caught above as a degenerate
+    // spread; otherwise flag individually outlying intervals.
     sigmoid(z, 3.0)
 }
 
@@ -418,6 +903,45 @@ fn sigmoid(x: f64, midpoint: f64) -> f64 {
     1.0 / (1.0 + (-2.0 * (x - midpoint)).exp())
 }
 
+/// Spread of `values` according to the profile config. Only the
+/// std-dev decides that a spread is degenerate: a majority of equal
+/// values zeroes the MAD without making the series constant.
+fn dispersion(values: &[f64], mean: f64, config: &ProfileConfig) -> f64 {
+    if config.robust_dispersion {
+        let mad = mad_std_dev(values);
+        if mad >= config.degenerate_spread.tolerance {
+            return mad;
+        }
+    }
+    std_dev(values, mean)
+}
+
+/// Robust standard deviation via median absolute deviation:
+///   σ ≈ 1.4826 · median(|x_i − median(x)|)
+/// The 1.4826 factor (1 / Φ⁻¹(3/4)) makes MAD a consistent estimator
+/// of σ for normally distributed data, so it is a drop-in replacement
+/// for the classic std-dev in z-scores.
+fn mad_std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let center = median(values);
+    let deviations: Vec<f64> = values.iter().map(|&x| (x - center).abs()).collect();
+    1.4826 * median(&deviations)
+}
+
+/// Median of a slice (NaNs sort last).
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
 /// Standard deviation helper
 fn std_dev(values: &[f64], mean: f64) -> f64 {
     if values.len() < 2 {
@@ -429,6 +953,27 @@ fn std_dev(values: &[f64], mean: f64) -> f64 {
     variance.sqrt()
 }
 
+/// (from, to) keys aren't valid JSON object keys, so the transition
+/// matrix is serialized as a list of (from, to, probability) entries.
+mod transition_entries {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer>(
+        matrix: &HashMap<(String, String), f64>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(matrix.iter().map(|((from, to), p)| (from, to, p)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<HashMap<(String, String), f64>, D::Error> {
+        let entries: Vec<(String, String, f64)> = Vec::deserialize(deserializer)?;
+        Ok(entries.into_iter().map(|(from, to, p)| ((from, to), p)).collect())
+    }
+}
+
 use chrono::Timelike;
 
 #[cfg(test)]
@@ -453,10 +998,254 @@ mod tests {
         assert_eq!(AlertLevel::from_energy(0.9), AlertLevel::Red);
     }
 
+    #[test]
+    fn test_mad_resists_single_outlier() {
+        let clean: Vec<f64> = (0..50).map(|i| 1.0 + 0.1 * ((i % 7) as f64 - 3.0)).collect();
+        let mut dirty = clean.clone();
+        dirty[25] = 500.0; // one teleport
+
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        let std_clean = std_dev(&clean, mean(&clean));
+        let std_dirty = std_dev(&dirty, mean(&dirty));
+        let mad_clean = mad_std_dev(&clean);
+        let mad_dirty = mad_std_dev(&dirty);
+
+        assert!(std_dirty > 100.0 * std_clean, "std: {std_clean} -> {std_dirty}");
+        assert!((mad_dirty - mad_clean).abs() < 0.1 * mad_clean, "mad: {mad_clean} -> {mad_dirty}");
+    }
+
+    #[test]
+    fn test_mad_consistent_with_std_for_normal_data() {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(1);
+        // Box-Muller, σ = 2
+        let values: Vec<f64> = (0..20_000)
+            .map(|_| {
+                let u1: f64 = rng.gen_range(1e-12..1.0);
+                let u2: f64 = rng.gen_range(0.0..1.0);
+                2.0 * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+            })
+            .collect();
+        assert!((mad_std_dev(&values) - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_robust_dispersion_majority_equal_not_degenerate() {
+        // A fixed 30-minute timer most of the time: the MAD is zero,
+        // the series is not constant
+        let intervals: Vec<f64> = (0..400)
+            .map(|i| if i % 20 < 11 { 1800.0 } else { 1800.0 + ((i * 37) % 120) as f64 })
+            .collect();
+        assert_eq!(mad_std_dev(&intervals), 0.0);
+        let displacements: Vec<f64> = (0..400).map(|i| 0.5 + 0.1 * (i % 7) as f64).collect();
+
+        let robust = ProfileConfig { robust_dispersion: true, ..Default::default() };
+        let profile = BehavioralProfile::from_series(&displacements, &intervals, &robust);
+        let classic = BehavioralProfile::from_series(&displacements, &intervals, &ProfileConfig::default());
+        assert!(!profile.has_degenerate_spread());
+        assert_eq!(profile.std_interval_seconds, classic.std_interval_seconds);
+        assert_eq!(profile.std_displacement_km, mad_std_dev(&displacements));
+
+        // A constant series is still degenerate
+        let constant = BehavioralProfile::from_series(&displacements, &[1800.0; 400], &robust);
+        assert!(constant.has_degenerate_spread());
+    }
+
+    #[test]
+    fn test_genesis_included_vs_excluded() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        // Genesis at 03:00 in its own cell, the rest at midday elsewhere
+        let mut samples = vec![(1_700_017_200i64, "8a1e8052da1ffff")];
+        samples.extend((1..10).map(|i| (1_700_049_600 + i * 900, "8a1e8052db1ffff")));
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        let included = BehavioralProfile::from_chain(&chain);
+        let excluded = BehavioralProfile::from_chain_with_config(
+            &chain,
+            &ProfileConfig { genesis: GenesisHandling::Exclude, ..Default::default() },
+        );
+
+        assert_eq!(included.cell_histogram.len(), 2);
+        assert_eq!(excluded.cell_histogram.len(), 1);
+        assert!(included.hourly_profile[3] > 0.0);
+        assert_eq!(excluded.hourly_profile[3], 0.0);
+        assert!((excluded.hourly_profile.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // Pairwise statistics never include the genesis
+        assert_eq!(included.mean_displacement_km, excluded.mean_displacement_km);
+        assert_eq!(included.mean_interval_seconds, excluded.mean_interval_seconds);
+
+        let weights = HamiltonianWeights::default();
+        let with_genesis = evaluate_hamiltonian(&chain, &included, &weights);
+        let without_genesis = evaluate_hamiltonian(&chain, &excluded, &weights);
+        assert_eq!(with_genesis.scores.len(), 10);
+        assert_eq!(without_genesis.scores.len(), 9);
+        assert_eq!(without_genesis.scores[0].index, 1);
+    }
+
+    #[test]
+    fn test_profile_checkpoint_roundtrip() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(120, 8)).unwrap();
+        let profile = BehavioralProfile::from_chain(&chain);
+        assert!(!profile.transition_matrix.is_empty());
+
+        let checkpoint = serde_json::to_string(&profile).unwrap();
+        let restored: BehavioralProfile = serde_json::from_str(&checkpoint).unwrap();
+        assert_eq!(restored.cell_histogram, profile.cell_histogram);
+        assert_eq!(restored.transition_matrix, profile.transition_matrix);
+        assert_eq!(restored.hourly_profile, profile.hourly_profile);
+        assert_eq!(restored.mean_displacement_km, profile.mean_displacement_km);
+        assert_eq!(restored.std_displacement_km, profile.std_displacement_km);
+        assert_eq!(restored.std_interval_seconds, profile.std_interval_seconds);
+
+        let weights = HamiltonianWeights::default();
+        let before = evaluate_hamiltonian(&chain, &profile, &weights);
+        let after = evaluate_hamiltonian(&chain, &restored, &weights);
+        assert_eq!(before.mean_energy, after.mean_energy);
+    }
+
+    #[test]
+    fn test_transition_matrix_capped_to_top_sources() {
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        // Mostly one-off cells: almost every source is rare
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(300, 5)).unwrap();
+        let config = ProfileConfig { max_transition_sources: 16, ..Default::default() };
+        let capped = BehavioralProfile::from_chain_with_config(&chain, &config);
+        let full = BehavioralProfile::from_chain(&chain);
+        assert!(full.other_transitions.is_none());
+
+        let other = capped.other_transitions.as_ref().unwrap();
+        assert_eq!(other.kept_sources.len(), 16);
+        assert!(other.probability > 0.0 && other.probability <= 1.0);
+        assert!(capped.transition_matrix.keys().all(|(from, _)| other.kept_sources.contains(from)));
+        assert!(capped.transition_matrix.len() < full.transition_matrix.len());
+        for (key, p) in &capped.transition_matrix {
+            assert_eq!(full.transition_matrix.get(key), Some(p));
+        }
+
+        // A transition out of a dropped source scores from the pooled bucket
+        let pair = chain.breadcrumbs.windows(2)
+            .find(|pair| !other.kept_sources.contains(&pair[0].location_cell))
+            .unwrap();
+        let energy = compute_h_kinetic(&pair[1], Some(&pair[0]), &capped);
+        assert_eq!(energy, sigmoid(-other.probability.log2(), 5.0));
+
+        // A never-seen transition out of a dropped source is still novel
+        let novel = NovelTransitionPenalty::default().base;
+        let unseen = chain.breadcrumbs.iter()
+            .find(|b| !full.transition_matrix.contains_key(&(pair[0].location_cell.clone(), b.location_cell.clone())))
+            .unwrap();
+        assert_eq!(compute_h_kinetic(unseen, Some(&pair[0]), &capped), novel);
+        assert!(novel > 0.5 && energy < novel);
+        // As is one out of a cell never visited at all
+        let mut stranger = pair[0].clone();
+        stranger.location_cell = "8a2a1072b59ffff".to_string();
+        assert_eq!(compute_h_kinetic(&pair[1], Some(&stranger), &capped), novel);
+
+        let result = evaluate_hamiltonian(&chain, &capped, &HamiltonianWeights::default());
+        assert!(result.scores.iter().all(|s| (0.0..=1.0).contains(&s.h_kinetic)));
+
+        let restored: BehavioralProfile =
+            serde_json::from_str(&serde_json::to_string(&capped).unwrap()).unwrap();
+        assert_eq!(restored.other_transitions, capped.other_transitions);
+    }
+
+    #[test]
+    fn test_novel_transition_scaled_by_anchor_distance() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let (home, work) = (cell(41.9028, 12.4964), cell(41.9100, 12.4800));
+        let samples: Vec<(i64, &str)> = (0..40)
+            .map(|i| (1_700_000_000 + i * 1800, if i % 2 == 0 { home.as_str() } else { work.as_str() }))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+
+        let config = ProfileConfig {
+            novel_transition: NovelTransitionPenalty { base: 0.7, anchor_scale_km: Some(5.0) },
+            ..Default::default()
+        };
+        let profile = BehavioralProfile::from_chain_with_config(&chain, &config);
+        assert!(profile.anchor_cells.contains(&home));
+
+        let last = chain.breadcrumbs.last().unwrap();
+        let mut nearby = last.clone();
+        nearby.location_cell = cell(41.9040, 12.4975); // ~150 m from home
+        let mut distant = last.clone();
+        distant.location_cell = cell(48.8566, 2.3522); // Paris
+
+        let near_energy = compute_h_kinetic(&nearby, Some(last), &profile);
+        let far_energy = compute_h_kinetic(&distant, Some(last), &profile);
+        assert!(near_energy < 0.05, "nearby: {near_energy}");
+        assert!(far_energy > 0.69 && far_energy <= 0.7, "distant: {far_energy}");
+
+        // Default keeps the flat penalty
+        let flat = BehavioralProfile::from_chain(&chain);
+        assert_eq!(compute_h_kinetic(&nearby, Some(last), &flat), 0.7);
+        assert_eq!(compute_h_kinetic(&distant, Some(last), &flat), 0.7);
+    }
+
+    #[test]
+    fn test_perfectly_regular_chain_not_all_green() {
+        use crate::chain::tests::synthetic_breadcrumbs;
+
+        // Same two cells, same 30-minute cadence: zero spread in both
+        // displacement and interval
+        let cell = |lat: f64, lng: f64| {
+            h3o::LatLng::new(lat, lng).unwrap().to_cell(h3o::Resolution::Ten).to_string()
+        };
+        let (home, work) = (cell(41.9028, 12.4964), cell(41.9100, 12.4800));
+        let samples: Vec<(i64, &str)> = (0..48)
+            .map(|i| (1_700_000_000 + i * 1800, if i % 2 == 0 { home.as_str() } else { work.as_str() }))
+            .collect();
+        let chain = BreadcrumbChain::from_breadcrumbs(synthetic_breadcrumbs(&samples)).unwrap();
+        let profile = BehavioralProfile::from_chain(&chain);
+        assert!(profile.std_displacement_km < 0.001);
+        assert!(profile.std_interval_seconds < 0.001);
+        assert!(profile.has_degenerate_spread());
+
+        let result = evaluate_hamiltonian(&chain, &profile, &HamiltonianWeights::default());
+        assert!(result.degenerate_spread);
+        assert!(result.alert_count.green < result.scores.len());
+        let last = result.scores.last().unwrap();
+        assert_eq!(last.h_spatial, 1.0);
+        assert_eq!(last.h_structure, 1.0);
+
+        // A deviation from the constant cadence is maximally anomalous
+        let mut late = chain.breadcrumbs[47].clone();
+        late.timestamp += chrono::Duration::seconds(60);
+        assert_eq!(compute_h_structure(&late, Some(&chain.breadcrumbs[46]), &profile), 1.0);
+
+        // The regularity energy is configurable
+        let config = ProfileConfig {
+            degenerate_spread: DegenerateSpread { tolerance: 0.001, regularity_energy: 0.0 },
+            ..Default::default()
+        };
+        let lenient = BehavioralProfile::from_chain_with_config(&chain, &config);
+        let result = evaluate_hamiltonian(&chain, &lenient, &HamiltonianWeights::default());
+        assert_eq!(result.alert_count.green, result.scores.len());
+    }
+
     #[test]
     fn test_default_weights_sum_to_one() {
         let w = HamiltonianWeights::default();
         let sum = w.spatial + w.temporal + w.kinetic + w.flock + w.contextual + w.structure;
         assert!((sum - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_median_tolerates_nan() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), 2.5);
+        // NaN sorts last under total order instead of breaking the sort
+        let mut values: Vec<f64> = (0..100).map(|i| ((i * 37) % 100) as f64).collect();
+        values.extend([f64::NAN; 3]);
+        assert_eq!(median(&values), 51.0);
+    }
 }
@@ -0,0 +1,208 @@
+// trip-verifier/src/history.rs
+//
+// Verification History
+// =====================
+//
+// A single CriticalityResult is a snapshot. A Verifier that sees the
+// same identity repeatedly can do better: accumulate past verdicts and
+// derive a longitudinal trust estimate.
+//
+// The estimate is an exponentially weighted moving average (EWMA) of
+// trust scores, started from an Anonymous prior (0), so an identity
+// earns its smoothed score by verifying consistently over time:
+//   s_k = λ · T_k + (1 − λ) · s_{k−1},   s_0 = prior
+//
+// A sudden drop of a new score below the smoothed estimate of the
+// records before it is flagged as a possible account takeover: the
+// key is the same, but the trajectory no longer looks like its owner.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::certificate::PoHCertificate;
+
+/// Records required before a drop is reported as a takeover.
+/// Avoids flagging identities still ramping up from the prior.
+pub const MIN_TAKEOVER_HISTORY: usize = 3;
+
+/// One past verification of an identity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerificationRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Trust score [0, 100]
+    pub trust_score: f64,
+    /// Number of breadcrumbs in the evaluated chain
+    pub chain_length: usize,
+}
+
+/// A verification scoring far below the identity's history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TakeoverAlert {
+    pub timestamp: DateTime<Utc>,
+    /// Smoothed trust before this verification
+    pub expected_score: f64,
+    /// Trust score of this verification
+    pub observed_score: f64,
+    /// expected_score − observed_score
+    pub drop: f64,
+}
+
+/// Configuration for history smoothing and takeover detection.
+#[derive(Debug, Clone)]
+pub struct HistoryConfig {
+    /// EWMA weight λ of the newest score, in (0, 1]
+    pub smoothing: f64,
+    /// Smoothed score before the first record
+    pub prior_score: f64,
+    /// Minimum drop (trust points) reported as a takeover
+    pub drop_threshold: f64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            smoothing: 0.3,
+            prior_score: 0.0,
+            drop_threshold: 25.0,
+        }
+    }
+}
+
+/// Past verifications, keyed by identity public key (hex).
+#[derive(Debug, Clone, Default)]
+pub struct VerificationHistory {
+    config: HistoryConfig,
+    records: HashMap<String, Vec<VerificationRecord>>,
+}
+
+impl VerificationHistory {
+    pub fn new(config: HistoryConfig) -> Self {
+        Self { config, records: HashMap::new() }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(HistoryConfig::default())
+    }
+
+    /// Record a verification, keeping records in timestamp order.
+    /// Returns an alert if it is a sudden drop against prior history.
+    pub fn record(&mut self, identity: &str, record: VerificationRecord) -> Option<TakeoverAlert> {
+        let records = self.records.entry(identity.to_string()).or_default();
+        let pos = records.partition_point(|r| r.timestamp <= record.timestamp);
+        records.insert(pos, record);
+
+        // Back-filled records don't describe the current state
+        let is_latest = pos + 1 == records.len();
+        if is_latest { self.detect_takeover(identity) } else { None }
+    }
+
+    /// Record the verdict carried by an issued certificate.
+    pub fn record_certificate(&mut self, cert: &PoHCertificate) -> Option<TakeoverAlert> {
+        self.record(&cert.identity_key, VerificationRecord {
+            timestamp: cert.issued_at,
+            trust_score: cert.trust_score,
+            chain_length: cert.chain_length as usize,
+        })
+    }
+
+    /// All records for an identity, oldest first.
+    pub fn records(&self, identity: &str) -> &[VerificationRecord] {
+        self.records.get(identity).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// EWMA trust estimate, or None if the identity was never seen.
+    pub fn smoothed_trust(&self, identity: &str) -> Option<f64> {
+        let records = self.records(identity);
+        (!records.is_empty()).then(|| self.ewma(records))
+    }
+
+    /// Check whether the latest record is a sudden drop relative to
+    /// the smoothed estimate of the records before it.
+    pub fn detect_takeover(&self, identity: &str) -> Option<TakeoverAlert> {
+        let records = self.records(identity);
+        let (latest, prior) = records.split_last()?;
+        if prior.len() < MIN_TAKEOVER_HISTORY {
+            return None;
+        }
+
+        let expected = self.ewma(prior);
+        let drop = expected - latest.trust_score;
+        (drop >= self.config.drop_threshold).then_some(TakeoverAlert {
+            timestamp: latest.timestamp,
+            expected_score: expected,
+            observed_score: latest.trust_score,
+            drop,
+        })
+    }
+
+    /// Number of identities tracked.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn ewma(&self, records: &[VerificationRecord]) -> f64 {
+        let lambda = self.config.smoothing.clamp(f64::EPSILON, 1.0);
+        records.iter().fold(self.config.prior_score, |s, r| {
+            lambda * r.trust_score + (1.0 - lambda) * s
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: &str = "abababababababababababababababababababababababababababababababab";
+
+    fn record(day: i64, trust_score: f64) -> VerificationRecord {
+        VerificationRecord {
+            timestamp: DateTime::from_timestamp(1_700_000_000 + day * 86_400, 0).unwrap(),
+            trust_score,
+            chain_length: 200 + day as usize * 24,
+        }
+    }
+
+    #[test]
+    fn test_steady_human_score_rises() {
+        let mut history = VerificationHistory::with_defaults();
+        let mut previous = 0.0;
+
+        for day in 0..10 {
+            let score = if day % 2 == 0 { 78.0 } else { 74.0 };
+            assert!(history.record(IDENTITY, record(day, score)).is_none());
+
+            let smoothed = history.smoothed_trust(IDENTITY).unwrap();
+            assert!(smoothed > previous, "day {day}: {smoothed} <= {previous}");
+            previous = smoothed;
+        }
+
+        assert!(previous > 70.0 && previous < 78.0, "smoothed: {previous}");
+        assert_eq!(history.records(IDENTITY).len(), 10);
+        assert_eq!(history.smoothed_trust("unknown"), None);
+    }
+
+    #[test]
+    fn test_takeover_drop_detected() {
+        let mut history = VerificationHistory::with_defaults();
+        for day in 0..10 {
+            history.record(IDENTITY, record(day, 76.0));
+        }
+
+        let alert = history.record(IDENTITY, record(10, 18.0)).expect("takeover alert");
+        assert_eq!(alert.observed_score, 18.0);
+        assert!(alert.expected_score > 70.0);
+        assert!(alert.drop >= 25.0);
+        assert_eq!(history.detect_takeover(IDENTITY), Some(alert));
+    }
+
+    #[test]
+    fn test_no_takeover_without_history() {
+        let mut history = VerificationHistory::with_defaults();
+        history.record(IDENTITY, record(0, 80.0));
+        history.record(IDENTITY, record(1, 80.0));
+        assert!(history.record(IDENTITY, record(2, 10.0)).is_none());
+    }
+}
@@ -14,13 +14,22 @@
 // Maximum Likelihood Estimation (MLE) on the truncated Pareto
 // distribution.
 //
+// `LevyResult::cdf_points` samples the empirical and fitted CDFs for
+// plotting, to see where a borderline KS statistic comes from.
+//
 // Reference: González, Hidalgo, Barabási (2008), "Understanding
 // individual human mobility patterns", Nature 453.
 
-use crate::error::{TripError, Result};
+use rand::Rng;
+use serde::Serialize;
+use crate::error::{TripError, Result, ensure_finite};
+
+/// Trapezoid steps in the numerical normalization of the truncated
+/// power law (see `fit_levy_with_steps`).
+pub const DEFAULT_INTEGRATION_STEPS: usize = 1000;
 
 /// Result of Lévy flight fitting.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LevyResult {
     /// Lévy exponent β.
     /// Human range: [0.8, 1.2]
@@ -39,9 +48,66 @@ pub struct LevyResult {
 
     /// Classification
     pub classification: LevyClassification,
+
+    /// Threshold the fit used (km)
+    pub x_min: f64,
+
+    /// The displacements above `x_min` that were fitted, ascending
+    pub samples: Vec<f64>,
+
+    /// Trapezoid steps of the fit's normalization integrals, reused
+    /// by `cdf_points`
+    pub integration_steps: usize,
+}
+
+impl LevyResult {
+    /// Placeholder for a fit that was skipped.
+    pub fn not_measured() -> Self {
+        Self {
+            beta: 0.0,
+            kappa_km: 0.0,
+            ks_statistic: 1.0,
+            n_samples: 0,
+            classification: LevyClassification::NotMeasured,
+            x_min: 0.0,
+            samples: Vec::new(),
+            integration_steps: DEFAULT_INTEGRATION_STEPS,
+        }
+    }
+
+    /// Empirical and fitted CDFs at `n` displacements spread
+    /// log-uniformly from `x_min` to the largest fitted displacement,
+    /// as (displacement km, empirical CDF, fitted CDF).
+    ///
+    /// The KS statistic is the largest gap between the two; plotting
+    /// them shows where the model diverges. Empty if nothing was fitted
+    /// or the fitted model cannot be normalized.
+    pub fn cdf_points(&self, n: usize) -> Vec<(f64, f64, f64)> {
+        let Some(&x_max) = self.samples.last() else {
+            return Vec::new();
+        };
+        let z_total = normalization_constant(self.beta, self.kappa_km, self.x_min, self.integration_steps);
+        if z_total <= 0.0 || !z_total.is_finite() {
+            return Vec::new();
+        }
+        let (log_min, log_max) = (self.x_min.ln(), x_max.ln());
+        let count = self.samples.len() as f64;
+
+        (0..n)
+            .map(|i| {
+                let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 1.0 };
+                let x = (log_min + (log_max - log_min) * t).exp().min(x_max);
+                let empirical = self.samples.partition_point(|&s| s <= x) as f64 / count;
+                // Clamped against quadrature error at the ends
+                let fitted = fitted_cdf(x, self.beta, self.kappa_km, z_total, self.integration_steps)
+                    .clamp(0.0, 1.0);
+                (x, empirical, fitted)
+            })
+            .collect()
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum LevyClassification {
     /// β < 0.5 — Too concentrated (possibly stationary bot)
     TooConcentrated,
@@ -53,6 +119,8 @@ pub enum LevyClassification {
     HighMobility,
     /// β > 1.8 — Ballistic (robot, drone, scripted)
     Ballistic,
+    /// Not fitted (stationary chain, see `StationaryPolicy`)
+    NotMeasured,
 }
 
 impl LevyClassification {
@@ -77,6 +145,7 @@ impl LevyClassification {
             Self::HumanLevy => "human_levy",
             Self::HighMobility => "high_mobility",
             Self::Ballistic => "ballistic",
+            Self::NotMeasured => "not_measured",
         }
     }
 }
@@ -88,14 +157,29 @@ impl LevyClassification {
 /// 2. Estimate κ from the exponential tail truncation
 ///
 /// # Arguments
-/// * `displacements` — displacement magnitudes in km (must be > 0)
+/// * `displacements` — displacement magnitudes in km (must be > 0;
+///   NaN/Inf fails with `NonFinite`)
 /// * `x_min` — minimum displacement threshold for fitting (km).
-///             Smaller displacements are noise from H3 quantization.
-///             Default: 0.01 km (10 meters)
+///   Smaller displacements are noise from H3 quantization.
+///   Default: 0.01 km (10 meters)
 pub fn fit_levy(displacements: &[f64], x_min: f64) -> Result<LevyResult> {
+    fit_levy_with_steps(displacements, x_min, DEFAULT_INTEGRATION_STEPS)
+}
+
+/// Like `fit_levy`, with `integration_steps` trapezoid steps per
+/// normalization integral. The KS statistic evaluates one integral
+/// per displacement, so fit time is linear in the step count; β does
+/// not depend on it.
+pub fn fit_levy_with_steps(
+    displacements: &[f64],
+    x_min: f64,
+    integration_steps: usize,
+) -> Result<LevyResult> {
+    ensure_finite("Lévy input", displacements)?;
+
     // Filter to displacements above threshold
     let mut valid: Vec<f64> = displacements.iter()
-        .filter(|&&d| d > x_min && d.is_finite())
+        .filter(|&&d| d > x_min)
         .copied()
         .collect();
 
@@ -109,26 +193,18 @@ pub fn fit_levy(displacements: &[f64], x_min: f64) -> Result<LevyResult> {
     let n = valid.len();
 
     // --- Step 1: Hill estimator for β ---
-    // β_hill = n / Σ ln(x_i / x_min)
-    let sum_log: f64 = valid.iter()
-        .map(|&x| (x / x_min).ln())
-        .sum();
-
-    if sum_log <= 0.0 {
-        return Err(TripError::LevyFitError(
-            "All displacements equal to x_min".to_string()
-        ));
-    }
-
-    let beta_hill = n as f64 / sum_log;
+    let beta_hill = hill_beta(&valid, x_min).ok_or_else(|| TripError::LevyFitError(
+        "All displacements equal to x_min".to_string()
+    ))?;
 
     // --- Step 2: Estimate κ via MLE grid search ---
     // For a truncated power law P(x) ∝ x^(-1-β) · exp(-x/κ),
     // we find κ that maximizes the log-likelihood.
-    let kappa = estimate_kappa(&valid, beta_hill, x_min);
+    let steps = integration_steps.max(1);
+    let kappa = estimate_kappa(&valid, beta_hill, x_min, steps);
 
     // --- Step 3: Kolmogorov-Smirnov goodness of fit ---
-    let ks = ks_test_truncated_pareto(&valid, beta_hill, kappa, x_min);
+    let ks = ks_test_truncated_pareto(&valid, beta_hill, kappa, x_min, steps);
 
     let classification = LevyClassification::from_beta(beta_hill);
 
@@ -138,6 +214,9 @@ pub fn fit_levy(displacements: &[f64], x_min: f64) -> Result<LevyResult> {
         ks_statistic: ks,
         n_samples: n,
         classification,
+        x_min,
+        samples: valid,
+        integration_steps: steps,
     })
 }
 
@@ -146,15 +225,51 @@ pub fn fit_levy_default(displacements: &[f64]) -> Result<LevyResult> {
     fit_levy(displacements, 0.01)
 }
 
+/// β of `iterations` bootstrap resamples of the displacements above
+/// `x_min` (drawn with replacement), for a percentile interval.
+/// Empty when there are too few displacements to fit.
+pub fn bootstrap_beta(
+    displacements: &[f64],
+    x_min: f64,
+    iterations: usize,
+    rng: &mut impl Rng,
+) -> Vec<f64> {
+    let valid: Vec<f64> = displacements.iter()
+        .filter(|&&d| d > x_min && d.is_finite())
+        .copied()
+        .collect();
+    if valid.len() < 20 {
+        return Vec::new();
+    }
+    let mut sample = vec![0.0; valid.len()];
+    (0..iterations)
+        .filter_map(|_| {
+            for x in sample.iter_mut() {
+                *x = valid[rng.gen_range(0..valid.len())];
+            }
+            hill_beta(&sample, x_min)
+        })
+        .collect()
+}
+
 // ========================================================================
 // Internal helpers
 // ========================================================================
 
+/// Hill estimator β = n / Σ ln(x_i / x_min) over values above x_min;
+/// None if they carry no spread above it.
+fn hill_beta(values: &[f64], x_min: f64) -> Option<f64> {
+    let sum_log: f64 = values.iter()
+        .map(|&x| (x / x_min).ln())
+        .sum();
+    (sum_log > 0.0).then(|| values.len() as f64 / sum_log)
+}
+
 /// Estimate κ via maximum likelihood on a grid.
 /// κ is the distance at which the power-law is truncated by
 /// an exponential cutoff. For humans, this represents their
 /// characteristic travel range.
-fn estimate_kappa(sorted_data: &[f64], beta: f64, x_min: f64) -> f64 {
+fn estimate_kappa(sorted_data: &[f64], beta: f64, x_min: f64, steps: usize) -> f64 {
     let x_max = sorted_data.last().copied().unwrap_or(100.0);
 
     // Search over a grid of κ values
@@ -169,7 +284,7 @@ fn estimate_kappa(sorted_data: &[f64], beta: f64, x_min: f64) -> f64 {
     for i in 0..n_grid {
         let kappa = (log_min + (log_max - log_min) * i as f64 / n_grid as f64).exp();
 
-        let ll = log_likelihood_truncated_pareto(sorted_data, beta, kappa, x_min);
+        let ll = log_likelihood_truncated_pareto(sorted_data, beta, kappa, x_min, steps);
 
         if ll > best_ll {
             best_ll = ll;
@@ -187,9 +302,10 @@ fn log_likelihood_truncated_pareto(
     beta: f64,
     kappa: f64,
     x_min: f64,
+    steps: usize,
 ) -> f64 {
     // Normalization constant (numerical integration)
-    let z = normalization_constant(beta, kappa, x_min);
+    let z = normalization_constant(beta, kappa, x_min, steps);
     if z <= 0.0 || !z.is_finite() {
         return f64::NEG_INFINITY;
     }
@@ -206,10 +322,9 @@ fn log_likelihood_truncated_pareto(
 /// Normalization constant for the truncated Pareto:
 /// Z = ∫_{x_min}^{∞} x^(-1-β) · exp(-x/κ) dx
 /// Computed via numerical quadrature (trapezoidal rule).
-fn normalization_constant(beta: f64, kappa: f64, x_min: f64) -> f64 {
+fn normalization_constant(beta: f64, kappa: f64, x_min: f64, n_steps: usize) -> f64 {
     // Integrate from x_min to x_min + 20*kappa (practically infinity)
     let x_max = x_min + 20.0 * kappa;
-    let n_steps = 1000;
     let dx = (x_max - x_min) / n_steps as f64;
 
     let mut integral = 0.0;
@@ -223,15 +338,22 @@ fn normalization_constant(beta: f64, kappa: f64, x_min: f64) -> f64 {
     integral * dx
 }
 
+/// Fitted CDF F(x) = 1 - Z(x)/Z(x_min), given `z_total` = Z(x_min).
+fn fitted_cdf(x: f64, beta: f64, kappa: f64, z_total: f64, steps: usize) -> f64 {
+    let z_tail = normalization_constant(beta, kappa, x, steps);
+    1.0 - z_tail / z_total
+}
+
 /// Kolmogorov-Smirnov test: max|F_empirical - F_theoretical|
 fn ks_test_truncated_pareto(
     sorted_data: &[f64],
     beta: f64,
     kappa: f64,
     x_min: f64,
+    steps: usize,
 ) -> f64 {
     let n = sorted_data.len() as f64;
-    let z_total = normalization_constant(beta, kappa, x_min);
+    let z_total = normalization_constant(beta, kappa, x_min, steps);
 
     if z_total <= 0.0 {
         return 1.0;
@@ -241,10 +363,7 @@ fn ks_test_truncated_pareto(
 
     for (i, &x) in sorted_data.iter().enumerate() {
         let empirical = (i + 1) as f64 / n;
-
-        // Theoretical CDF: F(x) = 1 - Z(x)/Z(x_min)
-        let z_tail = normalization_constant(beta, kappa, x);
-        let theoretical = 1.0 - z_tail / z_total;
+        let theoretical = fitted_cdf(x, beta, kappa, z_total, steps);
 
         let diff = (empirical - theoretical).abs();
         max_diff = max_diff.max(diff);
@@ -295,4 +414,34 @@ mod tests {
         assert_eq!(LevyClassification::from_beta(1.5), LevyClassification::HighMobility);
         assert_eq!(LevyClassification::from_beta(2.0), LevyClassification::Ballistic);
     }
+
+    #[test]
+    fn test_cdf_points_monotonic_and_bounded() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let data: Vec<f64> = (0..400)
+            .map(|_| 0.01 * rng.gen_range(0.001f64..1.0).powf(-1.0))
+            .collect();
+        let result = fit_levy(&data, 0.01).unwrap();
+
+        let points = result.cdf_points(100);
+        assert_eq!(points.len(), 100);
+        assert!((points[0].0 - 0.01).abs() < 1e-12);
+        assert_eq!(points[99].0, *result.samples.last().unwrap());
+        assert_eq!(points[99].1, 1.0);
+        for pair in points.windows(2) {
+            assert!(pair[1].0 > pair[0].0);
+            assert!(pair[1].1 >= pair[0].1);
+            assert!(pair[1].2 >= pair[0].2, "{:?} then {:?}", pair[0], pair[1]);
+        }
+        assert!(points.iter().all(|&(_, e, f)| (0.0..=1.0).contains(&e) && (0.0..=1.0).contains(&f)));
+
+        // The sampled gap never exceeds the KS statistic (up to the
+        // empirical step at each sample)
+        let max_gap = points.iter().map(|&(_, e, f)| (e - f).abs()).fold(0.0, f64::max);
+        assert!(max_gap <= result.ks_statistic + 1.0 / result.n_samples as f64);
+
+        assert!(LevyResult::not_measured().cdf_points(10).is_empty());
+    }
 }
@@ -7,15 +7,45 @@
 // breadcrumb Evidence from an Attester, evaluates trajectory
 // statistics using the Criticality Engine, and produces
 // Proof-of-Humanity (PoH) Certificates as Attestation Results.
+//
+// With the "tracing" feature, the Criticality Engine emits structured
+// events for each analysis stage; wire them to any tracing subscriber.
+// Without it the library is silent.
+
+/// Emit a tracing event if the "tracing" feature is enabled.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
 
 pub mod breadcrumb;
 pub mod chain;
+pub mod beacon;
 pub mod psd;
 pub mod levy;
 pub mod hamiltonian;
+pub mod replay;
+pub mod diurnal;
+pub mod waiting;
 pub mod criticality;
+pub mod privacy;
 pub mod certificate;
+pub mod revocation;
+pub mod calibration;
+pub mod history;
+pub mod batch;
+pub mod facet;
+pub mod similarity;
+pub mod audit;
+pub mod compact;
 pub mod verification;
+pub mod liveness;
+pub mod clock;
+pub mod pipeline;
+#[cfg(feature = "test-util")]
+pub mod scenario;
 pub mod error;
 
 // Re-exports for convenience
@@ -24,3 +54,4 @@ pub use chain::BreadcrumbChain;
 pub use criticality::CriticalityEngine;
 pub use certificate::PoHCertificate;
 pub use error::TripError;
+pub use pipeline::verify_chain_json;
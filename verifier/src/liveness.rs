@@ -0,0 +1,165 @@
+// trip-verifier/src/liveness.rs
+//
+// Liveness Score
+// ==============
+//
+// Humanity and liveness are different questions. The trust score asks
+// whether a chain's movement statistics are those of a real person,
+// and needs hundreds of breadcrumbs to answer. Liveness asks whether
+// the identity is active right now: its chain head is recent, it kept
+// sampling in the last window, and (when one was run) it answered an
+// Active Verification challenge. A relying party gating a session on
+// "someone is holding this device" needs only liveness; a long chain
+// recorded last month can be fully human and not live at all, and a
+// fresh install can be live long before it can be called human.
+//
+// Components, each in [0, 1]:
+// - recency:  1 − head_age / max_head_age, clamped
+// - density:  breadcrumbs in the last `window_seconds`, over
+//             `target_breadcrumbs`, capped at 1
+// - response: 1 if the challenge was answered in time
+//
+// score = mean of the components present. A failed challenge forces
+// the score to 0: the identity was asked to prove presence and didn't.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the liveness score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LivenessPolicy {
+    /// Trailing window counted for density (seconds)
+    pub window_seconds: u64,
+    /// Head age at which recency reaches 0 (seconds)
+    pub max_head_age_seconds: u64,
+    /// Breadcrumbs in the window for full density credit
+    pub target_breadcrumbs: usize,
+}
+
+impl Default for LivenessPolicy {
+    fn default() -> Self {
+        Self { window_seconds: 3600, max_head_age_seconds: 3600, target_breadcrumbs: 4 }
+    }
+}
+
+/// How recently and densely an identity has been active.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LivenessReport {
+    /// Seconds since the chain head; None without timestamps
+    /// (location-blind evaluation)
+    pub head_age_seconds: Option<f64>,
+    /// Breadcrumbs in the trailing window
+    pub recent_breadcrumbs: usize,
+    /// Recency component [0, 1]
+    pub recency: f64,
+    /// Density component [0, 1]
+    pub density: f64,
+    /// Active Verification outcome, once recorded
+    pub active_response: Option<bool>,
+}
+
+impl LivenessReport {
+    /// Report for a chain with no timestamps to judge.
+    pub fn not_measured() -> Self {
+        Self {
+            head_age_seconds: None,
+            recent_breadcrumbs: 0,
+            recency: 0.0,
+            density: 0.0,
+            active_response: None,
+        }
+    }
+
+    /// Liveness of a chain with these breadcrumb timestamps, at `now`.
+    pub fn from_timestamps(timestamps: &[DateTime<Utc>], now: DateTime<Utc>, policy: &LivenessPolicy) -> Self {
+        let Some(&head) = timestamps.iter().max() else {
+            return Self::not_measured();
+        };
+        // A head slightly in the future (clock skew) counts as now
+        let head_age_seconds = ((now - head).num_milliseconds() as f64 / 1000.0).max(0.0);
+        let recency = (1.0 - head_age_seconds / policy.max_head_age_seconds.max(1) as f64).clamp(0.0, 1.0);
+
+        // An unbounded window reaches back to the start of time
+        let window_start = i64::try_from(policy.window_seconds).ok()
+            .and_then(chrono::Duration::try_seconds)
+            .and_then(|window| now.checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let recent_breadcrumbs = timestamps.iter().filter(|&&t| t > window_start && t <= now).count();
+        let density = (recent_breadcrumbs as f64 / policy.target_breadcrumbs.max(1) as f64).min(1.0);
+
+        Self {
+            head_age_seconds: Some(head_age_seconds),
+            recent_breadcrumbs,
+            recency,
+            density,
+            active_response: None,
+        }
+    }
+
+    /// Record whether the identity answered an Active Verification
+    /// challenge (see `VerificationSession::validate_response`).
+    pub fn with_active_response(mut self, passed: bool) -> Self {
+        self.active_response = Some(passed);
+        self
+    }
+
+    /// Liveness score [0, 1]; see the module docs.
+    pub fn score(&self) -> f64 {
+        if self.active_response == Some(false) {
+            return 0.0;
+        }
+        let mut components = Vec::with_capacity(3);
+        if self.head_age_seconds.is_some() {
+            components.extend([self.recency, self.density]);
+        }
+        if self.active_response == Some(true) {
+            components.push(1.0);
+        }
+        if components.is_empty() {
+            return 0.0;
+        }
+        components.iter().sum::<f64>() / components.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_liveness_components() {
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let policy = LivenessPolicy::default();
+        let every = |minutes: i64, n: i64, head_age: i64| -> Vec<DateTime<Utc>> {
+            (0..n).map(|i| now - Duration::minutes(head_age + i * minutes)).collect()
+        };
+
+        let live = LivenessReport::from_timestamps(&every(10, 20, 0), now, &policy);
+        assert_eq!(live.head_age_seconds, Some(0.0));
+        assert_eq!((live.recency, live.density), (1.0, 1.0));
+        assert_eq!(live.score(), 1.0);
+
+        // Half an hour stale, two breadcrumbs in the window
+        let stale = LivenessReport::from_timestamps(&every(20, 20, 30), now, &policy);
+        assert_eq!(stale.recent_breadcrumbs, 2);
+        assert_eq!(stale.score(), (0.5 + 0.5) / 2.0);
+
+        // A day old: nothing left, whatever the chain was
+        let old = LivenessReport::from_timestamps(&every(10, 500, 24 * 60), now, &policy);
+        assert_eq!(old.score(), 0.0);
+
+        // The challenge adds a component, or zeroes the score if failed
+        assert_eq!(stale.clone().with_active_response(true).score(), 2.0 / 3.0);
+        assert_eq!(live.with_active_response(false).score(), 0.0);
+        assert_eq!(LivenessReport::not_measured().score(), 0.0);
+        assert_eq!(LivenessReport::not_measured().with_active_response(true).score(), 1.0);
+
+        // A window too long for a Duration saturates instead of panicking
+        for window_seconds in [i64::MAX as u64, u64::MAX] {
+            let unbounded = LivenessPolicy { window_seconds, ..policy };
+            let all = LivenessReport::from_timestamps(&every(20, 20, 30), now, &unbounded);
+            assert_eq!(all.recent_breadcrumbs, 20);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// trip-verifier/src/pipeline.rs
+//
+// One-Call Verification
+// =====================
+//
+// The whole Verifier path for a service that just received an
+// Attester's export: parse the JSON, rebuild and check the chain,
+// verify every breadcrumb signature and block hash, run the Criticality Engine and
+// issue a signed PoH Certificate. Each stage fails with its own error:
+//
+// | Stage             | Error                                         |
+// |-------------------|-----------------------------------------------|
+// | JSON / timestamps | `InvalidBreadcrumb`                           |
+// | Chain structure   | `ChainIntegrity`, `InsufficientBreadcrumbs`   |
+// | Signatures        | `SignatureInvalid { index }`                  |
+// | Block hashes      | `ChainIntegrity`                              |
+// | Analysis          | `InsufficientBreadcrumbs`, `PsdError`, ...    |
+// | Certificate       | `CertificateError`                            |
+//
+// The Verifier's own key is checked against a `RevocationList` first:
+// a revoked key issues nothing, as every Relying Party holding the
+// list would reject its certificates anyway.
+//
+// Certificates carry the config digest and a validity from the default
+// `ValidityPolicy`. Callers needing anything else (a nonce, a fixed
+// clock, another validity) compose the steps themselves.
+
+use ed25519_dalek::SigningKey;
+use crate::breadcrumb::parse_breadcrumbs;
+use crate::certificate::{PoHCertificate, ValidityPolicy};
+use crate::chain::BreadcrumbChain;
+use crate::criticality::{CriticalityConfig, CriticalityEngine, CriticalityResult};
+use crate::error::{Result, TripError};
+use crate::revocation::RevocationList;
+
+/// Verify an exported breadcrumb chain (a JSON array) and issue a
+/// certificate signed by `verifier`, unless `revocations` lists that
+/// key as revoked by now.
+pub fn verify_chain_json(
+    json: &[u8],
+    config: &CriticalityConfig,
+    verifier: &SigningKey,
+    revocations: &RevocationList,
+) -> Result<(CriticalityResult, PoHCertificate)> {
+    let json = std::str::from_utf8(json)
+        .map_err(|e| TripError::InvalidBreadcrumb(format!("export is not UTF-8: {e}")))?;
+    let chain = BreadcrumbChain::from_breadcrumbs(parse_breadcrumbs(json)?)?;
+    chain.verify_signatures()?;
+    // The certificate's chain_head_hash is the head's stored block hash
+    chain.verify_block_hashes()?;
+
+    let engine = CriticalityEngine::new(config.clone());
+    let result = engine.evaluate(&chain)?;
+
+    let policy = ValidityPolicy::default();
+    let certificate = PoHCertificate::from_criticality_result(
+        &result,
+        chain.identity.clone(),
+        hex::encode(verifier.verifying_key().to_bytes()),
+        chain.head_hash().to_string(),
+        policy.valid_seconds(result.trust_score),
+    )
+    .with_config_digest(engine.digest()?);
+    revocations.check(&certificate)?;
+    let certificate = certificate.sign(verifier)?;
+
+    Ok((result, certificate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::tests::{random_walk_breadcrumbs, sign_breadcrumbs};
+
+    fn signed_export(key: &SigningKey) -> Vec<u8> {
+        let mut breadcrumbs = random_walk_breadcrumbs(300, 5);
+        sign_breadcrumbs(&mut breadcrumbs, key);
+        serde_json::to_vec(&breadcrumbs).unwrap()
+    }
+
+    #[test]
+    fn test_verify_chain_json() {
+        let attester = SigningKey::from_bytes(&[7u8; 32]);
+        let verifier = SigningKey::from_bytes(&[9u8; 32]);
+        let config = CriticalityConfig::default();
+        let json = signed_export(&attester);
+        let none = RevocationList::new();
+
+        let (result, certificate) = verify_chain_json(&json, &config, &verifier, &none).unwrap();
+        assert_eq!(result.chain_length, 300);
+        assert_eq!(certificate.chain_length, 300);
+        assert_eq!(certificate.identity_key, hex::encode(attester.verifying_key().to_bytes()));
+        assert_eq!(certificate.config_digest, Some(config.digest().unwrap()));
+        certificate.verify().unwrap();
+
+        // Each stage reports its own error
+        assert!(matches!(
+            verify_chain_json(&[0xff, 0xfe], &config, &verifier, &none),
+            Err(TripError::InvalidBreadcrumb(_))
+        ));
+        assert!(matches!(
+            verify_chain_json(br#"{"not": "a chain"}"#, &config, &verifier, &none),
+            Err(TripError::InvalidBreadcrumb(_))
+        ));
+
+        let mut breadcrumbs = random_walk_breadcrumbs(300, 5);
+        sign_breadcrumbs(&mut breadcrumbs, &attester);
+        breadcrumbs.remove(10);
+        let gapped = serde_json::to_vec(&breadcrumbs).unwrap();
+        assert!(matches!(
+            verify_chain_json(&gapped, &config, &verifier, &none),
+            Err(TripError::ChainIntegrity(_))
+        ));
+
+        let mut breadcrumbs = random_walk_breadcrumbs(300, 5);
+        sign_breadcrumbs(&mut breadcrumbs, &attester);
+        breadcrumbs[42].signature = breadcrumbs[41].signature.clone();
+        let forged = serde_json::to_vec(&breadcrumbs).unwrap();
+        assert!(matches!(
+            verify_chain_json(&forged, &config, &verifier, &none),
+            Err(TripError::SignatureInvalid { index: 42 })
+        ));
+
+        let mut breadcrumbs = random_walk_breadcrumbs(40, 5);
+        sign_breadcrumbs(&mut breadcrumbs, &attester);
+        let short = serde_json::to_vec(&breadcrumbs).unwrap();
+        assert!(matches!(
+            verify_chain_json(&short, &config, &verifier, &none),
+            Err(TripError::InsufficientBreadcrumbs { got: 40, .. })
+        ));
+
+        // A revoked Verifier key issues nothing
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&hex::encode(verifier.verifying_key().to_bytes()), certificate.issued_at);
+        assert!(matches!(
+            verify_chain_json(&json, &config, &verifier, &revocations),
+            Err(TripError::CertificateError(msg)) if msg.contains("revoked")
+        ));
+    }
+}
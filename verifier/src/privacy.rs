@@ -0,0 +1,272 @@
+// trip-verifier/src/privacy.rs
+//
+// Privacy Report
+// ===============
+//
+// Documents, programmatically, what location-derived data the
+// Criticality Engine keeps. The engine reads H3 cells to build the
+// behavioral profile and displacement series, but only statistical
+// exponents, aggregate counts, the sorted step lengths of the Lévy
+// fit and per-breadcrumb anomaly scores survive into the
+// CriticalityResult. Cell identifiers, the cell histogram, transition
+// matrix and the ordered displacement series are all dropped before
+// the result is returned, and raw lat/lon is never seen at all
+// (breadcrumbs carry only quantized cells).
+//
+// The exceptions are an engine built `with_profile_export`, which
+// keeps the whole behavioral profile (visited cells included) on the
+// result, and `CriticalityEngine::explain`, whose output names each
+// breadcrumb's cell and time. The report lists both.
+//
+// Retained quantities are named by their path in the serialized
+// result, and `PrivacyReport::new` destructures `CriticalityResult`
+// without `..`, so a new result field doesn't compile until it is
+// classified here.
+//
+// Opt-in via CriticalityEngine::evaluate_with_privacy_report.
+
+use crate::criticality::{BreadcrumbContribution, CriticalityResult};
+use crate::hamiltonian::BehavioralProfile;
+
+/// How much a retained quantity reveals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// A single number for the whole chain
+    Scalar,
+    /// A summary over the whole chain (counts, spectra)
+    Aggregate,
+    /// One anomaly score per breadcrumb; no location content
+    PerBreadcrumbScore,
+    /// One timestamp per breadcrumb
+    PerBreadcrumbTime,
+    /// Identifiers of visited cells, without when they were visited
+    VisitedCells,
+    /// One location value per breadcrumb (cell or coordinates)
+    PerBreadcrumbLocation,
+}
+
+/// A location-derived quantity present in the result.
+#[derive(Debug, Clone)]
+pub struct RetainedQuantity {
+    /// Path of the quantity in the serialized output
+    pub name: &'static str,
+    pub granularity: Granularity,
+    /// Number of values retained
+    pub count: usize,
+}
+
+/// Inventory of retained versus discarded location-derived data.
+#[derive(Debug, Clone)]
+pub struct PrivacyReport {
+    /// Quantities present in the CriticalityResult
+    pub retained: Vec<RetainedQuantity>,
+    /// Intermediate data dropped inside the engine
+    pub discarded: Vec<&'static str>,
+}
+
+impl PrivacyReport {
+    /// Build the report for a result and the profile it was built from.
+    pub fn new(result: &CriticalityResult, profile: &BehavioralProfile) -> Self {
+        use Granularity::*;
+
+        // Exhaustive on purpose: fields bound to `_` carry nothing
+        // location-derived beyond the scalars listed below
+        let CriticalityResult {
+            psd,
+            levy,
+            hamiltonian,
+            replay: _,
+            path_psd,
+            trust_score: _,
+            confidence: _,
+            chain_length: _,
+            unique_cells: _,
+            radius_of_gyration_km: _,
+            diurnal_strength: _,
+            directional_persistence: _,
+            waiting_times: _,
+            intervals,
+            is_human: _,
+            is_inconclusive: _,
+            liveness: _,
+            accuracy: _,
+            verdict: _,
+            calibration: _,
+            profile: exported,
+        } = result;
+
+        let mut retained = vec![
+            RetainedQuantity { name: "psd.alpha", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "psd.spectrum", granularity: Aggregate, count: psd.spectrum.len() },
+            RetainedQuantity { name: "levy.beta", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "levy.kappa_km", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "levy.samples", granularity: Aggregate, count: levy.samples.len() },
+            RetainedQuantity { name: "hamiltonian.scores", granularity: PerBreadcrumbScore, count: hamiltonian.scores.len() },
+            RetainedQuantity { name: "replay.replay_periodicity", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "unique_cells", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "radius_of_gyration_km", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "directional_persistence", granularity: Scalar, count: 1 },
+            RetainedQuantity { name: "cell_histogram_size", granularity: Scalar, count: profile.cell_histogram.len() },
+            RetainedQuantity { name: "anchor_count", granularity: Scalar, count: profile.anchor_cells.len() },
+        ];
+
+        let mut discarded = vec![
+            "location_cells",
+            "cell_histogram",
+            "anchor_cells",
+            "transition_matrix",
+            "hourly_profile",
+            "displacement_series",
+            "interval_series",
+            "path_series",
+        ];
+
+        if let Some(path_psd) = path_psd {
+            retained.push(RetainedQuantity {
+                name: "path_psd.psd.spectrum",
+                granularity: Aggregate,
+                count: path_psd.psd.spectrum.len(),
+            });
+        }
+
+        if intervals.is_some() {
+            // Bootstrap bounds: two numbers each
+            retained.push(RetainedQuantity { name: "intervals.alpha", granularity: Scalar, count: 2 });
+            retained.push(RetainedQuantity { name: "intervals.beta", granularity: Scalar, count: 2 });
+        }
+
+        if let Some(exported) = exported {
+            retained.push(RetainedQuantity {
+                name: "profile",
+                granularity: VisitedCells,
+                count: exported.cell_histogram.len(),
+            });
+            discarded.retain(|name| {
+                !matches!(*name, "cell_histogram" | "anchor_cells" | "transition_matrix" | "hourly_profile")
+            });
+        }
+
+        Self { retained, discarded }
+    }
+
+    /// Report for the output of `CriticalityEngine::explain`, which
+    /// names the cell and time of each breadcrumb it ranks.
+    pub fn for_explanation(contributions: &[BreadcrumbContribution]) -> Self {
+        use Granularity::*;
+
+        let n = contributions.len();
+        Self {
+            retained: vec![
+                RetainedQuantity { name: "cell", granularity: PerBreadcrumbLocation, count: n },
+                RetainedQuantity { name: "timestamp", granularity: PerBreadcrumbTime, count: n },
+                RetainedQuantity { name: "contribution", granularity: PerBreadcrumbScore, count: n },
+            ],
+            discarded: Vec::new(),
+        }
+    }
+
+    /// Does any retained quantity reveal where the identity has been?
+    pub fn contains_raw_location(&self) -> bool {
+        self.retained.iter()
+            .any(|q| matches!(q.granularity, Granularity::PerBreadcrumbLocation | Granularity::VisitedCells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::{BreadcrumbChain, tests::random_walk_breadcrumbs};
+    use crate::criticality::CriticalityEngine;
+    use serde_json::Value;
+
+    #[test]
+    fn test_report_lists_only_aggregates() {
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 11)).unwrap();
+        let (result, report) = CriticalityEngine::with_defaults()
+            .evaluate_with_privacy_report(&chain)
+            .unwrap();
+
+        assert!(!report.contains_raw_location());
+        for q in &report.retained {
+            assert_ne!(q.granularity, Granularity::PerBreadcrumbLocation, "{}", q.name);
+        }
+
+        let histogram = report.retained.iter()
+            .find(|q| q.name == "cell_histogram_size")
+            .unwrap();
+        assert_eq!(histogram.count, result.unique_cells);
+        assert!(report.retained.iter().any(|q| q.name == "anchor_count"));
+        assert!(report.discarded.contains(&"location_cells"));
+        assert!(report.discarded.contains(&"transition_matrix"));
+    }
+
+    /// Every array under an object in `value`, by dotted path, and
+    /// every string and object key.
+    fn walk(value: &Value, path: &str, arrays: &mut Vec<(String, usize)>, strings: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let child_path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                    if let Value::Array(items) = child {
+                        arrays.push((child_path.clone(), items.len()));
+                    }
+                    strings.push(key.clone());
+                    walk(child, &child_path, arrays, strings);
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| walk(item, path, arrays, strings)),
+            Value::String(s) => strings.push(s.clone()),
+            _ => {}
+        }
+    }
+
+    /// Check a report against what the serialized output really holds.
+    fn assert_report_matches(report: &PrivacyReport, serialized: &Value, chain: &BreadcrumbChain) {
+        let (mut arrays, mut strings) = (Vec::new(), Vec::new());
+        walk(serialized, "", &mut arrays, &mut strings);
+
+        // Every per-item quantity is listed, with the right count
+        for (path, len) in &arrays {
+            let covering = report.retained.iter()
+                .find(|q| q.name == path || path.starts_with(&format!("{}.", q.name)))
+                .unwrap_or_else(|| panic!("{path} ({len} values) missing from the report"));
+            if covering.name == path {
+                assert_eq!(covering.count, *len, "{path}");
+            }
+        }
+
+        // Cells appear in the output exactly when the report says so
+        let leaks = strings.iter()
+            .any(|s| chain.breadcrumbs.iter().any(|b| b.location_cell == *s));
+        assert_eq!(leaks, report.contains_raw_location());
+    }
+
+    #[test]
+    fn test_report_matches_serialized_result() {
+        use crate::criticality::Fidelity;
+
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 11)).unwrap();
+        let mut thorough = CriticalityEngine::with_defaults().config().clone();
+        thorough.fidelity = Fidelity::Thorough;
+        let engines = [
+            CriticalityEngine::with_defaults(),
+            CriticalityEngine::new(thorough),
+            CriticalityEngine::with_defaults().with_profile_export(),
+        ];
+        for engine in &engines {
+            let (result, report) = engine.evaluate_with_privacy_report(&chain).unwrap();
+            let serialized = serde_json::to_value(&result).unwrap();
+            assert_report_matches(&report, &serialized, &chain);
+        }
+
+        // Profile export keeps the visited cells
+        let (_, exported) = engines[2].evaluate_with_privacy_report(&chain).unwrap();
+        assert!(exported.contains_raw_location());
+
+        // explain names cells
+        let contributions = engines[0].explain(&chain, 5).unwrap();
+        let serialized = serde_json::to_string(&contributions).unwrap();
+        assert!(serialized.contains(&contributions[0].cell));
+        assert!(PrivacyReport::for_explanation(&contributions).contains_raw_location());
+    }
+}
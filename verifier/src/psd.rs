@@ -16,18 +16,32 @@
 // 1. Extract displacement time series from breadcrumb chain
 // 2. Apply Welch's method (segmented, windowed FFT)
 // 3. Compute PSD via |FFT|²
-// 4. Fit α via linear regression in log-log space
+// 4. Fit α via linear regression in log-log space (optionally on
+//    log-spaced bands, see `PsdFit`)
+//
+// Displacement magnitudes carry no direction, so a replay can keep a
+// human-like magnitude sequence while retracing the same cells.
+// `path_cross_check` runs the same analysis on the position along the
+// path and compares the two exponents.
+//
+// `StreamingPsd` takes breadcrumbs one at a time, for an Attester
+// that tracks α on-device. Its state serializes, so it can be
+// checkpointed across app restarts and resumed with the same result
+// as an uninterrupted stream.
 //
 // References:
 // - Parisi (2021), Nobel Prize — scale-free correlations
 // - Maczák et al. (2024) — spectral analysis of GPS trajectories
 // - Vadai et al. (2019) — fluctuations in daily motion
 
+use rand::Rng;
 use rustfft::{FftPlanner, num_complex::Complex};
-use crate::error::{TripError, Result};
+use serde::{Deserialize, Serialize};
+use crate::breadcrumb::{Breadcrumb, compute_displacements};
+use crate::error::{TripError, Result, ensure_finite};
 
 /// Result of PSD analysis on a displacement time series.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PsdResult {
     /// The PSD scaling exponent α.
     /// Human range: [0.30, 0.80] (pink noise)
@@ -49,8 +63,79 @@ pub struct PsdResult {
     pub classification: PsdClassification,
 }
 
+impl PsdResult {
+    /// Placeholder for an analysis that was skipped.
+    pub fn not_measured() -> Self {
+        Self {
+            alpha: 0.0,
+            r_squared: 0.0,
+            num_bins: 0,
+            spectrum: Vec::new(),
+            classification: PsdClassification::NotMeasured,
+        }
+    }
+}
+
+/// How to place samples on the time axis before the FFT.
+///
+/// Welch's method assumes uniform sampling, but breadcrumb intervals
+/// range from 10 minutes to 24 hours. Treating them as uniform warps
+/// the frequency axis: bursts of short intervals are stretched and
+/// long gaps compressed, which leaks power across bins and biases α.
+/// Resampling onto a uniform grid
+/// using the real timestamps removes that distortion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Resampling {
+    /// Treat samples as evenly spaced at the mean interval.
+    #[default]
+    None,
+    /// Linear interpolation between neighbouring samples.
+    Linear,
+    /// Zero-order hold: each grid point takes the last observed value.
+    ZeroOrderHold,
+}
+
+/// How the log-log regression weights frequency bins.
+///
+/// The log of a Welch estimate averaged over K segments has the same
+/// variance in every bin, so inverse-variance weights for the raw bins
+/// are all equal: `Unweighted` is the minimum-variance fit. But the
+/// bins are evenly spaced in f, so in log f the top decade holds 90%
+/// of them and the ordinary fit is decided almost entirely by the high
+/// frequencies. `LogBinned` averages the bins into bands of equal
+/// width in log f (`LOG_BAND_RATIO`) and fits the band means with
+/// equal weights, so every octave counts the same, as a scaling law
+/// should hold over the whole range.
+///
+/// On pure 1/f^α noise both are unbiased; `LogBinned` rests on fewer
+/// points, so its α is somewhat noisier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PsdFit {
+    /// Ordinary least squares, every bin weighted equally.
+    #[default]
+    Unweighted,
+    /// Least squares on log-spaced band means, every band weighted
+    /// equally.
+    LogBinned,
+}
+
+/// Frequency ratio spanned by one `PsdFit::LogBinned` band.
+pub const LOG_BAND_RATIO: f64 = 1.1;
+
+/// Upper bound on resampled length, as a multiple of the input length.
+const MAX_RESAMPLE_FACTOR: usize = 4;
+
+/// Configuration for PSD analysis.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PsdConfig {
+    /// Resampling applied to irregular series before Welch's method.
+    pub resampling: Resampling,
+    /// Weighting of the log-log fit.
+    pub fit: PsdFit,
+}
+
 /// Classification of the PSD scaling exponent per TRIP spec Table 3.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum PsdClassification {
     /// α < 0.10 — White noise (bots, random walk)
     WhiteNoise,
@@ -62,6 +147,8 @@ pub enum PsdClassification {
     StrongCorrelation,
     /// α > 1.50 — Brown noise (replay, scripted)
     BrownNoise,
+    /// Not computed (stationary chain, see `StationaryPolicy`)
+    NotMeasured,
 }
 
 impl PsdClassification {
@@ -86,6 +173,7 @@ impl PsdClassification {
             Self::Biological => "biological",
             Self::StrongCorrelation => "strong_correlation",
             Self::BrownNoise => "brown_noise",
+            Self::NotMeasured => "not_measured",
         }
     }
 }
@@ -105,6 +193,11 @@ impl PsdClassification {
 /// # Returns
 /// `PsdResult` with α, R², and diagnostic info.
 pub fn compute_psd(displacements: &[f64], dt_mean: f64) -> Result<PsdResult> {
+    compute_psd_with_fit(displacements, dt_mean, PsdFit::Unweighted)
+}
+
+/// Like `compute_psd`, with a choice of log-log fit weighting.
+pub fn compute_psd_with_fit(displacements: &[f64], dt_mean: f64, fit: PsdFit) -> Result<PsdResult> {
     let n = displacements.len();
 
     if n < 32 {
@@ -112,6 +205,7 @@ pub fn compute_psd(displacements: &[f64], dt_mean: f64) -> Result<PsdResult> {
             format!("Need at least 32 displacements, got {n}")
         ));
     }
+    ensure_finite("PSD input", displacements)?;
 
     // --- Step 1: Remove mean (center the signal) ---
     let mean = displacements.iter().sum::<f64>() / n as f64;
@@ -185,7 +279,13 @@ pub fn compute_psd(displacements: &[f64], dt_mean: f64) -> Result<PsdResult> {
     let log_f: Vec<f64> = spectrum.iter().map(|&(f, _)| f.ln()).collect();
     let log_p: Vec<f64> = spectrum.iter().map(|&(_, p)| p.ln()).collect();
 
-    let (slope, _intercept, r_squared) = linear_regression(&log_f, &log_p);
+    let (slope, _intercept, r_squared) = match fit {
+        PsdFit::Unweighted => linear_regression(&log_f, &log_p),
+        PsdFit::LogBinned => {
+            let (band_f, band_p) = log_bands(&log_f, &log_p);
+            linear_regression(&band_f, &band_p)
+        }
+    };
     let alpha = -slope; // PSD ∝ f^(-α), so slope = -α
 
     let classification = PsdClassification::from_alpha(alpha);
@@ -199,11 +299,101 @@ pub fn compute_psd(displacements: &[f64], dt_mean: f64) -> Result<PsdResult> {
     })
 }
 
+/// α of `iterations` bootstrap refits of `result`'s log-log regression,
+/// resampling its frequency bins with replacement (for `LogBinned`,
+/// weighted 1/f, each octave's share in the band fit), for a
+/// percentile interval. Reflects the scatter of
+/// the spectrum about the fitted line, not the sampling error of the
+/// spectrum itself. Empty when `result` has too few bins.
+pub fn bootstrap_alpha(
+    result: &PsdResult,
+    fit: PsdFit,
+    iterations: usize,
+    rng: &mut impl Rng,
+) -> Vec<f64> {
+    let bins = &result.spectrum;
+    if bins.len() < 4 {
+        return Vec::new();
+    }
+    let (mut log_f, mut log_p, mut weights) = (Vec::new(), Vec::new(), Vec::new());
+    (0..iterations)
+        .filter_map(|_| {
+            log_f.clear();
+            log_p.clear();
+            weights.clear();
+            for _ in 0..bins.len() {
+                let (f, p) = bins[rng.gen_range(0..bins.len())];
+                log_f.push(f.ln());
+                log_p.push(p.ln());
+                // Band widths grow with f, so a band's share per bin is ∝ 1/f
+                weights.push(match fit {
+                    PsdFit::Unweighted => 1.0,
+                    PsdFit::LogBinned => 1.0 / f,
+                });
+            }
+            let (slope, _, _) = weighted_linear_regression(&log_f, &log_p, &weights);
+            slope.is_finite().then_some(-slope)
+        })
+        .collect()
+}
+
+/// Smallest α(path) − α(displacement) that passes `path_cross_check`.
+pub const DEFAULT_MIN_PATH_ALPHA_EXCESS: f64 = 0.5;
+
+/// PSD of the path embedding, checked against the displacement PSD.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathPsdResult {
+    /// PSD of the position along the path
+    /// (`BreadcrumbChain::path_series`)
+    pub psd: PsdResult,
+
+    /// α of the path minus α of the displacement magnitudes
+    pub alpha_excess: f64,
+
+    /// Excess below the required minimum: the path does not wander
+    /// as far as its displacements imply, i.e. it retraces itself.
+    pub suspicious: bool,
+}
+
+/// Cross-check the displacement PSD against the PSD of the path.
+///
+/// A moving trajectory's position is the running sum of its steps,
+/// and summation steepens a spectrum by up to 2 (a 1/f² factor), less
+/// when headings are uncorrelated. Organic paths therefore sit well
+/// above their displacement α. A path that shuttles over the same
+/// cells stays bounded: its position spectrum is no steeper than the
+/// displacements', whatever their magnitudes.
+pub fn path_cross_check(
+    path_km: &[f64],
+    interval_seconds: &[f64],
+    displacement_alpha: f64,
+    config: &PsdConfig,
+    min_alpha_excess: f64,
+) -> Result<PathPsdResult> {
+    let psd = compute_psd_from_chain_with_config(path_km, interval_seconds, config)?;
+    let alpha_excess = psd.alpha - displacement_alpha;
+    Ok(PathPsdResult {
+        psd,
+        alpha_excess,
+        suspicious: alpha_excess < min_alpha_excess,
+    })
+}
+
 /// Compute PSD from a BreadcrumbChain's displacement series.
 /// Convenience function that handles the displacement extraction.
 pub fn compute_psd_from_chain(
     displacement_km: &[f64],
     interval_seconds: &[f64],
+) -> Result<PsdResult> {
+    compute_psd_from_chain_with_config(displacement_km, interval_seconds, &PsdConfig::default())
+}
+
+/// Compute PSD from a chain's displacement series, resampling onto a
+/// uniform time grid first if the config asks for it.
+pub fn compute_psd_from_chain_with_config(
+    displacement_km: &[f64],
+    interval_seconds: &[f64],
+    config: &PsdConfig,
 ) -> Result<PsdResult> {
     if displacement_km.len() != interval_seconds.len() {
         return Err(TripError::PsdError(
@@ -212,13 +402,204 @@ pub fn compute_psd_from_chain(
     }
 
     let dt_mean = interval_seconds.iter().sum::<f64>() / interval_seconds.len() as f64;
-    compute_psd(displacement_km, dt_mean)
+
+    if config.resampling == Resampling::None {
+        return compute_psd_with_fit(displacement_km, dt_mean, config.fit);
+    }
+
+    // Each displacement is observed at the end of its interval
+    let times: Vec<f64> = interval_seconds.iter()
+        .scan(0.0, |t, &dt| { *t += dt; Some(*t) })
+        .collect();
+    let (resampled, dt_grid) = resample_uniform(displacement_km, &times, config.resampling);
+    compute_psd_with_fit(&resampled, dt_grid, config.fit)
+}
+
+/// PSD of a breadcrumb stream, built one breadcrumb at a time.
+///
+/// Welch's method centers on the mean and sizes its segments from the
+/// whole series, so the state is the displacement and interval series
+/// so far, plus the last breadcrumb to measure the next one from. The
+/// state serializes: a checkpoint restored and fed the rest of the
+/// stream gives exactly the α of an uninterrupted stream.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamingPsd {
+    config: PsdConfig,
+    last: Option<Breadcrumb>,
+    displacement_km: Vec<f64>,
+    interval_seconds: Vec<f64>,
+}
+
+impl StreamingPsd {
+    pub fn new(config: PsdConfig) -> Self {
+        Self { config, ..Self::default() }
+    }
+
+    /// Add the next breadcrumb of the stream. Errors, leaving the
+    /// state unchanged, on an invalid cell or a non-increasing
+    /// timestamp.
+    pub fn push(&mut self, breadcrumb: &Breadcrumb) -> Result<()> {
+        if let Some(last) = self.last.take() {
+            let pair = [last, breadcrumb.clone()];
+            match compute_displacements(&pair) {
+                Ok(d) => {
+                    self.displacement_km.push(d[0].distance_km);
+                    self.interval_seconds.push(d[0].dt_seconds);
+                }
+                Err(e) => {
+                    let [last, _] = pair;
+                    self.last = Some(last);
+                    return Err(e);
+                }
+            }
+        }
+        self.last = Some(breadcrumb.clone());
+        Ok(())
+    }
+
+    /// Displacements seen so far
+    pub fn len(&self) -> usize {
+        self.displacement_km.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.displacement_km.is_empty()
+    }
+
+    /// PSD of the stream so far (see `compute_psd_from_chain_with_config`).
+    pub fn result(&self) -> Result<PsdResult> {
+        compute_psd_from_chain_with_config(&self.displacement_km, &self.interval_seconds, &self.config)
+    }
+}
+
+/// Resample `values` observed at increasing `times` onto a uniform grid.
+///
+/// The grid spacing is the median observed interval, so the dominant
+/// sampling cadence is kept as-is while bursts of extra samples are
+/// thinned and long gaps are filled in. Returns the resampled series
+/// and the grid spacing (seconds).
+pub fn resample_uniform(values: &[f64], times: &[f64], method: Resampling) -> (Vec<f64>, f64) {
+    let n = values.len().min(times.len());
+    if n < 2 {
+        return (values[..n].to_vec(), 1.0);
+    }
+
+    let t0 = times[0];
+    let span = times[n - 1] - t0;
+    if method == Resampling::None || span <= 0.0 {
+        return (values[..n].to_vec(), span.max(0.0) / (n - 1) as f64);
+    }
+
+    let mut intervals: Vec<f64> = times[..n].windows(2).map(|w| w[1] - w[0]).collect();
+    intervals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = intervals[intervals.len() / 2];
+
+    // Bound the grid so a handful of near-zero intervals can't blow it up
+    let max_points = n * MAX_RESAMPLE_FACTOR;
+    let natural = if median > 0.0 { (span / median).floor() as usize + 1 } else { usize::MAX };
+    let (m, dt) = if natural <= max_points {
+        (natural, median)
+    } else {
+        (max_points, span / (max_points - 1) as f64)
+    };
+
+    let mut out = Vec::with_capacity(m);
+    let mut j = 0;
+    for k in 0..m {
+        let t = t0 + dt * k as f64;
+        // Advance to the last sample at or before t
+        while j + 1 < n && times[j + 1] <= t {
+            j += 1;
+        }
+        let v = match method {
+            Resampling::Linear if j + 1 < n && times[j + 1] > times[j] => {
+                let frac = (t - times[j]) / (times[j + 1] - times[j]);
+                values[j] + frac * (values[j + 1] - values[j])
+            }
+            _ => values[j],
+        };
+        out.push(v);
+    }
+
+    (out, dt)
+}
+
+/// Minimum series length for which α is estimated to within ±ε at
+/// 95% confidence by `compute_psd`.
+///
+/// Statistical assumptions:
+/// - The displacement series is a stationary Gaussian process with a
+///   pure power-law spectrum, so log-log residuals are estimation
+///   noise only (no model misfit).
+/// - Each Welch-averaged bin is a scaled χ²₂ₖ/2K over K segments, so
+///   ln(PSD) has variance ψ′(K) (trigamma), inflated by 1 + 2ρ²(K−1)/K
+///   for Hann windows at 50% overlap (ρ ≈ 0.167).
+/// - The OLS slope has Var(α̂) = σ²_lnP / Σ(ln fᵢ − mean)², inflated
+///   by 1 + 2(ρ₁² + ρ₂²) ≈ 1.94 for the Hann window's correlation
+///   between neighbouring frequency bins (ρ₁ = 2/3, ρ₂ = 1/6). This
+///   makes the estimate slightly conservative for short series.
+/// - The 95% interval uses the normal approximation (±1.96·SE).
+///
+/// Segment length follows `optimal_segment_length`, which is why the
+/// answer is searched per segment-length band rather than solved
+/// in closed form. Returns `usize::MAX` for non-positive ε.
+pub fn samples_for_alpha_precision(target_epsilon: f64) -> usize {
+    const Z_95: f64 = 1.96;
+    const HANN_OVERLAP_RHO2: f64 = 0.167 * 0.167;
+    const HANN_BIN_INFLATION: f64 = 1.0 + 2.0 * (4.0 / 9.0 + 1.0 / 36.0);
+
+    if !(target_epsilon > 0.0 && target_epsilon.is_finite()) {
+        return usize::MAX;
+    }
+    let max_variance = (target_epsilon / Z_95).powi(2);
+
+    // n ∈ [band_start, 4L) maps to segment length L
+    let mut segment_len = 64usize;
+    let mut band_start = 64usize;
+    while segment_len < usize::MAX / 8 {
+        let band_end = 4 * segment_len;
+        let bins = segment_len / 2;
+        let log_f_spread = log_index_spread(bins);
+
+        // Smallest segment count K meeting the target within this band
+        let step = segment_len / 2;
+        let mut k = (band_start.saturating_sub(segment_len)) / step + 1;
+        loop {
+            let n = segment_len + (k - 1) * step;
+            if n >= band_end {
+                break;
+            }
+            let k_f = k as f64;
+            let var_log_p = trigamma(k) * (1.0 + 2.0 * HANN_OVERLAP_RHO2 * (k_f - 1.0) / k_f);
+            if HANN_BIN_INFLATION * var_log_p / log_f_spread <= max_variance {
+                return n.max(band_start);
+            }
+            k += 1;
+        }
+
+        band_start = band_end;
+        segment_len *= 2;
+    }
+    usize::MAX
 }
 
 // ========================================================================
 // Internal helpers
 // ========================================================================
 
+/// Σ (ln i − mean)² over frequency bins i = 1..=m
+fn log_index_spread(m: usize) -> f64 {
+    let logs: Vec<f64> = (1..=m).map(|i| (i as f64).ln()).collect();
+    let mean = logs.iter().sum::<f64>() / m as f64;
+    logs.iter().map(|&l| (l - mean).powi(2)).sum()
+}
+
+/// Trigamma ψ′(k) for positive integer k: π²/6 − Σ_{j<k} 1/j²
+fn trigamma(k: usize) -> f64 {
+    let partial: f64 = (1..k).map(|j| 1.0 / (j as f64 * j as f64)).sum();
+    std::f64::consts::PI.powi(2) / 6.0 - partial
+}
+
 /// Hann window: w(n) = 0.5 * (1 - cos(2π·n / (N-1)))
 fn hann(size: usize) -> Vec<f64> {
     let n = size as f64;
@@ -240,12 +621,34 @@ fn optimal_segment_length(total_samples: usize) -> usize {
 /// Simple linear regression: y = slope·x + intercept
 /// Returns (slope, intercept, r_squared)
 fn linear_regression(x: &[f64], y: &[f64]) -> (f64, f64, f64) {
-    let n = x.len() as f64;
-    let sum_x: f64 = x.iter().sum();
-    let sum_y: f64 = y.iter().sum();
-    let sum_xy: f64 = x.iter().zip(y.iter()).map(|(a, b)| a * b).sum();
-    let sum_x2: f64 = x.iter().map(|a| a * a).sum();
-    let sum_y2: f64 = y.iter().map(|a| a * a).sum();
+    weighted_linear_regression(x, y, &vec![1.0; x.len()])
+}
+
+/// Means of (log f, log P) over consecutive bands spanning a frequency
+/// ratio of `LOG_BAND_RATIO`; `log_f` must be ascending.
+fn log_bands(log_f: &[f64], log_p: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let (mut band_f, mut band_p) = (Vec::new(), Vec::new());
+    let mut start = 0;
+    while start < log_f.len() {
+        let edge = log_f[start] + LOG_BAND_RATIO.ln();
+        let end = start + log_f[start..].iter().take_while(|&&f| f < edge).count();
+        let m = (end - start) as f64;
+        band_f.push(log_f[start..end].iter().sum::<f64>() / m);
+        band_p.push(log_p[start..end].iter().sum::<f64>() / m);
+        start = end;
+    }
+    (band_f, band_p)
+}
+
+/// Weighted least squares: minimizes Σ wᵢ(yᵢ − slope·xᵢ − intercept)².
+/// Returns (slope, intercept, weighted r_squared)
+fn weighted_linear_regression(x: &[f64], y: &[f64], w: &[f64]) -> (f64, f64, f64) {
+    let n: f64 = w.iter().sum();
+    let sum_x: f64 = x.iter().zip(w).map(|(a, wi)| wi * a).sum();
+    let sum_y: f64 = y.iter().zip(w).map(|(b, wi)| wi * b).sum();
+    let sum_xy: f64 = x.iter().zip(y.iter()).zip(w).map(|((a, b), wi)| wi * a * b).sum();
+    let sum_x2: f64 = x.iter().zip(w).map(|(a, wi)| wi * a * a).sum();
+    let sum_y2: f64 = y.iter().zip(w).map(|(b, wi)| wi * b * b).sum();
 
     let denom = n * sum_x2 - sum_x * sum_x;
     if denom.abs() < f64::EPSILON {
@@ -258,10 +661,10 @@ fn linear_regression(x: &[f64], y: &[f64]) -> (f64, f64, f64) {
     // R² = 1 - SS_res / SS_tot
     let y_mean = sum_y / n;
     let ss_tot = sum_y2 - n * y_mean * y_mean;
-    let ss_res: f64 = x.iter().zip(y.iter())
-        .map(|(&xi, &yi)| {
+    let ss_res: f64 = x.iter().zip(y.iter()).zip(w)
+        .map(|((&xi, &yi), &wi)| {
             let pred = slope * xi + intercept;
-            (yi - pred).powi(2)
+            wi * (yi - pred).powi(2)
         })
         .sum();
 
@@ -314,6 +717,53 @@ mod tests {
         assert_eq!(result.classification, PsdClassification::BrownNoise);
     }
 
+    /// 1/f^α noise: a window of a 16× longer spectrally synthesized
+    /// series, so (as in real data) there is power below the lowest
+    /// Welch bin.
+    fn power_law_noise(n: usize, alpha: f64, seed: u64) -> Vec<f64> {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let total = 16 * n;
+        let mut buffer = vec![Complex::new(0.0, 0.0); total];
+        for k in 1..total / 2 {
+            let amplitude = (k as f64).powf(-alpha / 2.0) * rng.gen_range(0.5..1.5);
+            buffer[k] = Complex::from_polar(amplitude, rng.gen_range(0.0..std::f64::consts::TAU));
+            buffer[total - k] = buffer[k].conj();
+        }
+        FftPlanner::<f64>::new().plan_fft_inverse(total).process(&mut buffer);
+        buffer[..n].iter().map(|c| c.re).collect()
+    }
+
+    #[test]
+    fn test_log_binned_fit_on_pure_power_law() {
+        let (n, runs) = (1024, 50);
+        for alpha in [0.3, 0.6, 1.0] {
+            for fit in [PsdFit::Unweighted, PsdFit::LogBinned] {
+                let estimates: Vec<f64> = (0..runs)
+                    .map(|seed| compute_psd_with_fit(&power_law_noise(n, alpha, seed), 300.0, fit).unwrap().alpha)
+                    .collect();
+                let bias = estimates.iter().sum::<f64>() / runs as f64 - alpha;
+                let rmse = (estimates.iter().map(|a| (a - alpha).powi(2)).sum::<f64>() / runs as f64).sqrt();
+                assert!(bias.abs() < 0.03, "{fit:?} α={alpha}: bias {bias}");
+                assert!(rmse < 0.1, "{fit:?} α={alpha}: rmse {rmse}");
+            }
+        }
+
+        // Bands span LOG_BAND_RATIO: singletons at the low end, wider above
+        let log_f: Vec<f64> = (1..=64).map(|i| (i as f64).ln()).collect();
+        let (band_f, band_p) = log_bands(&log_f, &log_f);
+        assert_eq!(&band_f[..3], &log_f[..3]);
+        assert!(band_f.len() < 30 && band_f.len() == band_p.len());
+        assert!(band_f.windows(2).all(|w| w[1] > w[0]));
+
+        // Uniform weights reproduce the ordinary fit
+        let x = [0.0, 1.0, 2.0, 3.0];
+        let y = [1.0, 2.5, 2.9, 4.2];
+        let (weighted, ordinary) = (weighted_linear_regression(&x, &y, &[2.0; 4]).0, linear_regression(&x, &y).0);
+        assert!((weighted - ordinary).abs() < 1e-12);
+    }
+
     /// Regression fit quality
     #[test]
     fn test_linear_regression_perfect() {
@@ -335,6 +785,67 @@ mod tests {
         assert!((w[32] - 1.0).abs() < 0.01); // peak at center
     }
 
+    /// Resampling onto the dominant cadence recovers α of the
+    /// underlying uniform series; treating the samples as uniform does not.
+    #[test]
+    fn test_resampling_irregular_series() {
+        use rand::{SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // Ground truth: brown noise sampled every 600 s
+        let mut truth = vec![0.0f64; 512];
+        for i in 1..truth.len() {
+            truth[i] = truth[i - 1] + rng.gen_range(-1.0..1.0);
+        }
+        let alpha_truth = compute_psd(&truth, 600.0).unwrap().alpha;
+
+        // Irregular: same samples plus bursts of extra readings 60 s
+        // after roughly one in six of them
+        let mut values = Vec::new();
+        let mut intervals = Vec::new();
+        let mut last_t = 0.0;
+        for (i, &v) in truth.iter().enumerate() {
+            let t = 600.0 * (i + 1) as f64;
+            intervals.push(t - last_t);
+            values.push(v);
+            last_t = t;
+            if i + 1 < truth.len() && rng.gen_range(0..6) == 0 {
+                intervals.push(60.0);
+                values.push(v + rng.gen_range(-5.0..5.0));
+                last_t = t + 60.0;
+            }
+        }
+
+        let naive = compute_psd_from_chain_with_config(
+            &values, &intervals, &PsdConfig::default(),
+        ).unwrap().alpha;
+
+        for resampling in [Resampling::Linear, Resampling::ZeroOrderHold] {
+            let config = PsdConfig { resampling, ..Default::default() };
+            let alpha = compute_psd_from_chain_with_config(&values, &intervals, &config)
+                .unwrap()
+                .alpha;
+            assert!(
+                (alpha - alpha_truth).abs() < 1e-9,
+                "{resampling:?}: expected α={alpha_truth}, got {alpha}"
+            );
+            assert!((naive - alpha_truth).abs() > (alpha - alpha_truth).abs() + 0.1,
+                "naive α={naive} should be biased away from {alpha_truth}");
+        }
+    }
+
+    /// Tighter precision needs more samples
+    #[test]
+    fn test_samples_for_alpha_precision_monotonic() {
+        let eps = [0.5, 0.3, 0.2, 0.1, 0.05, 0.02];
+        let needed: Vec<usize> = eps.iter().map(|&e| samples_for_alpha_precision(e)).collect();
+        for pair in needed.windows(2) {
+            assert!(pair[1] > pair[0], "{needed:?}");
+        }
+        assert!(needed[0] >= 64);
+        assert_eq!(samples_for_alpha_precision(0.0), usize::MAX);
+    }
+
     /// Minimum sample check
     #[test]
     fn test_insufficient_samples() {
@@ -342,4 +853,48 @@ mod tests {
         let result = compute_psd(&signal, 300.0);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_streaming_checkpoint_resume() {
+        use crate::chain::BreadcrumbChain;
+        use crate::chain::tests::random_walk_breadcrumbs;
+
+        let breadcrumbs = random_walk_breadcrumbs(300, 4);
+        let config = PsdConfig { resampling: Resampling::Linear, ..PsdConfig::default() };
+
+        let mut uninterrupted = StreamingPsd::new(config.clone());
+        for b in &breadcrumbs {
+            uninterrupted.push(b).unwrap();
+        }
+
+        // Checkpoint after 120 breadcrumbs, restore, and continue
+        let mut first = StreamingPsd::new(config.clone());
+        for b in &breadcrumbs[..120] {
+            first.push(b).unwrap();
+        }
+        let checkpoint = serde_json::to_string(&first).unwrap();
+        drop(first);
+        let mut resumed: StreamingPsd = serde_json::from_str(&checkpoint).unwrap();
+        for b in &breadcrumbs[120..] {
+            resumed.push(b).unwrap();
+        }
+
+        assert_eq!(resumed.len(), 299);
+        let (a, b) = (resumed.result().unwrap(), uninterrupted.result().unwrap());
+        assert_eq!(a.alpha.to_bits(), b.alpha.to_bits());
+        assert_eq!(a.r_squared.to_bits(), b.r_squared.to_bits());
+
+        // Same as the batch analysis of the whole chain, whose "simd"
+        // haversine may differ from the scalar one in the last bits
+        let chain = BreadcrumbChain::from_breadcrumbs(breadcrumbs.clone()).unwrap();
+        let batch = compute_psd_from_chain_with_config(
+            &chain.displacement_series(), &chain.interval_series(), &config,
+        ).unwrap();
+        let tolerance = if cfg!(feature = "simd") { 1e-9 } else { 0.0 };
+        assert!((b.alpha - batch.alpha).abs() <= tolerance, "{} vs {}", b.alpha, batch.alpha);
+
+        // A breadcrumb out of order is rejected without losing state
+        assert!(resumed.push(&breadcrumbs[10]).is_err());
+        assert_eq!(resumed.len(), 299);
+    }
 }
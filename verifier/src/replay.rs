@@ -0,0 +1,237 @@
+// trip-verifier/src/replay.rs
+//
+// Replay Periodicity Detection
+// =============================
+//
+// GPS replay attacks often loop a recorded track. Noise injection
+// can push the PSD exponent back into the pink range, but the
+// spatial path still repeats near-exactly at a fixed lag.
+//
+// We autocorrelate the sequence of cell-to-cell moves: for each
+// lag L, count how many moves at position i are repeated at
+// position i + L. Two moves match when both their start and end lie
+// within `REPLAY_TOLERANCE_KM` of each other, so a replay whose fixes
+// were jittered into neighbouring cells still lines up. Moves shorter
+// than the tolerance are ignored: staying home is not evidence of a
+// replay, and jitter alone can't be told apart from it.
+//
+// A human revisits places, but never with the same sequence of
+// moves at a constant sample offset across the whole chain.
+//
+// Moves are hashed into a grid of tolerance-sized buckets by start
+// and end point, so only moves in neighbouring buckets are compared.
+// At most `MAX_REPLAY_CANDIDATES` later moves per bucket are taken,
+// which keeps a chain repeating one move thousands of times linear;
+// a looped replay already shows at its first few periods.
+
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::breadcrumb::{Breadcrumb, h3_cell_to_latlon};
+
+/// Shortest lag considered (in breadcrumbs).
+pub const MIN_REPLAY_LAG: usize = 2;
+
+/// Minimum number of comparable moves for a lag to be scored.
+pub const MIN_REPLAY_OVERLAP: usize = 8;
+
+/// Distance within which two move endpoints count as the same place
+/// (km). About 1.5 resolution-10 cell spacings: a fix jittered into a
+/// neighbouring cell still matches its original.
+pub const REPLAY_TOLERANCE_KM: f64 = 0.2;
+
+/// Later moves examined per grid bucket for each move.
+pub const MAX_REPLAY_CANDIDATES: usize = 64;
+
+/// Mean km per degree of latitude.
+const KM_PER_DEGREE: f64 = 111.0;
+
+/// Result of replay periodicity analysis.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayResult {
+    /// Fraction of moves repeated at the best lag [0, 1].
+    /// Organic trajectories stay low; looped replays approach 1.
+    pub replay_periodicity: f64,
+
+    /// Lag (in breadcrumbs) with the strongest repetition.
+    pub best_lag: usize,
+
+    /// Number of moves compared at the best lag.
+    pub overlap: usize,
+}
+
+/// A move between two points on a local plane (km).
+#[derive(Debug, Clone, Copy)]
+struct Move {
+    from: (f64, f64),
+    to: (f64, f64),
+}
+
+impl Move {
+    /// Grid bucket of the start and end point.
+    fn bucket(&self, tolerance_km: f64) -> [i64; 4] {
+        let cell = |v: f64| (v / tolerance_km).floor() as i64;
+        [cell(self.from.0), cell(self.from.1), cell(self.to.0), cell(self.to.1)]
+    }
+
+    fn matches(&self, other: &Move, tolerance_km: f64) -> bool {
+        let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).hypot(a.1 - b.1) <= tolerance_km;
+        close(self.from, other.from) && close(self.to, other.to)
+    }
+}
+
+/// Score how strongly the chain's spatial path repeats at a fixed lag,
+/// matching moves within `REPLAY_TOLERANCE_KM`.
+pub fn replay_periodicity(breadcrumbs: &[Breadcrumb]) -> ReplayResult {
+    replay_periodicity_within(breadcrumbs, REPLAY_TOLERANCE_KM)
+}
+
+/// `replay_periodicity` with a custom, positive match tolerance (km).
+pub fn replay_periodicity_within(breadcrumbs: &[Breadcrumb], tolerance_km: f64) -> ReplayResult {
+    // Equirectangular projection around the first fix; replays span a
+    // city, not a hemisphere
+    let points: Vec<Option<(f64, f64)>> = breadcrumbs.iter()
+        .map(|b| h3_cell_to_latlon(&b.location_cell).ok())
+        .collect();
+    let lat0 = points.iter().flatten().next().map_or(0.0, |p| p.0);
+    let project = |(lat, lon): (f64, f64)| {
+        (lon * KM_PER_DEGREE * lat0.to_radians().cos(), lat * KM_PER_DEGREE)
+    };
+    let moves: Vec<Option<Move>> = points.windows(2)
+        .map(|pair| Some(Move { from: project(pair[0]?), to: project(pair[1]?) }))
+        .collect();
+    // Only moves longer than the tolerance are scored, but their
+    // repeats may be shorter: jitter can shrink a move as well as grow it
+    let moving: Vec<bool> = moves.iter()
+        .map(|m| m.is_some_and(|m| (m.to.0 - m.from.0).hypot(m.to.1 - m.from.1) > tolerance_km))
+        .collect();
+
+    let mut buckets: HashMap<[i64; 4], Vec<usize>> = HashMap::new();
+    for (i, m) in moves.iter().enumerate() {
+        if let Some(m) = m {
+            buckets.entry(m.bucket(tolerance_km)).or_default().push(i);
+        }
+    }
+
+    // matched[lag]: moves repeated `lag` moves later
+    let max_lag = moves.len() / 2;
+    let mut matched = vec![0usize; max_lag + 1];
+    for (i, m) in moves.iter().enumerate() {
+        let Some(m) = m.filter(|_| moving[i]) else { continue };
+        let [fx, fy, tx, ty] = m.bucket(tolerance_km);
+        for dfx in -1..=1 {
+            for dfy in -1..=1 {
+                for dtx in -1..=1 {
+                    for dty in -1..=1 {
+                        let Some(later) = buckets.get(&[fx + dfx, fy + dfy, tx + dtx, ty + dty]) else {
+                            continue;
+                        };
+                        let start = later.partition_point(|&j| j < i + MIN_REPLAY_LAG);
+                        for &j in later[start..].iter().take(MAX_REPLAY_CANDIDATES) {
+                            if j - i > max_lag {
+                                break;
+                            }
+                            if moves[j].is_some_and(|other| m.matches(&other, tolerance_km)) {
+                                matched[j - i] += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // moving_before[k]: moves among the first k
+    let mut moving_before = vec![0usize; moves.len() + 1];
+    for (i, &m) in moving.iter().enumerate() {
+        moving_before[i + 1] = moving_before[i] + usize::from(m);
+    }
+
+    let mut best = ReplayResult::default();
+
+    for lag in MIN_REPLAY_LAG..=max_lag {
+        let compared = moving_before[moves.len() - lag];
+        let matched = matched[lag];
+
+        if compared < MIN_REPLAY_OVERLAP {
+            continue;
+        }
+
+        let score = matched as f64 / compared as f64;
+        if score > best.replay_periodicity {
+            best = ReplayResult {
+                replay_periodicity: score,
+                best_lag: lag,
+                overlap: compared,
+            };
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::tests::{random_walk_breadcrumbs, synthetic_breadcrumbs};
+
+    #[test]
+    fn test_looped_track_detected() {
+        let track = random_walk_breadcrumbs(40, 3);
+        let samples: Vec<(i64, &str)> = (0..200)
+            .map(|i| (1_700_000_000 + i as i64 * 900, track[i % 40].location_cell.as_str()))
+            .collect();
+        let looped = synthetic_breadcrumbs(&samples);
+
+        let result = replay_periodicity(&looped);
+        assert!(result.replay_periodicity > 0.95, "looped: {result:?}");
+        assert_eq!(result.best_lag % 40, 0);
+    }
+
+    #[test]
+    fn test_organic_track_low() {
+        let organic = random_walk_breadcrumbs(200, 3);
+        let result = replay_periodicity(&organic);
+        assert!(result.replay_periodicity < 0.2, "organic: {result:?}");
+    }
+
+    #[test]
+    fn test_stationary_ignored() {
+        let samples: Vec<(i64, &str)> = (0..100)
+            .map(|i| (1_700_000_000 + i as i64 * 900, "8a1e8052da1ffff"))
+            .collect();
+        let result = replay_periodicity(&synthetic_breadcrumbs(&samples));
+        assert_eq!(result.replay_periodicity, 0.0);
+    }
+
+    #[test]
+    fn test_jittered_replay_detected() {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        // A recorded track looped five times, each fix moved to a
+        // neighbouring cell 30% of the time
+        let mut rng = StdRng::seed_from_u64(11);
+        let track = random_walk_breadcrumbs(40, 3);
+        let cells: Vec<String> = (0..200)
+            .map(|i| {
+                let cell = crate::breadcrumb::parse_cell(&track[i % 40].location_cell).unwrap();
+                if rng.gen_bool(0.3) {
+                    let ring: Vec<_> = cell.grid_disk::<Vec<_>>(1).into_iter().filter(|&c| c != cell).collect();
+                    format!("{:x}", u64::from(ring[rng.gen_range(0..ring.len())]))
+                } else {
+                    format!("{:x}", u64::from(cell))
+                }
+            })
+            .collect();
+        let samples: Vec<(i64, &str)> = cells.iter().enumerate()
+            .map(|(i, c)| (1_700_000_000 + i as i64 * 900, c.as_str()))
+            .collect();
+        let jittered = synthetic_breadcrumbs(&samples);
+
+        // Exact matching loses most of the repeats
+        let exact = replay_periodicity_within(&jittered, 0.001);
+        let result = replay_periodicity(&jittered);
+        assert!(result.replay_periodicity > 0.8, "jittered: {result:?}");
+        assert!(exact.replay_periodicity < result.replay_periodicity - 0.3, "exact: {exact:?}");
+        assert_eq!(result.best_lag % 40, 0);
+    }
+}
@@ -0,0 +1,127 @@
+// trip-verifier/src/revocation.rs
+//
+// Verifier Key Revocation
+// =======================
+//
+// Relying parties cache trust in a set of Verifier keys. When one of
+// those keys is compromised, its owner publishes the key with the time
+// from which it can no longer be trusted. A `RevocationList` holds
+// those entries, and `PoHCertificate::verify_with_revocations` rejects
+// any certificate the revoked key issued at or after that time. Facet
+// aggregation and `verify_chain_json` consult the list as well.
+// Certificates issued earlier stay valid until they expire.
+//
+// `issued_at` is signed by the Verifier key itself, so a holder of the
+// stolen key can backdate a certificate to before the revocation. Set
+// the effective-from time to the earliest moment the key may have
+// leaked rather than the moment the leak was noticed. Short validity
+// periods (see `ValidityPolicy`) bound how long a backdated
+// certificate stays usable.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::certificate::PoHCertificate;
+use crate::error::{TripError, Result};
+
+/// Revoked Verifier public keys (hex), each with the time from which
+/// its certificates are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RevocationList {
+    revoked: HashMap<String, DateTime<Utc>>,
+}
+
+impl RevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Revoke `verifier_key` from `effective_from` on. Revoking a key
+    /// again keeps the earlier time.
+    pub fn revoke(&mut self, verifier_key: &str, effective_from: DateTime<Utc>) {
+        self.revoked
+            .entry(verifier_key.to_ascii_lowercase())
+            .and_modify(|t| *t = (*t).min(effective_from))
+            .or_insert(effective_from);
+    }
+
+    /// When `verifier_key` was revoked, if it was.
+    pub fn revoked_at(&self, verifier_key: &str) -> Option<DateTime<Utc>> {
+        self.revoked.get(&verifier_key.to_ascii_lowercase()).copied()
+    }
+
+    /// Is a certificate from `verifier_key` issued at `issued_at` revoked?
+    pub fn is_revoked(&self, verifier_key: &str, issued_at: DateTime<Utc>) -> bool {
+        self.revoked_at(verifier_key).is_some_and(|from| issued_at >= from)
+    }
+
+    /// Reject `certificate` if its Verifier key was revoked before it
+    /// was issued.
+    pub fn check(&self, certificate: &PoHCertificate) -> Result<()> {
+        match self.revoked_at(&certificate.verifier_key) {
+            Some(from) if certificate.issued_at >= from => Err(TripError::CertificateError(format!(
+                "Verifier key {} revoked from {from}; certificate issued {}",
+                certificate.verifier_key, certificate.issued_at
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.revoked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.revoked.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use ed25519_dalek::SigningKey;
+    use crate::chain::BreadcrumbChain;
+    use crate::chain::tests::random_walk_breadcrumbs;
+    use crate::criticality::CriticalityEngine;
+
+    #[test]
+    fn test_revocation_applies_from_effective_time() {
+        let verifier = SigningKey::from_bytes(&[2u8; 32]);
+        let verifier_key = hex::encode(verifier.verifying_key().to_bytes());
+        let chain = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 7)).unwrap();
+        let result = CriticalityEngine::with_defaults().evaluate(&chain).unwrap();
+        let cert = PoHCertificate::from_criticality_result(
+            &result,
+            chain.identity.clone(),
+            verifier_key.clone(),
+            "c".repeat(64),
+            3600,
+        ).sign(&verifier).unwrap();
+
+        // Issued before the key was revoked: still accepted
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&verifier_key, cert.issued_at + Duration::seconds(1));
+        assert!(!revocations.is_revoked(&verifier_key, cert.issued_at));
+        cert.verify_with_revocations(&revocations).unwrap();
+
+        // Issued after it: rejected, though the signature is valid
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&verifier_key.to_uppercase(), cert.issued_at - Duration::minutes(5));
+        cert.verify().unwrap();
+        assert!(matches!(
+            cert.verify_with_revocations(&revocations),
+            Err(TripError::CertificateError(msg)) if msg.contains("revoked")
+        ));
+
+        // A later revocation of the same key doesn't push the time back
+        revocations.revoke(&verifier_key, cert.issued_at + Duration::hours(1));
+        assert_eq!(revocations.revoked_at(&verifier_key), Some(cert.issued_at - Duration::minutes(5)));
+        assert_eq!(revocations.len(), 1);
+
+        // Other Verifiers are unaffected
+        let mut revocations = RevocationList::new();
+        revocations.revoke(&"ab".repeat(32), cert.issued_at - Duration::minutes(5));
+        cert.verify_with_revocations(&revocations).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+// trip-verifier/src/scenario.rs
+//
+// Attack Scenarios (feature "test-util")
+// =======================================
+//
+// Hand-crafted chains for checking how the detectors respond to a
+// known attack: a human who hands their phone to a bot at breadcrumb
+// 300, a replay that starts at 500. A `ScenarioBuilder` strings
+// segments from different generative models into one signed chain
+// and labels every breadcrumb with the regime that produced it, so
+// windowed verdicts (`CriticalityEngine::evaluate_windows`) can be
+// scored against ground truth.
+//
+// Generative models, all at H3 resolution 10 around a fixed origin:
+// - Human: Lévy step lengths (P(Δr) ∝ Δr^-2, 50 m – 20 km) whose
+//   order follows 1/f noise, so large and small moves cluster as in
+//   real routines (windowed α ≈ 0.3–0.9); headings drift; 10–60
+//   minute intervals
+// - Bot: independent uniform 0.2–3 km steps in random directions on a
+//   fixed 15 minute clock
+// - Replay: the chain's own earlier cells and intervals, looped from
+//   its first breadcrumb
+//
+// Every segment continues from where the previous one ended, so the
+// handoff itself is not a jump.
+
+use chrono::{DateTime, TimeZone, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rustfft::{FftPlanner, num_complex::Complex};
+use crate::breadcrumb::{Breadcrumb, MetaFlags};
+use crate::chain::BreadcrumbChain;
+use crate::error::Result;
+
+/// Generative model behind a segment, and the ground-truth label of
+/// the breadcrumbs it produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Regime {
+    Human,
+    Bot,
+    Replay,
+}
+
+/// Composes labeled segments into one signed chain.
+#[derive(Debug, Clone)]
+pub struct ScenarioBuilder {
+    seed: u64,
+    origin: (f64, f64),
+    start: DateTime<Utc>,
+    segments: Vec<(Regime, usize)>,
+}
+
+/// A signed chain with its per-breadcrumb ground truth.
+pub struct Scenario {
+    pub chain: BreadcrumbChain,
+    /// Regime of each breadcrumb, aligned with `chain.breadcrumbs`
+    pub labels: Vec<Regime>,
+}
+
+impl ScenarioBuilder {
+    /// Empty scenario starting in Rome at 2023-11-14 22:13 UTC.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            origin: (41.9028, 12.4964),
+            start: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Start somewhere else (degrees).
+    pub fn with_origin(mut self, lat: f64, lng: f64) -> Self {
+        self.origin = (lat, lng);
+        self
+    }
+
+    /// Start at another time.
+    pub fn with_start(mut self, start: DateTime<Utc>) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Append `n` breadcrumbs of organic movement.
+    pub fn human(self, n: usize) -> Self {
+        self.segment(Regime::Human, n)
+    }
+
+    /// Append `n` breadcrumbs of constant-rate random movement.
+    pub fn bot(self, n: usize) -> Self {
+        self.segment(Regime::Bot, n)
+    }
+
+    /// Append `n` breadcrumbs replaying the chain from its start.
+    /// As the first segment it has nothing to replay and stays put.
+    pub fn replay(self, n: usize) -> Self {
+        self.segment(Regime::Replay, n)
+    }
+
+    pub fn segment(mut self, regime: Regime, n: usize) -> Self {
+        self.segments.push((regime, n));
+        self
+    }
+
+    /// Generate the chain and sign every breadcrumb with `key`.
+    pub fn build(&self, key: &SigningKey) -> Result<Scenario> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut walker = Walker {
+            position: self.origin,
+            heading: rng.gen_range(0.0..std::f64::consts::TAU),
+            time: self.start.timestamp(),
+        };
+        let mut samples: Vec<(i64, String)> = Vec::new();
+        let mut labels = Vec::new();
+
+        for &(regime, n) in &self.segments {
+            match regime {
+                Regime::Human => {
+                    let steps = human_steps(n, &mut rng);
+                    for step in steps {
+                        samples.push(walker.sample());
+                        walker.heading += rng.gen_range(-0.8..0.8);
+                        walker.advance(step, rng.gen_range(600..3600));
+                    }
+                }
+                Regime::Bot => {
+                    for _ in 0..n {
+                        samples.push(walker.sample());
+                        walker.heading = rng.gen_range(0.0..std::f64::consts::TAU);
+                        walker.advance(rng.gen_range(0.2..3.0), 900);
+                    }
+                }
+                Regime::Replay => {
+                    let recorded = match samples.is_empty() {
+                        true => vec![walker.sample()],
+                        false => samples.clone(),
+                    };
+                    for i in 0..n {
+                        let j = i % recorded.len();
+                        let dt = match recorded.get(j + 1) {
+                            Some(next) => next.0 - recorded[j].0,
+                            None => 900,
+                        };
+                        samples.push((walker.time, recorded[j].1.clone()));
+                        walker.time += dt;
+                    }
+                    if let Some((_, cell)) = samples.last().filter(|_| n > 0) {
+                        walker.position = cell_center(cell);
+                    }
+                }
+            }
+            labels.extend(std::iter::repeat_n(regime, n));
+        }
+
+        let breadcrumbs = sign_samples(&samples, key);
+        Ok(Scenario { chain: BreadcrumbChain::from_breadcrumbs(breadcrumbs)?, labels })
+    }
+}
+
+impl Scenario {
+    /// Index of the first breadcrumb of each segment, with its regime.
+    pub fn transitions(&self) -> Vec<(usize, Regime)> {
+        let mut out: Vec<(usize, Regime)> = Vec::new();
+        for (i, &regime) in self.labels.iter().enumerate() {
+            if out.last().is_none_or(|&(_, last)| last != regime) {
+                out.push((i, regime));
+            }
+        }
+        out
+    }
+
+    /// Most common regime among breadcrumbs `start..end`.
+    pub fn dominant_regime(&self, start: usize, end: usize) -> Option<Regime> {
+        let window = self.labels.get(start..end.min(self.labels.len()))?;
+        [Regime::Human, Regime::Bot, Regime::Replay].into_iter()
+            .map(|regime| (window.iter().filter(|&&r| r == regime).count(), regime))
+            .filter(|&(count, _)| count > 0)
+            .max_by_key(|&(count, _)| count)
+            .map(|(_, regime)| regime)
+    }
+}
+
+/// Position, heading and clock carried across segments.
+struct Walker {
+    position: (f64, f64),
+    heading: f64,
+    time: i64,
+}
+
+impl Walker {
+    fn sample(&self) -> (i64, String) {
+        let (lat, lng) = self.position;
+        let cell = h3o::LatLng::new(lat, lng)
+            .expect("scenario stays within valid coordinates")
+            .to_cell(h3o::Resolution::Ten);
+        (self.time, cell.to_string())
+    }
+
+    fn advance(&mut self, step_km: f64, dt_seconds: i64) {
+        let (lat, lng) = &mut self.position;
+        *lat += step_km / 111.32 * self.heading.cos();
+        *lng += step_km / (111.32 * lat.to_radians().cos()) * self.heading.sin();
+        self.time += dt_seconds;
+    }
+}
+
+/// Center (degrees) of a cell this module generated.
+fn cell_center(cell: &str) -> (f64, f64) {
+    let center = h3o::LatLng::from(
+        cell.parse::<h3o::CellIndex>().expect("scenario cells are valid"),
+    );
+    (center.lat(), center.lng())
+}
+
+/// Lévy step lengths (km) ordered by 1/f noise: each step takes
+/// the rank of the noise at its position, so the magnitudes keep the
+/// power-law distribution and inherit the noise's correlations.
+fn human_steps(n: usize, rng: &mut StdRng) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let total = 4 * n.max(2);
+    let mut buffer = vec![Complex::new(0.0, 0.0); total];
+    for k in 1..total / 2 {
+        let amplitude = 1.0 / (k as f64).sqrt();
+        buffer[k] = Complex::from_polar(amplitude, rng.gen_range(0.0..std::f64::consts::TAU));
+        buffer[total - k] = buffer[k].conj();
+    }
+    FftPlanner::<f64>::new().plan_fft_inverse(total).process(&mut buffer);
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| buffer[a].re.total_cmp(&buffer[b].re));
+    let mut steps = vec![0.0; n];
+    for (rank, &i) in order.iter().enumerate() {
+        let u = (n - rank) as f64 / (n + 1) as f64;
+        steps[i] = (0.05 / u).min(20.0);
+    }
+    steps
+}
+
+/// Hash-linked, signed breadcrumbs at (unix seconds, cell) samples.
+fn sign_samples(samples: &[(i64, String)], key: &SigningKey) -> Vec<Breadcrumb> {
+    let identity = hex::encode(key.verifying_key().to_bytes());
+    let mut out: Vec<Breadcrumb> = Vec::with_capacity(samples.len());
+    for (i, (t, cell)) in samples.iter().enumerate() {
+        let mut b = Breadcrumb {
+            index: i as u64,
+            identity_public_key: identity.clone(),
+            timestamp: Utc.timestamp_opt(*t, 0).unwrap(),
+            location_cell: cell.clone(),
+            location_resolution: 10,
+            context_digest: format!("{i:064x}"),
+            previous_hash: out.last().map(|b| b.block_hash.clone()),
+            meta_flags: MetaFlags {
+                battery: None,
+                sampling: "normal".to_string(),
+                state: "unknown".to_string(),
+                network: "unknown".to_string(),
+                accuracy: None,
+                manual: false,
+            },
+            signature: String::new(),
+            block_hash: String::new(),
+            beacon_digest: None,
+            skip_hash: None,
+        };
+        b.signature = hex::encode(key.sign(b.data_to_sign().as_bytes()).to_bytes());
+        b.block_hash = b.compute_block_hash();
+        out.push(b);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::criticality::CriticalityEngine;
+
+    #[test]
+    fn test_scenario_labels_and_signatures() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let scenario = ScenarioBuilder::new(1).human(100).bot(50).replay(30).build(&key).unwrap();
+
+        assert_eq!(scenario.chain.len(), 180);
+        assert_eq!(scenario.labels.len(), 180);
+        assert_eq!(
+            scenario.transitions(),
+            vec![(0, Regime::Human), (100, Regime::Bot), (150, Regime::Replay)]
+        );
+        assert_eq!(scenario.dominant_regime(90, 140), Some(Regime::Bot));
+        let cells: Vec<&str> = scenario.chain.breadcrumbs.iter()
+            .map(|b| b.location_cell.as_str())
+            .collect();
+        assert_eq!(cells[150..180], cells[0..30]);
+        assert!(scenario.chain.verify_signatures().is_ok());
+        assert!(scenario.chain.verify_block_hashes().is_ok());
+    }
+
+    #[test]
+    fn test_human_to_bot_handoff_detected() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let scenario = ScenarioBuilder::new(0).human(300).bot(300).build(&key).unwrap();
+        let (window, step) = (150, 25);
+        let windows = CriticalityEngine::with_defaults()
+            .evaluate_windows(&scenario.chain, window, step)
+            .unwrap();
+        assert_eq!(windows.len(), (600 - window) / step + 1);
+
+        // The handoff is where α leaves the pink band (≥ 0.30) for good
+        let last_pink = windows.iter()
+            .rposition(|w| w.alpha.is_some_and(|alpha| alpha >= 0.30))
+            .unwrap();
+        let detected = windows[last_pink + 1].start;
+        let labeled = scenario.transitions()[1].0;
+        assert!(
+            detected.abs_diff(labeled) <= window / 2,
+            "handoff detected at {detected}, labeled at {labeled}"
+        );
+        assert_eq!(scenario.dominant_regime(detected, detected + window), Some(Regime::Bot));
+        assert_eq!(scenario.dominant_regime(0, windows[last_pink].end), Some(Regime::Human));
+    }
+
+    #[test]
+    fn test_segments_continue_across_boundaries() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let scenario = ScenarioBuilder::new(2)
+            .human(40).replay(25).bot(20).replay(10).human(20)
+            .build(&key)
+            .unwrap();
+        let cells: Vec<&str> = scenario.chain.breadcrumbs.iter()
+            .map(|b| b.location_cell.as_str())
+            .collect();
+        let transitions = scenario.transitions();
+        assert_eq!(transitions.len(), 5);
+
+        // Whatever follows a replay starts from the last replayed cell
+        for &(start, _) in &transitions[1..] {
+            if scenario.labels[start - 1] == Regime::Replay {
+                assert_eq!(cells[start], cells[start - 1], "segment at {start}");
+            }
+        }
+        // Other handoffs are at most one step away (bot steps ≤ 3 km,
+        // human ≤ 20 km)
+        let km = |a: &str, b: &str| {
+            let (a, b) = (cell_center(a), cell_center(b));
+            let dlat = (a.0 - b.0) * 111.32;
+            let dlng = (a.1 - b.1) * 111.32 * a.0.to_radians().cos();
+            dlat.hypot(dlng)
+        };
+        for &(start, _) in &transitions[1..] {
+            assert!(km(cells[start - 1], cells[start]) <= 20.5, "segment at {start}");
+        }
+        // Timestamps stay strictly increasing across every boundary
+        assert!(scenario.chain.breadcrumbs.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    }
+}
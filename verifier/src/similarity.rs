@@ -0,0 +1,191 @@
+// trip-verifier/src/similarity.rs
+//
+// Trajectory Similarity
+// ======================
+//
+// A farming operator can run many identities by carrying several
+// phones along one real person's route. Each chain looks human on
+// its own; what gives them away is that they are the same trajectory.
+//
+// Two signals, each in [0, 1]:
+// - Cell overlap: Jaccard index of the visited H3 cell sets
+// - Co-location: share of breadcrumbs with a breadcrumb of the other
+//   chain in the same cell within CO_LOCATION_WINDOW_SECS, averaged
+//   over both directions
+//
+// similarity = (overlap + co-location) / 2
+//
+// Independent people rarely share more than a few anchor cells and
+// almost never share them at the same minutes; duplicates share both.
+// This is the pairwise primitive for a Sybil-cluster detector:
+// `co_location_clusters` links every pair of chains whose co-location
+// reaches a threshold and returns the groups. A herd of identities all
+// shadowing one person shows up as one large cluster; people who only
+// share a household or a commute now and then don't reach it.
+
+use std::collections::{HashMap, HashSet};
+use crate::breadcrumb::Breadcrumb;
+use crate::chain::BreadcrumbChain;
+
+/// Maximum time offset for two breadcrumbs to count as co-located.
+pub const CO_LOCATION_WINDOW_SECS: i64 = 1800;
+
+/// `co_location_score` at or above which two chains are linked into
+/// one cluster by default. A couple living together co-locate at home
+/// overnight and little else; shadowing phones co-locate everywhere.
+pub const DEFAULT_CO_LOCATION_THRESHOLD: f64 = 0.6;
+
+/// Similarity of two trajectories in [0, 1]; near 1 for duplicates.
+pub fn trajectory_similarity(a: &BreadcrumbChain, b: &BreadcrumbChain) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let cells_a: HashSet<&str> = a.breadcrumbs.iter().map(|x| x.location_cell.as_str()).collect();
+    let cells_b: HashSet<&str> = b.breadcrumbs.iter().map(|x| x.location_cell.as_str()).collect();
+    let overlap = cells_a.intersection(&cells_b).count() as f64
+        / cells_a.union(&cells_b).count() as f64;
+
+    (overlap + co_location_score(a, b)) / 2.0
+}
+
+/// How often two chains occupy the same cell at overlapping times, in
+/// [0, 1]: the share of each chain's breadcrumbs with a same-cell
+/// breadcrumb of the other within CO_LOCATION_WINDOW_SECS, averaged
+/// over both directions. Symmetric; 0 if either chain is empty.
+pub fn co_location_score(a: &BreadcrumbChain, b: &BreadcrumbChain) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    (co_located_fraction(&a.breadcrumbs, &b.breadcrumbs)
+        + co_located_fraction(&b.breadcrumbs, &a.breadcrumbs)) / 2.0
+}
+
+/// Group chains that move together: single-linkage clusters of the
+/// pairs whose `co_location_score` is at least `threshold`. Returns
+/// the indices into `chains` of every cluster with two or more
+/// members, each sorted, largest cluster first.
+pub fn co_location_clusters(chains: &[BreadcrumbChain], threshold: f64) -> Vec<Vec<usize>> {
+    // Union-find over chain indices
+    let mut parent: Vec<usize> = (0..chains.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..chains.len() {
+        for j in i + 1..chains.len() {
+            if co_location_score(&chains[i], &chains[j]) >= threshold {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_of_root = HashMap::new();
+    for i in 0..chains.len() {
+        let r = root(&mut parent, i);
+        let k = *cluster_of_root.entry(r).or_insert_with(|| {
+            clusters.push(Vec::new());
+            clusters.len() - 1
+        });
+        clusters[k].push(i);
+    }
+    clusters.retain(|c| c.len() > 1);
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+    clusters
+}
+
+/// Share of `from` breadcrumbs with a same-cell breadcrumb in `to`
+/// within the co-location window. `to` must be in timestamp order,
+/// which chain verification guarantees.
+fn co_located_fraction(from: &[Breadcrumb], to: &[Breadcrumb]) -> f64 {
+    let window = chrono::Duration::seconds(CO_LOCATION_WINDOW_SECS);
+
+    let matched = from.iter()
+        .filter(|x| {
+            let start = to.partition_point(|y| y.timestamp < x.timestamp - window);
+            to[start..].iter()
+                .take_while(|y| y.timestamp <= x.timestamp + window)
+                .any(|y| y.location_cell == x.location_cell)
+        })
+        .count();
+
+    matched as f64 / from.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::tests::random_walk_breadcrumbs;
+
+    #[test]
+    fn test_near_duplicate_trajectories_similar() {
+        let original = random_walk_breadcrumbs(200, 5);
+
+        // Second phone on the same route: own key, own sampling jitter,
+        // occasionally resolving to a different cell
+        let mut copy = original.clone();
+        for (i, b) in copy.iter_mut().enumerate() {
+            b.identity_public_key = "cd".repeat(32);
+            b.timestamp += chrono::Duration::seconds(((i * 37) % 240) as i64);
+            if i % 10 == 3 {
+                b.location_cell = original[(i + 1) % original.len()].location_cell.clone();
+            }
+        }
+
+        let a = BreadcrumbChain::from_breadcrumbs(original).unwrap();
+        let b = BreadcrumbChain::from_breadcrumbs(copy).unwrap();
+        let similarity = trajectory_similarity(&a, &b);
+        assert!(similarity > 0.8, "similarity = {similarity}");
+        assert!((similarity - trajectory_similarity(&b, &a)).abs() < 1e-12);
+        assert!((trajectory_similarity(&a, &a) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_independent_trajectories_dissimilar() {
+        let a = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 5)).unwrap();
+        let b = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 6)).unwrap();
+        let similarity = trajectory_similarity(&a, &b);
+        assert!(similarity < 0.1, "similarity = {similarity}");
+    }
+
+    /// A second phone carried along `original`, sampled up to 4 minutes
+    /// off and under its own key.
+    fn shadow(original: &[Breadcrumb], key_byte: u8) -> BreadcrumbChain {
+        let mut copy = original.to_vec();
+        for (i, b) in copy.iter_mut().enumerate() {
+            b.identity_public_key = format!("{key_byte:02x}").repeat(32);
+            b.timestamp += chrono::Duration::seconds(((i * 37 + key_byte as usize) % 240) as i64);
+        }
+        BreadcrumbChain::from_breadcrumbs(copy).unwrap()
+    }
+
+    #[test]
+    fn test_co_location_co_moving_vs_independent() {
+        let route = random_walk_breadcrumbs(200, 5);
+        let real = BreadcrumbChain::from_breadcrumbs(route.clone()).unwrap();
+        let follower = shadow(&route, 0xcd);
+        let co_moving = co_location_score(&real, &follower);
+        assert!(co_moving > 0.9, "co-moving = {co_moving}");
+        assert!((co_moving - co_location_score(&follower, &real)).abs() < 1e-12);
+
+        let stranger = BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, 6)).unwrap();
+        let independent = co_location_score(&real, &stranger);
+        assert!(independent < 0.1, "independent = {independent}");
+
+        // A herd of four shadowing identities around one person, among
+        // three unrelated people
+        let mut chains = vec![stranger];
+        chains.extend((0..4).map(|k| shadow(&route, 0x10 + k)));
+        chains.extend([7, 8].map(|seed| BreadcrumbChain::from_breadcrumbs(random_walk_breadcrumbs(200, seed)).unwrap()));
+        chains.insert(3, real);
+        let clusters = co_location_clusters(&chains, DEFAULT_CO_LOCATION_THRESHOLD);
+        assert_eq!(clusters, vec![vec![1, 2, 3, 4, 5]]);
+        assert!(co_location_clusters(&chains[..1], DEFAULT_CO_LOCATION_THRESHOLD).is_empty());
+    }
+}
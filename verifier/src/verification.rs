@@ -12,20 +12,53 @@
 //
 // The nonce binding prevents replay of certificates across
 // different Relying Party contexts.
+//
+// Deployments that restrict who may request verification have the
+// RP sign {identity_key || nonce} and accept only requests signed by
+// a known RP key (see `RelyingPartyPolicy`). Open deployments accept
+// unsigned requests.
+//
+// Deadline extension: a session may allow one extra window after the
+// deadline for an Attester that acknowledged the challenge in time
+// (`ChallengeAck`) but lost its response to the network. The
+// extension is granted once and capped at
+// MAX_DEADLINE_EXTENSION_SECONDS, so the longest a challenge can stay
+// answerable is the deadline plus the cap. Replay exposure grows by
+// that window and no more: the ack and the response must both echo
+// this session's nonce, are signed and timestamped by the Attester
+// when signatures are verified, an ack stamped outside the session's
+// window is refused, and a late ack (after the original deadline)
+// buys nothing, so an attacker can't keep a captured
+// challenge alive by acknowledging it repeatedly.
 
+use std::collections::HashSet;
+use std::sync::Arc;
 use chrono::{DateTime, Utc, Duration};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use crate::breadcrumb::TIMESTAMP_FORMAT;
+use crate::certificate::{DEFAULT_CLOCK_SKEW_SECONDS, decode_hex_field};
+use crate::clock::{Clock, SystemClock};
 use crate::error::{TripError, Result};
 
 /// Default deadline for attester to respond (seconds).
 pub const DEFAULT_DEADLINE_SECONDS: u64 = 30;
 
+/// Longest deadline extension a session can grant (seconds).
+pub const MAX_DEADLINE_EXTENSION_SECONDS: u64 = 60;
+
 /// Step 1: Relying Party's verification request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationRequest {
     pub identity_key: String,  // Ed25519 public key hex of the Attester
     pub nonce: Vec<u8>,        // 16-byte random nonce from the RP
+    /// Ed25519 public key hex of the requesting RP, if signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rp_public_key: Option<String>,
+    /// RP signature over `data_to_sign()` (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rp_signature: Option<String>,
 }
 
 impl VerificationRequest {
@@ -33,12 +66,80 @@ impl VerificationRequest {
     pub fn new(identity_key: String) -> Self {
         let mut nonce = vec![0u8; 16];
         rand::thread_rng().fill(&mut nonce[..]);
-        Self { identity_key, nonce }
+        Self::with_nonce(identity_key, nonce)
     }
 
     /// Create a request with a specific nonce (for testing).
     pub fn with_nonce(identity_key: String, nonce: Vec<u8>) -> Self {
-        Self { identity_key, nonce }
+        Self { identity_key, nonce, rp_public_key: None, rp_signature: None }
+    }
+
+    /// Sign the request with the Relying Party's key.
+    pub fn signed_by(mut self, rp: &SigningKey) -> Self {
+        self.rp_public_key = Some(hex::encode(rp.verifying_key().to_bytes()));
+        self.rp_signature = Some(hex::encode(rp.sign(&self.data_to_sign()).to_bytes()));
+        self
+    }
+
+    /// Bytes covered by the RP signature: the identity key (hex, as
+    /// sent) followed by the raw nonce.
+    pub fn data_to_sign(&self) -> Vec<u8> {
+        let mut data = self.identity_key.as_bytes().to_vec();
+        data.extend_from_slice(&self.nonce);
+        data
+    }
+
+    /// Verify the RP signature against the RP key in the request.
+    /// Malformed key or signature hex is reported as such (see
+    /// `decode_hex_field`); anything else is `UnauthorizedRequest`.
+    pub fn verify_rp_signature(&self) -> Result<()> {
+        let unauthorized = |msg: &str| TripError::UnauthorizedRequest(msg.to_string());
+
+        let (key, signature) = match (&self.rp_public_key, &self.rp_signature) {
+            (Some(key), Some(signature)) => (key, signature),
+            _ => return Err(unauthorized("request is not signed")),
+        };
+        let key_bytes: [u8; 32] = decode_hex_field(key, "rp_public_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(signature, "rp_signature")?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes)
+            .map_err(|_| unauthorized("malformed RP public key"))?;
+        key.verify(&self.data_to_sign(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| unauthorized("RP signature does not verify"))
+    }
+}
+
+/// Which Relying Parties may request verification.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RelyingPartyPolicy {
+    /// Anyone may; a signature, if present, must still verify
+    #[default]
+    Open,
+    /// Only requests signed by one of these RP public keys (hex)
+    Restricted(HashSet<String>),
+}
+
+impl RelyingPartyPolicy {
+    /// Restrict requests to the given RP public keys (hex).
+    pub fn restricted<I: IntoIterator<Item = String>>(keys: I) -> Self {
+        Self::Restricted(keys.into_iter().map(|k| k.to_ascii_lowercase()).collect())
+    }
+
+    /// Accept or reject a request under this policy.
+    pub fn check(&self, request: &VerificationRequest) -> Result<()> {
+        match self {
+            Self::Open if request.rp_signature.is_none() => Ok(()),
+            Self::Open => request.verify_rp_signature(),
+            Self::Restricted(keys) => {
+                request.verify_rp_signature()?;
+                let key = request.rp_public_key.as_deref().unwrap_or_default();
+                if keys.contains(&key.to_ascii_lowercase()) {
+                    Ok(())
+                } else {
+                    Err(TripError::UnauthorizedRequest(format!("unknown RP key {key}")))
+                }
+            }
+        }
     }
 }
 
@@ -52,9 +153,14 @@ pub struct LivenessChallenge {
 
 impl LivenessChallenge {
     pub fn from_request(request: &VerificationRequest) -> Self {
+        Self::from_request_with_clock(request, &SystemClock)
+    }
+
+    /// Challenge issued at `clock`'s time.
+    pub fn from_request_with_clock(request: &VerificationRequest, clock: &dyn Clock) -> Self {
         Self {
             nonce: request.nonce.clone(),
-            challenge_timestamp: Utc::now(),
+            challenge_timestamp: clock.now(),
             response_deadline_seconds: DEFAULT_DEADLINE_SECONDS,
         }
     }
@@ -64,7 +170,68 @@ impl LivenessChallenge {
     }
 
     pub fn is_expired(&self) -> bool {
-        Utc::now() > self.deadline()
+        self.is_expired_with_clock(&SystemClock)
+    }
+
+    /// Is the deadline past at `clock`'s time?
+    pub fn is_expired_with_clock(&self, clock: &dyn Clock) -> bool {
+        clock.now() > self.deadline()
+    }
+}
+
+/// Step 2a (optional): the Attester's acknowledgment that it received
+/// the challenge, sent as soon as it arrives. Lets a session extend
+/// the deadline once for a response delayed in transit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeAck {
+    pub nonce_echo: Vec<u8>,          // Must match the challenge nonce
+    pub ack_timestamp: DateTime<Utc>,
+    pub ed25519_signature: String,    // Signature over the ack (hex)
+}
+
+impl ChallengeAck {
+    /// Build and sign an acknowledgment of `challenge`, stamped with
+    /// the current time.
+    pub fn create_signed(challenge: &LivenessChallenge, identity: &SigningKey) -> Self {
+        Self::create_signed_with_clock(challenge, identity, &SystemClock)
+    }
+
+    /// Like `create_signed`, stamped with `clock`'s time.
+    pub fn create_signed_with_clock(
+        challenge: &LivenessChallenge,
+        identity: &SigningKey,
+        clock: &dyn Clock,
+    ) -> Self {
+        let mut ack = Self {
+            nonce_echo: challenge.nonce.clone(),
+            ack_timestamp: clock.now(),
+            ed25519_signature: String::new(),
+        };
+        let signature = identity.sign(ack.data_to_sign().as_bytes());
+        ack.ed25519_signature = hex::encode(signature.to_bytes());
+        ack
+    }
+
+    /// Canonical JSON covered by the Attester's signature. The "ack"
+    /// key keeps it distinct from a response's signed bytes.
+    pub fn data_to_sign(&self) -> String {
+        serde_json::json!({
+            "ack": hex::encode(&self.nonce_echo),
+            "timestamp": self.ack_timestamp.format(TIMESTAMP_FORMAT).to_string(),
+        })
+        .to_string()
+    }
+
+    /// Verify the signature against the Attester's public key (hex).
+    pub fn verify_signature(&self, identity_key: &str) -> Result<()> {
+        let invalid = || TripError::UnauthorizedRequest("challenge ack signature does not verify".to_string());
+
+        let key_bytes: [u8; 32] = decode_hex_field(identity_key, "identity_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(&self.ed25519_signature, "ed25519_signature")?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| invalid())?;
+        key.verify(self.data_to_sign().as_bytes(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| invalid())
     }
 }
 
@@ -78,12 +245,80 @@ pub struct LivenessResponse {
     pub ed25519_signature: String,    // Signature over the response (hex)
 }
 
+impl LivenessResponse {
+    /// Build and sign a response to `challenge` with the Attester's
+    /// identity key, stamped with the current time.
+    pub fn create_signed(
+        challenge: &LivenessChallenge,
+        identity: &SigningKey,
+        chain_head_hash: String,
+        current_breadcrumb_index: u64,
+    ) -> Self {
+        Self::create_signed_with_clock(
+            challenge, identity, chain_head_hash, current_breadcrumb_index, &SystemClock,
+        )
+    }
+
+    /// Like `create_signed`, stamped with `clock`'s time.
+    pub fn create_signed_with_clock(
+        challenge: &LivenessChallenge,
+        identity: &SigningKey,
+        chain_head_hash: String,
+        current_breadcrumb_index: u64,
+        clock: &dyn Clock,
+    ) -> Self {
+        let mut response = Self {
+            nonce_echo: challenge.nonce.clone(),
+            chain_head_hash,
+            response_timestamp: clock.now(),
+            current_breadcrumb_index,
+            ed25519_signature: String::new(),
+        };
+        let signature = identity.sign(response.data_to_sign().as_bytes());
+        response.ed25519_signature = hex::encode(signature.to_bytes());
+        response
+    }
+
+    /// Canonical JSON covered by the Attester's signature.
+    pub fn data_to_sign(&self) -> String {
+        serde_json::json!({
+            "nonce": hex::encode(&self.nonce_echo),
+            "chain_head": self.chain_head_hash,
+            "timestamp": self.response_timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            "index": self.current_breadcrumb_index,
+        })
+        .to_string()
+    }
+
+    /// Verify the signature against the Attester's public key (hex).
+    pub fn verify_signature(&self, identity_key: &str) -> Result<()> {
+        let invalid = || TripError::SignatureInvalid { index: self.current_breadcrumb_index };
+
+        let key_bytes: [u8; 32] = decode_hex_field(identity_key, "identity_key")?;
+        let sig_bytes: [u8; 64] = decode_hex_field(&self.ed25519_signature, "ed25519_signature")?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| invalid())?;
+        key.verify(self.data_to_sign().as_bytes(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| invalid())
+    }
+}
+
 /// Active Verification session state (held by the Verifier).
 pub struct VerificationSession {
     pub request: VerificationRequest,
     pub challenge: LivenessChallenge,
     pub state: SessionState,
     pub created_at: DateTime<Utc>,
+    /// Check the response signature against the request's identity key
+    pub verify_signature: bool,
+    /// Extra time (seconds) after the deadline for an acknowledged
+    /// challenge; 0 disables the extension. Private so it can't be set
+    /// past MAX_DEADLINE_EXTENSION_SECONDS.
+    deadline_extension_seconds: u64,
+    /// When the Attester's in-time `ChallengeAck` was accepted
+    acknowledged_at: Option<DateTime<Utc>>,
+    /// Time source for the challenge and its deadline
+    clock: Arc<dyn Clock>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -95,38 +330,139 @@ pub enum SessionState {
     /// Certificate produced
     Complete,
     /// Deadline expired or verification failed
-    Failed(String),
+    Failed(FailureReason),
+}
+
+/// Why a session failed, so a Relying Party can branch on it (e.g.
+/// retry an expired challenge, but not a forged nonce).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FailureReason {
+    /// The Attester answered after the challenge deadline
+    DeadlineExpired,
+    /// The response didn't echo the challenge nonce
+    NonceMismatch,
+    /// The response signature didn't verify
+    SignatureInvalid,
+    /// The response or certificate was seen before
+    ReplayDetected,
+    /// The PSD exponent α fell outside the biological band
+    AlphaOutOfRange,
+    /// α was in band, but the PSD fit's R² was below the configured
+    /// minimum: the spectrum is too noisy to trust the slope
+    PoorSpectralFit,
+    /// Any other failure, with a free-form description
+    Other(String),
 }
 
 impl VerificationSession {
     /// Create a new session from a Relying Party request.
     pub fn new(request: VerificationRequest) -> Self {
-        let challenge = LivenessChallenge::from_request(&request);
+        Self::new_with_clock(request, Arc::new(SystemClock))
+    }
+
+    /// Create a session whose challenge and deadline follow `clock`.
+    pub fn new_with_clock(request: VerificationRequest, clock: Arc<dyn Clock>) -> Self {
+        let challenge = LivenessChallenge::from_request_with_clock(&request, clock.as_ref());
         Self {
             request,
             challenge,
             state: SessionState::AwaitingResponse,
-            created_at: Utc::now(),
+            created_at: clock.now(),
+            verify_signature: false,
+            deadline_extension_seconds: 0,
+            acknowledged_at: None,
+            clock,
         }
     }
 
+    /// Create a session only if `policy` accepts the request.
+    pub fn authorized(request: VerificationRequest, policy: &RelyingPartyPolicy) -> Result<Self> {
+        policy.check(&request)?;
+        Ok(Self::new(request))
+    }
+
+    /// Require a valid Attester signature on the liveness response.
+    pub fn with_signature_verification(mut self) -> Self {
+        self.verify_signature = true;
+        self
+    }
+
+    /// Allow one deadline extension of `seconds` (capped at
+    /// MAX_DEADLINE_EXTENSION_SECONDS) for an acknowledged challenge.
+    pub fn with_deadline_extension(mut self, seconds: u64) -> Self {
+        self.deadline_extension_seconds = seconds.min(MAX_DEADLINE_EXTENSION_SECONDS);
+        self
+    }
+
+    /// Extra time (seconds) granted after the deadline once the
+    /// challenge is acknowledged; 0 if the extension is disabled.
+    pub fn deadline_extension_seconds(&self) -> u64 {
+        self.deadline_extension_seconds
+    }
+
+    /// When the Attester's in-time `ChallengeAck` was accepted.
+    pub fn acknowledged_at(&self) -> Option<DateTime<Utc>> {
+        self.acknowledged_at
+    }
+
+    /// The deadline in force: the challenge's own, or the extended one
+    /// once the challenge was acknowledged.
+    pub fn deadline(&self) -> DateTime<Utc> {
+        match self.acknowledged_at {
+            Some(_) => {
+                self.challenge.deadline() + Duration::seconds(self.deadline_extension_seconds as i64)
+            }
+            None => self.challenge.deadline(),
+        }
+    }
+
+    /// Record the Attester's acknowledgment of the challenge. Only an
+    /// ack received before the original deadline counts; a repeat ack
+    /// keeps the first one's time and extends nothing further. The
+    /// ack's own timestamp must also fall between the challenge and
+    /// its deadline, give or take DEFAULT_CLOCK_SKEW_SECONDS.
+    pub fn acknowledge(&mut self, ack: &ChallengeAck) -> Result<()> {
+        if self.challenge.is_expired_with_clock(self.clock.as_ref()) {
+            return Err(TripError::DeadlineExpired);
+        }
+        if ack.nonce_echo != self.challenge.nonce {
+            return Err(TripError::NonceMismatch);
+        }
+        let skew = Duration::seconds(DEFAULT_CLOCK_SKEW_SECONDS as i64);
+        let window = (self.challenge.challenge_timestamp - skew)..=(self.challenge.deadline() + skew);
+        if !window.contains(&ack.ack_timestamp) {
+            return Err(TripError::UnauthorizedRequest(format!(
+                "challenge ack stamped {} is outside the session window", ack.ack_timestamp
+            )));
+        }
+        if self.verify_signature {
+            ack.verify_signature(&self.request.identity_key)?;
+        }
+        self.acknowledged_at.get_or_insert(self.clock.now());
+        Ok(())
+    }
+
     /// Validate the Attester's liveness response.
     pub fn validate_response(&mut self, response: &LivenessResponse) -> Result<()> {
-        // Check deadline
-        if self.challenge.is_expired() {
-            self.state = SessionState::Failed("Deadline expired".to_string());
+        // Check deadline, extended if the challenge was acknowledged
+        if self.clock.now() > self.deadline() {
+            self.state = SessionState::Failed(FailureReason::DeadlineExpired);
             return Err(TripError::DeadlineExpired);
         }
 
         // Check nonce match
         if response.nonce_echo != self.challenge.nonce {
-            self.state = SessionState::Failed("Nonce mismatch".to_string());
+            self.state = SessionState::Failed(FailureReason::NonceMismatch);
             return Err(TripError::NonceMismatch);
         }
 
-        // TODO: Verify Ed25519 signature over the response
-        // using the identity_key from the original request.
-        // Requires: ed25519_dalek signature verification.
+        // Check the Attester's signature, if required
+        if self.verify_signature {
+            if let Err(e) = response.verify_signature(&self.request.identity_key) {
+                self.state = SessionState::Failed(FailureReason::SignatureInvalid);
+                return Err(e);
+            }
+        }
 
         self.state = SessionState::Evaluating;
         Ok(())
@@ -138,7 +474,7 @@ impl VerificationSession {
     }
 
     /// Mark the session as failed.
-    pub fn fail(&mut self, reason: String) {
+    pub fn fail(&mut self, reason: FailureReason) {
         self.state = SessionState::Failed(reason);
     }
 }
@@ -146,6 +482,8 @@ impl VerificationSession {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use crate::clock::FixedClock;
 
     #[test]
     fn test_verification_flow() {
@@ -185,5 +523,229 @@ mod tests {
         };
 
         assert!(session.validate_response(&bad_response).is_err());
+        assert_eq!(session.state, SessionState::Failed(FailureReason::NonceMismatch));
+    }
+
+    #[test]
+    fn test_expired_deadline_reason() {
+        let request = VerificationRequest::new("abc123".to_string());
+        let mut session = VerificationSession::new(request);
+        session.challenge.challenge_timestamp -= Duration::seconds(60);
+
+        let response = LivenessResponse {
+            nonce_echo: session.challenge.nonce.clone(),
+            chain_head_hash: "deadbeef".repeat(8),
+            response_timestamp: Utc::now(),
+            current_breadcrumb_index: 500,
+            ed25519_signature: "sig".to_string(),
+        };
+
+        assert!(matches!(session.validate_response(&response), Err(TripError::DeadlineExpired)));
+        assert_eq!(session.state, SessionState::Failed(FailureReason::DeadlineExpired));
+    }
+
+    #[test]
+    fn test_deadline_follows_session_clock() {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(start));
+        let request = VerificationRequest::new("abc123".to_string());
+        let respond = |session: &VerificationSession| LivenessResponse {
+            nonce_echo: session.challenge.nonce.clone(),
+            chain_head_hash: "deadbeef".repeat(8),
+            response_timestamp: clock.now(),
+            current_breadcrumb_index: 500,
+            ed25519_signature: "sig".to_string(),
+        };
+
+        // Exactly at the deadline is still in time
+        let mut session = VerificationSession::new_with_clock(request.clone(), clock.clone());
+        assert_eq!(session.created_at, start);
+        clock.advance(Duration::seconds(DEFAULT_DEADLINE_SECONDS as i64));
+        assert!(!session.challenge.is_expired_with_clock(clock.as_ref()));
+        session.validate_response(&respond(&session)).unwrap();
+
+        // One second later it isn't
+        clock.set(start);
+        let mut session = VerificationSession::new_with_clock(request, clock.clone());
+        clock.advance(Duration::seconds(DEFAULT_DEADLINE_SECONDS as i64 + 1));
+        assert!(session.challenge.is_expired_with_clock(clock.as_ref()));
+        assert!(matches!(session.validate_response(&respond(&session)), Err(TripError::DeadlineExpired)));
+    }
+
+    #[test]
+    fn test_acknowledged_challenge_gets_one_extension() {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(start));
+        let attester = SigningKey::from_bytes(&[6u8; 32]);
+        let request = VerificationRequest::new(hex::encode(attester.verifying_key().to_bytes()));
+        let deadline = Duration::seconds(DEFAULT_DEADLINE_SECONDS as i64);
+        let new_session = || {
+            VerificationSession::new_with_clock(request.clone(), clock.clone())
+                .with_signature_verification()
+                .with_deadline_extension(20)
+        };
+        let respond = |session: &VerificationSession| LivenessResponse::create_signed_with_clock(
+            &session.challenge, &attester, "deadbeef".repeat(8), 500, clock.as_ref(),
+        );
+
+        // Acknowledged in time, answered 15 s late: accepted
+        let mut session = new_session();
+        clock.advance(Duration::seconds(2));
+        let ack = ChallengeAck::create_signed_with_clock(&session.challenge, &attester, clock.as_ref());
+        session.acknowledge(&ack).unwrap();
+        clock.set(start + deadline + Duration::seconds(15));
+        session.validate_response(&respond(&session)).unwrap();
+        assert_eq!(session.state, SessionState::Evaluating);
+
+        // Past the extended deadline: rejected, and a second ack
+        // doesn't move it
+        clock.set(start);
+        let mut session = new_session();
+        session.acknowledge(&ack).unwrap();
+        session.acknowledge(&ack).unwrap();
+        assert_eq!(session.deadline(), start + deadline + Duration::seconds(20));
+        clock.set(start + deadline + Duration::seconds(21));
+        assert!(matches!(session.validate_response(&respond(&session)), Err(TripError::DeadlineExpired)));
+        assert_eq!(session.state, SessionState::Failed(FailureReason::DeadlineExpired));
+
+        // An ack after the original deadline buys nothing
+        clock.set(start);
+        let mut session = new_session();
+        clock.set(start + deadline + Duration::seconds(1));
+        assert!(matches!(session.acknowledge(&ack), Err(TripError::DeadlineExpired)));
+        assert!(session.validate_response(&respond(&session)).is_err());
+
+        // Forged or mismatched acks are refused; the extension is capped
+        clock.set(start);
+        let mut session = new_session();
+        let stranger = SigningKey::from_bytes(&[7u8; 32]);
+        let forged = ChallengeAck::create_signed_with_clock(&session.challenge, &stranger, clock.as_ref());
+        assert!(session.acknowledge(&forged).is_err());
+        let mut wrong_nonce = ack.clone();
+        wrong_nonce.nonce_echo = vec![0u8; 16];
+        assert!(matches!(session.acknowledge(&wrong_nonce), Err(TripError::NonceMismatch)));
+        assert_eq!(session.acknowledged_at(), None);
+        let session = new_session().with_deadline_extension(3600);
+        assert_eq!(session.deadline_extension_seconds(), MAX_DEADLINE_EXTENSION_SECONDS);
+        assert_eq!(session.deadline(), start + deadline);
+
+        // Acks stamped outside the session window are refused, even
+        // when received in time
+        let mut session = new_session();
+        let skew = Duration::seconds(DEFAULT_CLOCK_SKEW_SECONDS as i64);
+        for stamped in [start - skew - Duration::seconds(1), start + deadline + skew + Duration::seconds(1)] {
+            clock.set(stamped);
+            let stale = ChallengeAck::create_signed_with_clock(&session.challenge, &attester, clock.as_ref());
+            clock.set(start + Duration::seconds(1));
+            assert!(matches!(session.acknowledge(&stale), Err(TripError::UnauthorizedRequest(_))));
+        }
+        assert_eq!(session.acknowledged_at(), None);
+        clock.set(start - skew);
+        let early = ChallengeAck::create_signed_with_clock(&session.challenge, &attester, clock.as_ref());
+        clock.set(start + Duration::seconds(1));
+        session.acknowledge(&early).unwrap();
+        assert_eq!(session.acknowledged_at(), Some(start + Duration::seconds(1)));
+    }
+
+    #[test]
+    fn test_fail_records_reason() {
+        let request = VerificationRequest::new("abc123".to_string());
+        let mut session = VerificationSession::new(request);
+        session.fail(FailureReason::ReplayDetected);
+        assert_eq!(session.state, SessionState::Failed(FailureReason::ReplayDetected));
+
+        session.fail(FailureReason::Other("engine error".to_string()));
+        assert_eq!(session.state, SessionState::Failed(FailureReason::Other("engine error".to_string())));
+    }
+
+    #[test]
+    fn test_signed_response_validates() {
+        let attester = SigningKey::from_bytes(&[6u8; 32]);
+        let identity_key = hex::encode(attester.verifying_key().to_bytes());
+        let request = VerificationRequest::new(identity_key);
+        let mut session = VerificationSession::new(request.clone()).with_signature_verification();
+
+        let response = LivenessResponse::create_signed(
+            &session.challenge, &attester, "deadbeef".repeat(8), 500,
+        );
+        session.validate_response(&response).unwrap();
+        assert_eq!(session.state, SessionState::Evaluating);
+
+        // Placeholder, forged and tampered signatures are rejected
+        let mut placeholder = response.clone();
+        placeholder.ed25519_signature = "sig".to_string();
+        let mut session = VerificationSession::new(request.clone()).with_signature_verification();
+        placeholder.nonce_echo = session.challenge.nonce.clone();
+        assert!(session.validate_response(&placeholder).is_err());
+
+        let stranger = SigningKey::from_bytes(&[7u8; 32]);
+        let mut session = VerificationSession::new(request.clone()).with_signature_verification();
+        let forged = LivenessResponse::create_signed(
+            &session.challenge, &stranger, "deadbeef".repeat(8), 500,
+        );
+        assert!(matches!(
+            session.validate_response(&forged),
+            Err(TripError::SignatureInvalid { index: 500 })
+        ));
+        assert_eq!(session.state, SessionState::Failed(FailureReason::SignatureInvalid));
+
+        let mut session = VerificationSession::new(request).with_signature_verification();
+        let mut tampered = LivenessResponse::create_signed(
+            &session.challenge, &attester, "deadbeef".repeat(8), 500,
+        );
+        tampered.current_breadcrumb_index = 501;
+        assert!(session.validate_response(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_rp_signed_requests() {
+        let rp = SigningKey::from_bytes(&[8u8; 32]);
+        let rp_key = hex::encode(rp.verifying_key().to_bytes());
+        let policy = RelyingPartyPolicy::restricted([rp_key.to_uppercase()]);
+        let identity_key = "ab".repeat(32);
+
+        // Authorized
+        let request = VerificationRequest::new(identity_key.clone()).signed_by(&rp);
+        assert_eq!(request.rp_public_key.as_deref(), Some(rp_key.as_str()));
+        let session = VerificationSession::authorized(request.clone(), &policy).unwrap();
+        assert_eq!(session.state, SessionState::AwaitingResponse);
+
+        // Validly signed, but by an RP that isn't on the list
+        let stranger = SigningKey::from_bytes(&[9u8; 32]);
+        let unknown = VerificationRequest::new(identity_key.clone()).signed_by(&stranger);
+        unknown.verify_rp_signature().unwrap();
+        assert!(matches!(policy.check(&unknown), Err(TripError::UnauthorizedRequest(_))));
+
+        // Unsigned
+        let unsigned = VerificationRequest::new(identity_key.clone());
+        assert!(matches!(
+            VerificationSession::authorized(unsigned.clone(), &policy),
+            Err(TripError::UnauthorizedRequest(_))
+        ));
+
+        // Malformed RP key hex is reported as hex, not as a certificate
+        let mut malformed = request.clone();
+        malformed.rp_public_key = Some(rp_key[..63].to_string());
+        assert!(matches!(
+            malformed.verify_rp_signature(),
+            Err(TripError::InvalidHex(msg)) if msg.starts_with("rp_public_key")
+        ));
+
+        // Tampered nonce
+        let mut tampered = request.clone();
+        tampered.nonce[0] ^= 1;
+        assert!(matches!(policy.check(&tampered), Err(TripError::UnauthorizedRequest(_))));
+
+        // Open deployments still take unsigned requests
+        let open = RelyingPartyPolicy::default();
+        open.check(&unsigned).unwrap();
+        open.check(&request).unwrap();
+        assert!(open.check(&tampered).is_err());
+
+        // Unsigned requests keep their old wire format
+        let json = serde_json::to_string(&unsigned).unwrap();
+        assert!(!json.contains("rp_"));
+        let roundtrip: VerificationRequest = serde_json::from_str(&json).unwrap();
+        assert!(roundtrip.rp_signature.is_none());
     }
 }
@@ -0,0 +1,203 @@
+// trip-verifier/src/waiting.rs
+//
+// Inter-event Waiting Times
+// ==========================
+//
+// How long a person stays put before moving again is heavy-tailed:
+// mostly short stops, occasionally a very long one (Song et al. 2010,
+// P(Δt) ∝ Δt^(-γ)). A bot that moves on a Poisson clock waits an
+// exponentially distributed time instead. This is a temporal signal
+// independent of the PSD, which looks at displacement correlations.
+//
+// Implementation:
+// 1. A move is a non-zero displacement; the waiting time is the sum of
+//    the intervals since the previous move (the wait before the first
+//    move is censored and dropped)
+// 2. Fit γ by maximum likelihood on waits ≥ t_min (continuous Pareto)
+// 3. Fit a shifted exponential on the same waits
+// 4. Compare the two with Vuong's normalized log-likelihood ratio
+//    (Clauset, Shalizi, Newman 2009): clearly positive → heavy-tailed,
+//    clearly negative → exponential
+
+use serde::Serialize;
+use crate::error::{TripError, Result};
+
+/// |Vuong statistic| needed to prefer one model (two-sided 5%).
+pub const VUONG_THRESHOLD: f64 = 1.96;
+
+/// Result of fitting the waiting-time distribution.
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingTimeResult {
+    /// Power-law exponent γ of P(Δt) ∝ Δt^(-γ)
+    pub exponent: f64,
+
+    /// Smallest waiting time included in the fit (seconds)
+    pub t_min_seconds: f64,
+
+    /// Vuong statistic: normalized log-likelihood ratio of the power
+    /// law over the exponential. Positive favors the power law.
+    pub likelihood_ratio: f64,
+
+    /// Number of waiting times used in the fit
+    pub n_samples: usize,
+
+    /// Classification
+    pub classification: WaitingTimeClassification,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum WaitingTimeClassification {
+    /// Power law fits significantly better (human-like bursts)
+    HeavyTailed,
+    /// Exponential fits significantly better (constant-rate process)
+    Exponential,
+    /// Neither model is significantly better
+    Inconclusive,
+}
+
+impl WaitingTimeClassification {
+    pub fn from_likelihood_ratio(ratio: f64) -> Self {
+        if ratio > VUONG_THRESHOLD {
+            Self::HeavyTailed
+        } else if ratio < -VUONG_THRESHOLD {
+            Self::Exponential
+        } else {
+            Self::Inconclusive
+        }
+    }
+
+    pub fn is_heavy_tailed(&self) -> bool {
+        matches!(self, Self::HeavyTailed)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::HeavyTailed => "heavy_tailed",
+            Self::Exponential => "exponential",
+            Self::Inconclusive => "inconclusive",
+        }
+    }
+}
+
+/// Waiting times (seconds) between moves, from a displacement series
+/// and the intervals it was observed over.
+pub fn waiting_times(displacement_km: &[f64], interval_seconds: &[f64]) -> Vec<f64> {
+    let mut waits = Vec::new();
+    let mut elapsed = 0.0;
+    let mut moved = false;
+    for (&d, &dt) in displacement_km.iter().zip(interval_seconds) {
+        elapsed += dt;
+        if d > 0.0 {
+            if moved {
+                waits.push(elapsed);
+            }
+            moved = true;
+            elapsed = 0.0;
+        }
+    }
+    waits
+}
+
+/// Fit the waiting-time distribution above `t_min_seconds`.
+///
+/// Fails with `WaitingTimeError` on fewer than 20 waits at or above
+/// `t_min_seconds`, or if they are all equal to it.
+pub fn fit_waiting_times(waits: &[f64], t_min_seconds: f64) -> Result<WaitingTimeResult> {
+    let valid: Vec<f64> = waits.iter()
+        .filter(|&&t| t >= t_min_seconds && t.is_finite())
+        .copied()
+        .collect();
+
+    if valid.len() < 20 {
+        return Err(TripError::WaitingTimeError(
+            format!("Need at least 20 waiting times above t_min={t_min_seconds}s, got {}", valid.len())
+        ));
+    }
+    let n = valid.len() as f64;
+
+    // --- Step 1: Power law, γ = 1 + n / Σ ln(t / t_min) ---
+    let sum_log: f64 = valid.iter().map(|&t| (t / t_min_seconds).ln()).sum();
+    let sum_excess: f64 = valid.iter().map(|&t| t - t_min_seconds).sum();
+    if sum_log <= 0.0 || sum_excess <= 0.0 {
+        return Err(TripError::WaitingTimeError(
+            "All waiting times equal to t_min".to_string()
+        ));
+    }
+    let exponent = 1.0 + n / sum_log;
+
+    // --- Step 2: Shifted exponential, λ = 1 / mean(t - t_min) ---
+    let rate = n / sum_excess;
+
+    // --- Step 3: Vuong test on per-sample log-likelihood differences ---
+    let diffs: Vec<f64> = valid.iter()
+        .map(|&t| {
+            let log_power = (exponent - 1.0).ln() - t_min_seconds.ln()
+                - exponent * (t / t_min_seconds).ln();
+            let log_exp = rate.ln() - rate * (t - t_min_seconds);
+            log_power - log_exp
+        })
+        .collect();
+    let mean = diffs.iter().sum::<f64>() / n;
+    let variance = diffs.iter().map(|&d| (d - mean).powi(2)).sum::<f64>() / n;
+    let likelihood_ratio = if variance > 0.0 {
+        n.sqrt() * mean / variance.sqrt()
+    } else {
+        0.0
+    };
+
+    Ok(WaitingTimeResult {
+        exponent,
+        t_min_seconds,
+        likelihood_ratio,
+        n_samples: valid.len(),
+        classification: WaitingTimeClassification::from_likelihood_ratio(likelihood_ratio),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_heavy_tailed_waits() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let t_min = 600.0;
+        let gamma_true = 1.8;
+
+        // Inverse CDF for Pareto: t = t_min · u^(-1/(γ-1))
+        let waits: Vec<f64> = (0..400)
+            .map(|_| t_min * rng.gen_range(1e-6..1.0f64).powf(-1.0 / (gamma_true - 1.0)))
+            .collect();
+
+        let result = fit_waiting_times(&waits, t_min).unwrap();
+        assert!((result.exponent - gamma_true).abs() < 0.15, "γ = {}", result.exponent);
+        assert_eq!(result.classification, WaitingTimeClassification::HeavyTailed);
+    }
+
+    #[test]
+    fn test_constant_rate_waits() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let t_min = 600.0;
+
+        // Poisson process: exponential waits with a 30 min mean above t_min
+        let waits: Vec<f64> = (0..400)
+            .map(|_| t_min - 1800.0 * rng.gen_range(1e-12..1.0f64).ln())
+            .collect();
+
+        let result = fit_waiting_times(&waits, t_min).unwrap();
+        assert_eq!(result.classification, WaitingTimeClassification::Exponential);
+        assert!(result.likelihood_ratio < -VUONG_THRESHOLD);
+    }
+
+    #[test]
+    fn test_waiting_times_from_series() {
+        // Moves end intervals 2, 3 and 5; the wait before the first is censored
+        let displacements = [0.0, 1.0, 2.0, 0.0, 0.5, 0.0];
+        let intervals = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(waiting_times(&displacements, &intervals), vec![3.0, 9.0]);
+
+        assert!(matches!(fit_waiting_times(&[900.0; 10], 600.0), Err(TripError::WaitingTimeError(_))));
+        assert!(matches!(fit_waiting_times(&[600.0; 30], 600.0), Err(TripError::WaitingTimeError(_))));
+    }
+}
@@ -0,0 +1,24 @@
+// The analyze binary on the checked-in NDJSON export
+// (tests/fixtures/signed_chain.ndjson): the certificate goes next to
+// the export, which is left as it was.
+
+use std::fs;
+use std::process::Command;
+
+const NDJSON: &str = include_str!("fixtures/signed_chain.ndjson");
+
+#[test]
+fn ndjson_export_is_left_untouched() {
+    let dir = std::env::temp_dir().join(format!("trip-analyze-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let export = dir.join("chain.ndjson");
+    fs::write(&export, NDJSON).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_analyze")).arg(&export).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    assert_eq!(fs::read_to_string(&export).unwrap(), NDJSON);
+    let certificate = fs::read_to_string(dir.join("chain_poh.json")).unwrap();
+    assert!(certificate.contains("\"identity_key\""));
+    fs::remove_dir_all(&dir).unwrap();
+}